@@ -256,6 +256,10 @@ impl Handle {
     self.state().wallets.clone()
   }
 
+  pub fn watch_only_wallets(&self) -> BTreeSet<String> {
+    self.state().watch_only_wallets.clone()
+  }
+
   #[track_caller]
   pub fn mine_blocks(&self, n: u64) -> Vec<Block> {
     self.mine_blocks_with_subsidy(n, 50 * COIN_VALUE)
@@ -240,11 +240,36 @@ impl Api for Server {
     &self,
     txid: Txid,
     vout: u32,
-    _include_mempool: Option<bool>,
+    include_mempool: Option<bool>,
   ) -> Result<Option<GetTxOutResult>, jsonrpc_core::Error> {
     let state = self.state();
 
     let Some(value) = state.utxos.get(&OutPoint { txid, vout }) else {
+      if include_mempool.unwrap_or(true)
+        && let Some(output) = state
+          .mempool
+          .iter()
+          .find(|tx| tx.compute_txid() == txid)
+          .and_then(|tx| tx.output.get(usize::try_from(vout).unwrap()))
+      {
+        return Ok(Some(GetTxOutResult {
+          bestblock: BlockHash::all_zeros(),
+          coinbase: false,
+          confirmations: 0,
+          script_pub_key: GetRawTransactionResultVoutScriptPubKey {
+            asm: String::new(),
+            hex: output.script_pubkey.to_bytes(),
+            req_sigs: None,
+            type_: None,
+            addresses: Vec::new(),
+            address: Address::from_script(&output.script_pubkey, state.network)
+              .ok()
+              .map(|addr| addr.into_unchecked()),
+          },
+          value: output.value,
+        }));
+      }
+
       return Ok(None);
     };
 
@@ -283,7 +308,9 @@ impl Api for Server {
   }
 
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
-    if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
+    let state = self.state();
+
+    if let Some(wallet_name) = state.loaded_wallets.first().cloned() {
       Ok(GetWalletInfoResult {
         avoid_reuse: None,
         balance: Amount::from_sat(0),
@@ -293,7 +320,7 @@ impl Api for Server {
         keypool_size: 0,
         keypool_size_hd_internal: 0,
         pay_tx_fee: Amount::from_sat(0),
-        private_keys_enabled: true,
+        private_keys_enabled: !state.watch_only_wallets.contains(&wallet_name),
         scanning: None,
         tx_count: 0,
         unconfirmed_balance: Amount::from_sat(0),
@@ -343,12 +370,19 @@ impl Api for Server {
   fn create_wallet(
     &self,
     name: String,
-    _disable_private_keys: Option<bool>,
+    disable_private_keys: Option<bool>,
     _blank: Option<bool>,
     _passphrase: Option<String>,
     _avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error> {
-    self.state().wallets.insert(name.clone());
+    let mut state = self.state();
+
+    state.wallets.insert(name.clone());
+
+    if disable_private_keys.unwrap_or_default() {
+      state.watch_only_wallets.insert(name.clone());
+    }
+
     Ok(LoadWalletResult {
       name,
       warning: None,
@@ -406,7 +440,12 @@ impl Api for Server {
 
     if (output_value + estimated_fee) > input_value {
       for (value, outpoint) in utxos {
-        if state.locked.contains(&outpoint) {
+        if state.locked.contains(&outpoint)
+          || transaction
+            .input
+            .iter()
+            .any(|txin| txin.previous_output == outpoint)
+        {
           continue;
         }
 
@@ -644,39 +683,55 @@ impl Api for Server {
     let state = self.state();
 
     let Some(tx) = state.transactions.get(&txid) else {
-      return Err(jsonrpc_core::Error::new(
-        jsonrpc_core::types::error::ErrorCode::ServerError(-8),
-      ));
+      return Err(jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(-5),
+        message: "Invalid or non-wallet transaction id".into(),
+        data: None,
+      });
     };
 
-    let mut confirmations = None;
-
-    'outer: for (height, hash) in state.hashes.iter().enumerate() {
-      for tx in &state.blocks[hash].txdata {
-        if tx.compute_txid() == txid {
-          confirmations = Some(state.hashes.len() - height);
-          break 'outer;
-        }
-      }
-    }
+    let details = tx
+      .output
+      .iter()
+      .enumerate()
+      .filter_map(|(vout, output)| {
+        let address = Address::from_script(&output.script_pubkey, state.network).ok()?;
+
+        state
+          .is_wallet_address(&address)
+          .then(|| GetTransactionResultDetail {
+            address: Some(address.into_unchecked()),
+            category: if tx.is_coinbase() {
+              GetTransactionResultDetailCategory::Generate
+            } else {
+              GetTransactionResultDetailCategory::Receive
+            },
+            amount: output.value.to_signed().unwrap(),
+            label: None,
+            vout: vout.try_into().unwrap(),
+            fee: None,
+            abandoned: None,
+          })
+      })
+      .collect();
 
     Ok(
       serde_json::to_value(GetTransactionResult {
         info: WalletTxInfo {
           txid,
-          confirmations: confirmations.unwrap().try_into().unwrap(),
+          confirmations: state.get_confirmations(tx),
           time: 0,
           timereceived: 0,
           blockhash: None,
           blockindex: None,
-          blockheight: None,
+          blockheight: state.txid_to_block_height.get(&txid).copied(),
           blocktime: None,
           wallet_conflicts: Vec::new(),
           bip125_replaceable: Bip125Replaceable::Unknown,
         },
         amount: SignedAmount::from_sat(0),
         fee: None,
-        details: Vec::new(),
+        details,
         hex: serialize(tx),
       })
       .unwrap(),
@@ -909,8 +964,6 @@ impl Api for Server {
     unlock: bool,
     outputs: Vec<JsonOutPoint>,
   ) -> Result<bool, jsonrpc_core::Error> {
-    assert!(!unlock);
-
     let mut state = self.state();
 
     if state.fail_lock_unspent {
@@ -922,7 +975,12 @@ impl Api for Server {
         vout: output.vout,
         txid: output.txid,
       };
-      assert!(state.locked.insert(output));
+
+      if unlock {
+        assert!(state.locked.remove(&output));
+      } else {
+        assert!(state.locked.insert(output));
+      }
     }
 
     Ok(true)
@@ -987,12 +1045,21 @@ impl Api for Server {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
-    assert!(sighash_type.is_none());
     assert!(bip32derivs.is_none());
 
+    let sighash_type = sighash_type.map(|sighash_type| match sighash_type.as_str() {
+      "ALL" => 0x01,
+      "NONE" => 0x02,
+      "SINGLE" => 0x03,
+      "ALL|ANYONECANPAY" => 0x81,
+      "NONE|ANYONECANPAY" => 0x82,
+      "SINGLE|ANYONECANPAY" => 0x83,
+      _ => panic!("unsupported sighash type: {sighash_type}"),
+    });
+
     let mut psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
@@ -1022,8 +1089,10 @@ impl Api for Server {
         )
         .unwrap();
 
-        if self.state().is_wallet_address(&address) {
-          input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
+        if input.final_script_witness.is_none() && self.state().is_wallet_address(&address) {
+          let mut signature = vec![0; 64];
+          signature.extend(sighash_type);
+          input.final_script_witness = Some(Witness::from_slice(&[signature]));
         }
       }
     }
@@ -1041,16 +1110,19 @@ impl Api for Server {
   ) -> Result<FinalizePsbtResult, jsonrpc_core::Error> {
     assert!(extract.is_none());
 
-    let mut transaction = Psbt::deserialize(
+    let psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
         .unwrap(),
     )
-    .unwrap()
-    .unsigned_tx;
+    .unwrap();
 
-    for input in &mut transaction.input {
-      if input.witness.is_empty() {
+    let mut transaction = psbt.unsigned_tx;
+
+    for (input, psbt_input) in transaction.input.iter_mut().zip(psbt.inputs) {
+      if let Some(witness) = psbt_input.final_script_witness {
+        input.witness = witness;
+      } else if input.witness.is_empty() {
         input.witness = Witness::from_slice(&[&[0; 64]]);
       }
     }
@@ -1084,7 +1156,7 @@ impl Api for Server {
 
         let txout = &tx.output[usize::try_from(input.previous_output.vout).unwrap()];
 
-        let address = Address::from_script(&txout.script_pubkey, Network::Bitcoin).unwrap();
+        let address = Address::from_script(&txout.script_pubkey, self.network).unwrap();
 
         if self.state().is_wallet_address(&address) {
           balance_change -= i64::try_from(txout.value.to_sat()).unwrap();
@@ -1092,8 +1164,9 @@ impl Api for Server {
       }
 
       for output in tx.output {
-        let address = Address::from_script(&output.script_pubkey, Network::Bitcoin).unwrap();
-        if self.state().is_wallet_address(&address) {
+        if let Ok(address) = Address::from_script(&output.script_pubkey, self.network)
+          && self.state().is_wallet_address(&address)
+        {
           balance_change += i64::try_from(output.value.to_sat()).unwrap();
         }
       }
@@ -19,6 +19,7 @@ pub struct State {
   pub version: usize,
   pub wallet: Wallet,
   pub wallets: BTreeSet<String>,
+  pub watch_only_wallets: BTreeSet<String>,
 }
 
 impl State {
@@ -49,6 +50,7 @@ impl State {
       version,
       wallet: Wallet::new(network),
       wallets: BTreeSet::new(),
+      watch_only_wallets: BTreeSet::new(),
     }
   }
 
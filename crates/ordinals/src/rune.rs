@@ -8,11 +8,15 @@ pub struct Rune(pub u128);
 impl Rune {
   pub const RESERVED: u128 = 6402364363415443603228541259936211926;
 
-  const UNLOCKED: usize = 12;
+  /// Number of name lengths which unlock over the course of the unlock
+  /// schedule, after which names of any length may be etched.
+  pub const UNLOCKED: usize = 12;
 
   const UNLOCK_INTERVAL: u32 = SUBSIDY_HALVING_INTERVAL / 12;
 
-  const STEPS: &'static [u128] = &[
+  /// Values of the first name of each length, such that names of length `n`
+  /// are in `STEPS[n - 1]..STEPS[n]`.
+  pub const STEPS: &'static [u128] = &[
     0,
     26,
     702,
@@ -1,16 +1,24 @@
 use {
   super::*,
+  crate::index::event::Event,
   serde_hex::{SerHex, Strict},
 };
 
 pub use crate::{
   subcommand::decode::RawOutput as Decode,
   templates::{
-    BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
-    TransactionHtml as Transaction,
+    BlocksHtml as Blocks, RuneHtml as Rune, RunesCalendarHtml as RunesCalendar, RunesHtml as Runes,
+    StatusHtml as Status, TransactionHtml as Transaction,
   },
 };
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchInscription {
+  pub id: InscriptionId,
+  pub inscription: Option<InscriptionRecursive>,
+  pub metadata: Option<String>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub best_height: u32,
@@ -125,9 +133,21 @@ pub struct Inscription {
   pub sat: Option<ordinals::Sat>,
   pub satpoint: SatPoint,
   pub timestamp: i64,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub transfers: Option<Vec<InscriptionTransfer>>,
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct InscriptionTransfer {
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: SatPoint,
+  pub receiver: Option<String>,
+  pub sender: Option<String>,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<Charm>,
@@ -159,6 +179,26 @@ pub struct RelativeInscriptionRecursive {
   pub timestamp: i64,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventEntry {
+  pub cursor: u64,
+  pub event: Event,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Events {
+  pub cursor: Option<u64>,
+  pub events: Vec<EventEntry>,
+  pub more: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct FilteredInscriptions {
+  pub cursor: Option<u32>,
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Inscriptions {
   pub ids: Vec<InscriptionId>,
@@ -260,6 +300,113 @@ pub struct AddressInfo {
   pub runes_balances: Option<Vec<(SpacedRune, Decimal, Option<char>)>>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Metaprotocol {
+  pub count: u64,
+  pub name: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneActivity {
+  pub blocks: Vec<RuneBlockActivity>,
+  pub from: u32,
+  pub id: RuneId,
+  pub spaced_rune: SpacedRune,
+  pub to: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneBlockActivity {
+  pub burned: u128,
+  pub height: u32,
+  pub mints: u64,
+  pub receivers: u64,
+  pub transfers: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub balance: Pile,
+  pub outputs: u64,
+  pub script_pubkey: ScriptBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolders {
+  pub holders: Vec<RuneHolder>,
+  pub more: bool,
+  pub page_index: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressRuneHistory {
+  pub history: Vec<RuneHistory>,
+  pub more: bool,
+  pub page_index: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHistory {
+  pub height: u32,
+  pub outpoint: OutPoint,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub spent: Option<RuneSpend>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneMintable {
+  pub amount: Option<u128>,
+  pub height: u32,
+  pub mintable: bool,
+  pub reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneRecursive {
+  pub block: u64,
+  pub burned: u128,
+  pub divisibility: u8,
+  pub etching: Txid,
+  pub id: RuneId,
+  pub max_supply: u128,
+  pub mints: u128,
+  pub number: u64,
+  pub parent: Option<InscriptionId>,
+  pub premine: u128,
+  pub spaced_rune: SpacedRune,
+  pub supply: u128,
+  pub symbol: Option<char>,
+  pub terms: Option<Terms>,
+  pub timestamp: u64,
+  pub turbo: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneUnlockInterval {
+  pub end: u32,
+  pub end_time: DateTime<Utc>,
+  pub etched: u64,
+  pub length: usize,
+  pub minimum: ordinals::Rune,
+  pub names: u128,
+  pub start: u32,
+  pub start_time: DateTime<Utc>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneSpend {
+  pub height: u32,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+  pub cursor: Option<u32>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub more: bool,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Offers {
   pub offers: Vec<String>,
@@ -52,6 +52,8 @@ pub enum SnafuError {
   },
   #[snafu(display("Unrecognized outgoing: `{}`", input))]
   OutgoingParse { input: String },
+  #[snafu(display("Expected outpoint, inscription ID, or rune: `{}`", input))]
+  FrozenParse { input: String },
   #[snafu(display("Failed to parse decimal: {}", source))]
   RuneAmountParse { source: error::Error, input: String },
   #[snafu(display("Invalid chain `{}`", chain))]
@@ -76,6 +78,10 @@ pub enum SnafuError {
   },
   #[snafu(display("Unrecognized signer: `{}`", input))]
   SignerParse { input: String },
+  #[snafu(display("not enough cardinal utxos"))]
+  NotEnoughCardinalUtxos,
+  #[snafu(display("wallet contains no cardinal utxos"))]
+  NoCardinalUtxos,
 }
 
 impl From<Error> for SnafuError {
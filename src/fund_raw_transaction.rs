@@ -129,7 +129,7 @@ pub(crate) fn fund_raw_transaction(
             bitcoincore_rpc::jsonrpc::error::RpcError { code: -6, .. }
           ))
         ) {
-          anyhow!("not enough cardinal utxos")
+          Error::from(SnafuError::NotEnoughCardinalUtxos)
         } else {
           err.into()
         }
@@ -1,13 +1,14 @@
 use {
   self::{
     entry::{
-      Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      Entry, EventValue, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
+      InscriptionTransferValue, OfferEntry, OfferEntryValue, OutPointValue, RuneActivityValue,
+      RuneEntryValue, RuneHistoryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
     },
-    event::Event,
+    event::{Event, EventSender, EventSubscribers, EventSubscription},
     lot::Lot,
     reorg::Reorg,
-    updater::Updater,
+    updater::{BlockData, Updater},
     utxo_entry::{ParsedUtxoEntry, UtxoEntry, UtxoEntryBuf},
   },
   super::*,
@@ -35,43 +36,68 @@ use {
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    ops::{Range, RangeTo},
     sync::Once,
   },
 };
 
-pub use self::entry::RuneEntry;
+pub use self::{
+  entry::{RuneActivityEntry, RuneEntry},
+  inscription_filter::InscriptionFilter,
+};
 
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
+mod inscription_filter;
 mod lot;
 mod reorg;
 mod rtx;
+mod search;
 mod updater;
 mod utxo_entry;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 34;
+const MAX_SEQUENCE_NUMBERS_EXAMINED: usize = 100_000;
+const MAX_OFFERS: u64 = 100_000;
+const MAX_OFFERS_PER_INSCRIPTION: u64 = 100;
+const SCHEMA_VERSION: u64 = 44;
 
+define_multimap_table! { INSCRIPTION_ID_TO_OFFER_NUMBER, InscriptionIdValue, u64 }
 define_multimap_table! { LATEST_CHILD_SEQUENCE_NUMBER_TO_COLLECTION_SEQUENCE_NUMBER, u32, u32 }
+define_multimap_table! { OUTPOINT_TO_OFFER_NUMBER, &OutPointValue, u64 }
+define_multimap_table! { RUNE_ID_TO_HOLDER, RuneIdValue, (u128, u64, &[u8]) }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
 define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], OutPointValue }
+define_multimap_table! { SCRIPT_PUBKEY_TO_RUNE_OUTPOINT, &[u8], (u32, OutPointValue) }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_table! { COLLECTION_SEQUENCE_NUMBER_TO_LATEST_CHILD_SEQUENCE_NUMBER, u32, u32 }
+define_table! { CONTENT_TYPE_SEQUENCE_NUMBERS, (&str, u32), () }
 define_table! { GALLERY_SEQUENCE_NUMBERS, u32, () }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
+define_table! { INSCRIPTION_TRANSFER, (u32, u32), InscriptionTransferValue }
+define_table! { METAPROTOCOL_SEQUENCE_NUMBERS, (&str, u32), () }
+define_table! { METAPROTOCOL_TO_COUNT, &str, u64 }
+define_table! { NUMBER_TO_EVENT, u64, EventValue }
 define_table! { NUMBER_TO_OFFER, u64, &[u8] }
+define_table! { NUMBER_TO_OFFER_ENTRY, u64, OfferEntryValue }
+define_table! { OFFER_TXID_TO_NUMBER, &TxidValue, u64 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_RUNE_HISTORY, &OutPointValue, RuneHistoryValue }
+define_table! { OUTPOINT_TO_RUNE_SCRIPT_PUBKEY, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_UTXO_ENTRY, &OutPointValue, &UtxoEntry }
+define_table! { RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY, (RuneIdValue, u32), RuneActivityValue }
+define_table! { RUNE_ID_AND_SCRIPT_PUBKEY_TO_RUNE_HOLDING, (RuneIdValue, &[u8]), (u128, u64) }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEARCH_TERM_SEQUENCE_NUMBERS, (&str, u32), () }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
@@ -80,6 +106,14 @@ define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 
+#[derive(Debug, PartialEq)]
+pub(crate) enum OfferInsertion {
+  Duplicate,
+  Inserted,
+  TooMany,
+  TooManyForInscription,
+}
+
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
   Schema = 0,
@@ -99,6 +133,13 @@ pub(crate) enum Statistic {
   SatRanges = 14,
   UnboundInscriptions = 16,
   LastSavepointHeight = 17,
+  IndexEvents = 18,
+  IndexRuneHistory = 19,
+  IndexInscriptionHistory = 20,
+  IndexSearch = 21,
+  IndexFilters = 22,
+  IndexMetaprotocols = 23,
+  IndexRuneActivity = 24,
 }
 
 impl Statistic {
@@ -182,7 +223,8 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
           code: -5, message, ..
         },
       )))
-        if message.starts_with("No such mempool or blockchain transaction") =>
+        if message.starts_with("No such mempool or blockchain transaction")
+          || message.starts_with("Invalid or non-wallet transaction id") =>
       {
         Ok(None)
       }
@@ -202,14 +244,22 @@ pub struct Index {
   pub(crate) client: Client,
   database: Database,
   durability: redb::Durability,
-  event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  event_sender: Option<EventSender>,
+  event_subscribers: Option<EventSubscribers>,
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
   index_addresses: bool,
+  index_events: bool,
+  index_filters: bool,
+  index_inscription_history: bool,
   index_inscriptions: bool,
+  index_metaprotocols: bool,
+  index_rune_activity: bool,
+  index_rune_history: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
   index_transactions: bool,
   path: PathBuf,
   settings: Settings,
@@ -227,6 +277,20 @@ impl Index {
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   ) -> Result<Self> {
+    Self::open_with_sender(settings, event_sender.map(EventSender::Events))
+  }
+
+  /// Opens the index with an event sender which receives each event along
+  /// with the script pubkey of the output it concerns, if any, and its number,
+  /// if events are stored.
+  pub(crate) fn open_with_script_pubkey_event_sender(
+    settings: &Settings,
+    event_sender: tokio::sync::mpsc::Sender<(Event, Option<ScriptBuf>, Option<u64>)>,
+  ) -> Result<Self> {
+    Self::open_with_sender(settings, Some(EventSender::WithScriptPubkeys(event_sender)))
+  }
+
+  fn open_with_sender(settings: &Settings, event_sender: Option<EventSender>) -> Result<Self> {
     let client = settings.bitcoin_rpc_client(None)?;
 
     let path = settings.index().to_owned();
@@ -298,7 +362,11 @@ impl Index {
 
         let tx = database.begin_write()?;
 
+        tx.open_multimap_table(INSCRIPTION_ID_TO_OFFER_NUMBER)?;
+        tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
         tx.open_table(NUMBER_TO_OFFER)?;
+        tx.open_table(NUMBER_TO_OFFER_ENTRY)?;
+        tx.open_table(OFFER_TXID_TO_NUMBER)?;
 
         tx.commit()?;
 
@@ -307,6 +375,36 @@ impl Index {
       Err(DatabaseError::Storage(StorageError::Io(error)))
         if error.kind() == io::ErrorKind::NotFound =>
       {
+        ensure!(
+          !settings.index_rune_activity_raw() || settings.index_runes_raw(),
+          "`--index-rune-activity` requires `--index-runes`",
+        );
+
+        ensure!(
+          !settings.index_rune_history_raw() || settings.index_runes_raw(),
+          "`--index-rune-history` requires `--index-runes`",
+        );
+
+        ensure!(
+          !settings.index_inscription_history_raw() || settings.index_inscriptions_raw(),
+          "`--index-inscription-history` cannot be used with `--no-index-inscriptions`",
+        );
+
+        ensure!(
+          !settings.index_search_raw() || settings.index_inscriptions_raw(),
+          "`--index-search` cannot be used with `--no-index-inscriptions`",
+        );
+
+        ensure!(
+          !settings.index_filters_raw() || settings.index_inscriptions_raw(),
+          "`--index-filters` cannot be used with `--no-index-inscriptions`",
+        );
+
+        ensure!(
+          !settings.index_metaprotocols_raw() || settings.index_inscriptions_raw(),
+          "`--index-metaprotocols` cannot be used with `--no-index-inscriptions`",
+        );
+
         log::info!("Creating new index");
 
         let database = Database::builder()
@@ -318,23 +416,39 @@ impl Index {
         tx.set_durability(durability)?;
         tx.set_quick_repair(true);
 
+        tx.open_multimap_table(INSCRIPTION_ID_TO_OFFER_NUMBER)?;
         tx.open_multimap_table(LATEST_CHILD_SEQUENCE_NUMBER_TO_COLLECTION_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
+        tx.open_multimap_table(RUNE_ID_TO_HOLDER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
         tx.open_table(COLLECTION_SEQUENCE_NUMBER_TO_LATEST_CHILD_SEQUENCE_NUMBER)?;
+        tx.open_table(CONTENT_TYPE_SEQUENCE_NUMBERS)?;
         tx.open_table(GALLERY_SEQUENCE_NUMBERS)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+        tx.open_table(INSCRIPTION_TRANSFER)?;
+        tx.open_table(METAPROTOCOL_SEQUENCE_NUMBERS)?;
+        tx.open_table(METAPROTOCOL_TO_COUNT)?;
+        tx.open_table(NUMBER_TO_EVENT)?;
         tx.open_table(NUMBER_TO_OFFER)?;
+        tx.open_table(NUMBER_TO_OFFER_ENTRY)?;
+        tx.open_table(OFFER_TXID_TO_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_RUNE_HISTORY)?;
+        tx.open_table(OUTPOINT_TO_RUNE_SCRIPT_PUBKEY)?;
         tx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
+        tx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+        tx.open_table(RUNE_ID_AND_SCRIPT_PUBKEY_TO_RUNE_HOLDING)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SEARCH_TERM_SEQUENCE_NUMBERS)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -350,12 +464,48 @@ impl Index {
             u64::from(settings.index_addresses_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexEvents,
+            u64::from(settings.index_events_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexFilters,
+            u64::from(settings.index_filters_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexInscriptionHistory,
+            u64::from(settings.index_inscription_history_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexMetaprotocols,
+            u64::from(settings.index_metaprotocols_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexInscriptions,
             u64::from(settings.index_inscriptions_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexRuneActivity,
+            u64::from(settings.index_rune_activity_raw()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexRuneHistory,
+            u64::from(settings.index_rune_history_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
@@ -368,6 +518,12 @@ impl Index {
             u64::from(settings.index_sats_raw()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexSearch,
+            u64::from(settings.index_search_raw()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexTransactions,
@@ -429,8 +585,15 @@ impl Index {
     };
 
     let index_addresses;
+    let index_events;
+    let index_filters;
+    let index_inscription_history;
+    let index_metaprotocols;
+    let index_rune_activity;
+    let index_rune_history;
     let index_runes;
     let index_sats;
+    let index_search;
     let index_transactions;
     let index_inscriptions;
 
@@ -438,9 +601,17 @@ impl Index {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
       index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
+      index_filters = Self::is_statistic_set(&statistics, Statistic::IndexFilters)?;
+      index_inscription_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexInscriptionHistory)?;
+      index_metaprotocols = Self::is_statistic_set(&statistics, Statistic::IndexMetaprotocols)?;
+      index_rune_activity = Self::is_statistic_set(&statistics, Statistic::IndexRuneActivity)?;
+      index_rune_history = Self::is_statistic_set(&statistics, Statistic::IndexRuneHistory)?;
       index_inscriptions = Self::is_statistic_set(&statistics, Statistic::IndexInscriptions)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
+      index_search = Self::is_statistic_set(&statistics, Statistic::IndexSearch)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
     }
 
@@ -463,12 +634,20 @@ impl Index {
       database,
       durability,
       event_sender,
+      event_subscribers: None,
       first_index_height,
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
       index_addresses,
+      index_events,
+      index_filters,
+      index_inscription_history,
+      index_metaprotocols,
+      index_rune_activity,
+      index_rune_history,
       index_runes,
       index_sats,
+      index_search,
       index_transactions,
       index_inscriptions,
       settings: settings.clone(),
@@ -497,10 +676,36 @@ impl Index {
   }
 
   #[cfg(test)]
-  fn set_durability(&mut self, durability: redb::Durability) {
+  pub(crate) fn set_durability(&mut self, durability: redb::Durability) {
     self.durability = durability;
   }
 
+  /// Only build and send events to the event sender while `subscribers` has
+  /// at least one subscription.
+  pub(crate) fn with_event_subscribers(self, subscribers: EventSubscribers) -> Self {
+    Self {
+      event_subscribers: Some(subscribers),
+      ..self
+    }
+  }
+
+  /// Subscribe to events sent to the event sender. Returns `None` if events
+  /// are sent regardless of subscribers.
+  pub(crate) fn subscribe_to_events(&self) -> Option<EventSubscription> {
+    self
+      .event_subscribers
+      .as_ref()
+      .map(EventSubscribers::subscribe)
+  }
+
+  fn sends_events(&self) -> bool {
+    self.event_sender.is_some()
+      && self
+        .event_subscribers
+        .as_ref()
+        .is_none_or(EventSubscribers::any)
+  }
+
   pub fn contains_output(&self, output: &OutPoint) -> Result<bool> {
     Ok(
       self
@@ -516,18 +721,46 @@ impl Index {
     self.index_addresses
   }
 
+  pub fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
+  pub fn has_filter_index(&self) -> bool {
+    self.index_filters
+  }
+
   pub fn has_inscription_index(&self) -> bool {
     self.index_inscriptions
   }
 
+  pub fn has_inscription_history_index(&self) -> bool {
+    self.index_inscription_history
+  }
+
+  pub fn has_metaprotocol_index(&self) -> bool {
+    self.index_metaprotocols
+  }
+
   pub fn has_rune_index(&self) -> bool {
     self.index_runes
   }
 
+  pub fn has_rune_activity_index(&self) -> bool {
+    self.index_rune_activity
+  }
+
+  pub fn has_rune_history_index(&self) -> bool {
+    self.index_rune_history
+  }
+
   pub fn has_sat_index(&self) -> bool {
     self.index_sats
   }
 
+  pub fn has_search_index(&self) -> bool {
+    self.index_search
+  }
+
   pub fn status(&self, json_api: bool) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -570,6 +803,7 @@ impl Index {
         self.settings.chain().network(),
         Height(next_height),
       ),
+      rune_history_index: self.has_rune_history_index(),
       rune_index: self.has_rune_index(),
       runes: statistic(Statistic::Runes)?,
       sat_index: self.has_sat_index(),
@@ -671,6 +905,7 @@ impl Index {
       let wtx = self.begin_write()?;
 
       let mut updater = Updater {
+        committed_events: Vec::new(),
         height: wtx
           .open_table(HEIGHT_TO_BLOCK_HEADER)?
           .range(0..)?
@@ -860,24 +1095,282 @@ impl Index {
     )
   }
 
-  pub(crate) fn insert_offer(&self, offer: Psbt) -> Result {
+  pub(crate) fn get_offers_for_inscription(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Vec<(u64, Vec<u8>)>> {
+    let tx = self.database.begin_read()?;
+
+    let inscription_id_to_offer_number = tx.open_multimap_table(INSCRIPTION_ID_TO_OFFER_NUMBER)?;
+    let number_to_offer = tx.open_table(NUMBER_TO_OFFER)?;
+    let number_to_offer_entry = tx.open_table(NUMBER_TO_OFFER_ENTRY)?;
+
+    let mut offers = Vec::new();
+
+    for result in inscription_id_to_offer_number.get(&inscription_id.store())? {
+      let number = result?.value();
+
+      let entry = OfferEntry::load(number_to_offer_entry.get(number)?.unwrap().value());
+
+      let offer = number_to_offer.get(number)?.unwrap().value().to_vec();
+
+      offers.push((entry.amount, offer));
+    }
+
+    Ok(offers)
+  }
+
+  /// Returns offers which spend any of `outputs`.
+  pub(crate) fn get_offers_for_outputs(&self, outputs: &[OutPoint]) -> Result<Vec<Vec<u8>>> {
+    let tx = self.database.begin_read()?;
+
+    let outpoint_to_offer_number = tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
+    let number_to_offer = tx.open_table(NUMBER_TO_OFFER)?;
+
+    let mut numbers = BTreeSet::new();
+
+    for outpoint in outputs {
+      for result in outpoint_to_offer_number.get(&outpoint.store())? {
+        numbers.insert(result?.value());
+      }
+    }
+
+    let mut offers = Vec::new();
+
+    for number in numbers {
+      offers.push(number_to_offer.get(number)?.unwrap().value().to_vec());
+    }
+
+    Ok(offers)
+  }
+
+  /// Inserts `offer` to buy `inscription_id` for `amount`. Offers are not
+  /// inserted if they are already present, or if the server already holds
+  /// `MAX_OFFERS` offers, or `MAX_OFFERS_PER_INSCRIPTION` offers for
+  /// `inscription_id`.
+  pub(crate) fn insert_offer(
+    &self,
+    offer: Psbt,
+    inscription_id: InscriptionId,
+    amount: u64,
+  ) -> Result<OfferInsertion> {
+    let received = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_secs();
+
     let tx = self.database.begin_write()?;
 
     {
+      let mut offer_txid_to_number = tx.open_table(OFFER_TXID_TO_NUMBER)?;
+
+      let txid = offer.unsigned_tx.compute_txid().store();
+
+      if offer_txid_to_number.get(&txid)?.is_some() {
+        return Ok(OfferInsertion::Duplicate);
+      }
+
+      let mut inscription_id_to_offer_number =
+        tx.open_multimap_table(INSCRIPTION_ID_TO_OFFER_NUMBER)?;
+
+      if inscription_id_to_offer_number
+        .get(&inscription_id.store())?
+        .len()
+        >= MAX_OFFERS_PER_INSCRIPTION
+      {
+        return Ok(OfferInsertion::TooManyForInscription);
+      }
+
       let mut number_to_offer = tx.open_table(NUMBER_TO_OFFER)?;
 
+      if number_to_offer.len()? >= MAX_OFFERS {
+        return Ok(OfferInsertion::TooMany);
+      }
+
       let number = number_to_offer
         .last()?
         .map(|(key, _value)| key.value() + 1)
         .unwrap_or_default();
 
+      let mut outpoint_to_offer_number = tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
+
+      for input in &offer.unsigned_tx.input {
+        outpoint_to_offer_number.insert(&input.previous_output.store(), number)?;
+      }
+
       let offer = offer.serialize();
 
       number_to_offer.insert(number, offer.as_slice())?;
+
+      tx.open_table(NUMBER_TO_OFFER_ENTRY)?.insert(
+        number,
+        OfferEntry {
+          amount,
+          inscription_id,
+          received,
+        }
+        .store(),
+      )?;
+
+      inscription_id_to_offer_number.insert(&inscription_id.store(), number)?;
+
+      offer_txid_to_number.insert(&txid, number)?;
     }
 
     tx.commit()?;
 
+    Ok(OfferInsertion::Inserted)
+  }
+
+  /// Removes offers received before offers were validated, and, if `ttl` is
+  /// given, offers received more than `ttl` ago. Returns the number of offers
+  /// removed. Offers with inputs spent in indexed blocks are removed by the
+  /// updater.
+  pub(crate) fn prune_offers(&self, ttl: Option<Duration>) -> Result<usize> {
+    let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+
+    let mut stale = Vec::new();
+
+    {
+      let tx = self.database.begin_read()?;
+
+      let number_to_offer = tx.open_table(NUMBER_TO_OFFER)?;
+      let number_to_offer_entry = tx.open_table(NUMBER_TO_OFFER_ENTRY)?;
+
+      for result in number_to_offer.iter()? {
+        let number = result?.0.value();
+
+        let entry = number_to_offer_entry
+          .get(number)?
+          .map(|entry| OfferEntry::load(entry.value()));
+
+        // offers received before offers were validated have no entry
+        let Some(entry) = entry else {
+          stale.push(number);
+          continue;
+        };
+
+        if ttl.is_some_and(|ttl| now.saturating_sub(Duration::from_secs(entry.received)) > ttl) {
+          stale.push(number);
+        }
+      }
+    }
+
+    if stale.is_empty() {
+      return Ok(0);
+    }
+
+    let tx = self.database.begin_write()?;
+
+    for number in &stale {
+      Self::remove_offer(&tx, *number)?;
+    }
+
+    tx.commit()?;
+
+    Ok(stale.len())
+  }
+
+  /// Removes offers with inputs spent by `block`.
+  pub(crate) fn remove_offers_spent_in_block(tx: &WriteTransaction, block: &BlockData) -> Result {
+    let mut spent = BTreeSet::new();
+
+    {
+      let outpoint_to_offer_number = tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
+
+      if outpoint_to_offer_number.is_empty()? {
+        return Ok(());
+      }
+
+      for (transaction, _txid) in &block.txdata {
+        for input in &transaction.input {
+          for result in outpoint_to_offer_number.get(&input.previous_output.store())? {
+            spent.insert(result?.value());
+          }
+        }
+      }
+    }
+
+    for number in spent {
+      Self::remove_offer(tx, number)?;
+    }
+
+    Ok(())
+  }
+
+  fn remove_offer(tx: &WriteTransaction, number: u64) -> Result {
+    let Some(offer) = tx
+      .open_table(NUMBER_TO_OFFER)?
+      .remove(number)?
+      .map(|offer| Psbt::deserialize(offer.value()))
+      .transpose()?
+    else {
+      return Ok(());
+    };
+
+    tx.open_table(OFFER_TXID_TO_NUMBER)?
+      .remove(&offer.unsigned_tx.compute_txid().store())?;
+
+    if let Some(entry) = tx.open_table(NUMBER_TO_OFFER_ENTRY)?.remove(number)? {
+      let entry = OfferEntry::load(entry.value());
+
+      tx.open_multimap_table(INSCRIPTION_ID_TO_OFFER_NUMBER)?
+        .remove(&entry.inscription_id.store(), number)?;
+    }
+
+    let mut outpoint_to_offer_number = tx.open_multimap_table(OUTPOINT_TO_OFFER_NUMBER)?;
+
+    for input in &offer.unsigned_tx.input {
+      outpoint_to_offer_number.remove(&input.previous_output.store(), number)?;
+    }
+
+    Ok(())
+  }
+
+  pub fn get_events(&self, after: Option<u64>, limit: usize) -> Result<(Vec<(u64, Event)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let number_to_event = rtx.open_table(NUMBER_TO_EVENT)?;
+
+    let start = after.map(|after| after.saturating_add(1)).unwrap_or(0);
+
+    let mut events = number_to_event
+      .range(start..)?
+      .take(limit.saturating_add(1))
+      .map(|result| {
+        let (number, event) = result?;
+        Ok((number.value(), Event::load(event.value())))
+      })
+      .collect::<Result<Vec<(u64, Event)>>>()?;
+
+    let more = events.len() > limit;
+
+    events.truncate(limit);
+
+    Ok((events, more))
+  }
+
+  /// Returns the number of the last stored event, if any.
+  pub fn last_event_number(&self) -> Result<Option<u64>> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_table(NUMBER_TO_EVENT)?
+        .last()?
+        .map(|(number, _)| number.value()),
+    )
+  }
+
+  fn insert_events(
+    number_to_event: &mut Table<u64, EventValue>,
+    mut number: u64,
+    events: &[Event],
+  ) -> Result {
+    for event in events {
+      number_to_event.insert(number, event.clone().store())?;
+      number += 1;
+    }
+
     Ok(())
   }
 
@@ -1026,6 +1519,37 @@ impl Index {
     Ok(entries)
   }
 
+  /// Returns the number of etched runes by name length, for names of up to
+  /// `Rune::UNLOCKED` letters, the number of etched runes with longer names,
+  /// excluding reserved runes, and the number of reserved runes. Names of
+  /// each length occupy a contiguous range of rune values, which is counted
+  /// in `RUNE_TO_RUNE_ID`.
+  pub fn get_etched_rune_counts(&self) -> Result<(BTreeMap<usize, u64>, u64, u64)> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+
+    let count = |range: Range<u128>| -> Result<u64> {
+      Ok(rune_to_rune_id.range(range)?.count().try_into().unwrap())
+    };
+
+    let mut lengths = BTreeMap::new();
+
+    for length in 1..=Rune::UNLOCKED {
+      lengths.insert(length, count(Rune::STEPS[length - 1]..Rune::STEPS[length])?);
+    }
+
+    let unrestricted = count(Rune::STEPS[Rune::UNLOCKED]..Rune::RESERVED)?;
+
+    let reserved = rtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::ReservedRunes.key())?
+      .map(|count| count.value())
+      .unwrap_or_default();
+
+    Ok((lengths, unrestricted, reserved))
+  }
+
   pub fn runes_paginated(
     &self,
     page_size: usize,
@@ -1090,17 +1614,161 @@ impl Index {
       return Ok(Some(BTreeMap::new()));
     };
 
-    let balances_buffer = balances.value();
+    Ok(Some(Self::decode_rune_piles(
+      &id_to_rune_entries,
+      balances.value(),
+    )?))
+  }
+
+  /// Returns the rune balances `outpoint` was created with, according to rune
+  /// history, which, unlike rune balances, are kept after it is spent.
+  fn get_rune_history_balances_for_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> Result<Option<BTreeMap<SpacedRune, Pile>>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_history = rtx.open_table(OUTPOINT_TO_RUNE_HISTORY)?;
+
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let Some(history) = outpoint_to_history.get(&outpoint.store())? else {
+      return Ok(Some(BTreeMap::new()));
+    };
+
+    let (_height, _spent, balances) = history.value();
+
+    Ok(Some(Self::decode_rune_piles(
+      &id_to_rune_entries,
+      balances,
+    )?))
+  }
+
+  pub fn get_rune_history_for_address_paginated(
+    &self,
+    address: &Address,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::RuneHistory>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_history = rtx.open_table(OUTPOINT_TO_RUNE_HISTORY)?;
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let mut history = rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?
+      .get(address.script_pubkey().as_bytes())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let (_height, outpoint) = result?.value();
+
+        let entry = outpoint_to_history.get(&outpoint)?.unwrap();
+
+        let (height, spent, balances) = entry.value();
+
+        Ok(api::RuneHistory {
+          height,
+          outpoint: OutPoint::load(outpoint),
+          runes: Self::decode_rune_piles(&id_to_rune_entries, balances)?,
+          spent: spent.map(|(txid, height)| api::RuneSpend {
+            height,
+            txid: Txid::load(txid),
+          }),
+        })
+      })
+      .collect::<Result<Vec<api::RuneHistory>>>()?;
+
+    let more = history.len() > page_size;
+
+    if more {
+      history.pop();
+    }
+
+    Ok((history, more))
+  }
+
+  pub fn get_rune_activity(
+    &self,
+    id: RuneId,
+    from: u32,
+    to: u32,
+  ) -> Result<Vec<(u32, RuneActivityEntry)>> {
+    if from > to {
+      return Ok(Vec::new());
+    }
+
+    self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?
+      .range((id.store(), from)..=(id.store(), to))?
+      .map(|result| {
+        result
+          .map(|(key, value)| (key.value().1, RuneActivityEntry::load(value.value())))
+          .map_err(|err| err.into())
+      })
+      .collect()
+  }
+
+  pub fn get_rune_holder_count(&self, id: RuneId) -> Result<u64> {
+    Ok(
+      self
+        .database
+        .begin_read()?
+        .open_multimap_table(RUNE_ID_TO_HOLDER)?
+        .get(id.store())?
+        .len(),
+    )
+  }
+
+  pub fn get_rune_holders_paginated(
+    &self,
+    id: RuneId,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<(ScriptBuf, u128, u64)>, bool)> {
+    let mut holders = self
+      .database
+      .begin_read()?
+      .open_multimap_table(RUNE_ID_TO_HOLDER)?
+      .get(id.store())?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let holder = result?;
+        let (balance, outputs, script_pubkey) = holder.value();
+        Ok((
+          ScriptBuf::from_bytes(script_pubkey.to_vec()),
+          balance,
+          outputs,
+        ))
+      })
+      .collect::<Result<Vec<(ScriptBuf, u128, u64)>>>()?;
+
+    let more = holders.len() > page_size;
+
+    if more {
+      holders.pop();
+    }
 
-    let mut balances = BTreeMap::new();
+    Ok((holders, more))
+  }
+
+  fn decode_rune_piles(
+    id_to_rune_entries: &impl ReadableTable<RuneIdValue, RuneEntryValue>,
+    buffer: &[u8],
+  ) -> Result<BTreeMap<SpacedRune, Pile>> {
+    let mut piles = BTreeMap::new();
     let mut i = 0;
-    while i < balances_buffer.len() {
-      let ((id, amount), length) = Index::decode_rune_balance(&balances_buffer[i..]).unwrap();
+    while i < buffer.len() {
+      let ((id, amount), length) = Index::decode_rune_balance(&buffer[i..]).unwrap();
       i += length;
 
       let entry = RuneEntry::load(id_to_rune_entries.get(id.store())?.unwrap().value());
 
-      balances.insert(
+      piles.insert(
         entry.spaced_rune,
         Pile {
           amount,
@@ -1110,7 +1778,7 @@ impl Index {
       );
     }
 
-    Ok(Some(balances))
+    Ok(piles)
   }
 
   pub fn get_rune_balance_map(&self) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
@@ -1307,6 +1975,58 @@ impl Index {
     Ok((galleries, more))
   }
 
+  pub fn get_metaprotocols(&self) -> Result<Vec<(String, u64)>> {
+    let mut metaprotocols = self
+      .database
+      .begin_read()?
+      .open_table(METAPROTOCOL_TO_COUNT)?
+      .iter()?
+      .map(|result| {
+        let (metaprotocol, count) = result?;
+        Ok((metaprotocol.value().to_string(), count.value()))
+      })
+      .collect::<Result<Vec<(String, u64)>>>()?;
+
+    metaprotocols.sort_by(|(a_name, a_count), (b_name, b_count)| {
+      b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+    });
+
+    Ok(metaprotocols)
+  }
+
+  pub fn get_metaprotocol_inscriptions_paginated(
+    &self,
+    metaprotocol: &str,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut inscriptions = rtx
+      .open_table(METAPROTOCOL_SEQUENCE_NUMBERS)?
+      .range((metaprotocol, 0)..=(metaprotocol, u32::MAX))?
+      .rev()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let (key, _value) = result?;
+        let entry = sequence_number_to_inscription_entry.get(key.value().1)?;
+        Ok(InscriptionEntry::load(entry.unwrap().value()).id)
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = inscriptions.len() > page_size;
+
+    if more {
+      inscriptions.pop();
+    }
+
+    Ok((inscriptions, more))
+  }
+
   #[cfg(test)]
   pub(crate) fn get_children_by_inscription_id(
     &self,
@@ -2055,6 +2775,186 @@ impl Index {
     Ok((inscriptions, more))
   }
 
+  pub fn get_inscriptions_filtered(
+    &self,
+    filter: &InscriptionFilter,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
+
+    let content_type_sequence_numbers = rtx.open_table(CONTENT_TYPE_SEQUENCE_NUMBERS)?;
+    let height_to_last_sequence_number = rtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    let metaprotocol_sequence_numbers = rtx.open_table(METAPROTOCOL_SEQUENCE_NUMBERS)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let last_sequence_number = |heights: RangeTo<u32>| -> Result<u32> {
+      Ok(
+        height_to_last_sequence_number
+          .range(heights)?
+          .next_back()
+          .transpose()?
+          .map(|(_height, sequence_number)| sequence_number.value())
+          .unwrap_or_default(),
+      )
+    };
+
+    let start = match filter.from_height {
+      Some(from_height) => last_sequence_number(..from_height)?,
+      None => 0,
+    };
+
+    let end = match filter.to_height {
+      Some(to_height) => last_sequence_number(..to_height.saturating_add(1))?,
+      None => u32::MAX,
+    }
+    .min(filter.before.unwrap_or(u32::MAX))
+    .min(
+      sequence_number_to_inscription_entry
+        .len()?
+        .try_into()
+        .unwrap_or(u32::MAX),
+    );
+
+    let content_type = filter.content_type_key();
+
+    let mut postings = Vec::new();
+
+    if let Some(content_type) = &content_type {
+      postings.push((&content_type_sequence_numbers, content_type.as_str()));
+    }
+
+    if let Some(metaprotocol) = &filter.metaprotocol {
+      postings.push((&metaprotocol_sequence_numbers, metaprotocol.as_str()));
+    }
+
+    Self::intersect_sequence_numbers(&postings, start..end, page_size, |sequence_number| {
+      let entry = InscriptionEntry::load(
+        sequence_number_to_inscription_entry
+          .get(sequence_number)?
+          .unwrap()
+          .value(),
+      );
+
+      Ok(filter.matches_entry(&entry).then_some(entry.id))
+    })
+  }
+
+  pub fn search_inscriptions(
+    &self,
+    query: &str,
+    before: Option<u32>,
+    page_size: usize,
+  ) -> Result<(Vec<InscriptionId>, Option<u32>)> {
+    let rtx = self.database.begin_read()?;
+
+    let search_term_sequence_numbers = rtx.open_table(SEARCH_TERM_SEQUENCE_NUMBERS)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let terms = search::tokenize(query).collect::<BTreeSet<String>>();
+
+    if terms.is_empty() {
+      return Ok((Vec::new(), None));
+    }
+
+    let postings = terms
+      .iter()
+      .map(|term| (&search_term_sequence_numbers, term.as_str()))
+      .collect::<Vec<_>>();
+
+    Self::intersect_sequence_numbers(
+      &postings,
+      0..before.unwrap_or(u32::MAX),
+      page_size,
+      |sequence_number| {
+        let id = InscriptionEntry::load(
+          sequence_number_to_inscription_entry
+            .get(sequence_number)?
+            .unwrap()
+            .value(),
+        )
+        .id;
+
+        Ok((!self.settings.is_hidden(id)).then_some(id))
+      },
+    )
+  }
+
+  /// Walks the sequence numbers in `range` that appear in every posting list,
+  /// newest first, and returns up to `limit` of them for which `f` returns
+  /// `Some`. Posting lists are leapfrogged by seeking each one to the current
+  /// candidate, and with no posting lists every sequence number in `range` is
+  /// a candidate. At most `MAX_SEQUENCE_NUMBERS_EXAMINED` lookups are made. If
+  /// the walk stops before reaching the start of `range`, because `limit` was
+  /// reached or the lookup budget ran out, the exclusive upper bound to resume
+  /// from is returned.
+  fn intersect_sequence_numbers<T>(
+    postings: &[(&ReadOnlyTable<(&'static str, u32), ()>, &str)],
+    range: Range<u32>,
+    limit: usize,
+    mut f: impl FnMut(u32) -> Result<Option<T>>,
+  ) -> Result<(Vec<T>, Option<u32>)> {
+    let mut examined = 0;
+    let mut matches = Vec::new();
+    let mut end = range.end;
+
+    loop {
+      let Some(mut candidate) = end
+        .checked_sub(1)
+        .filter(|candidate| range.contains(candidate))
+      else {
+        return Ok((matches, None));
+      };
+
+      let mut agreed = 0;
+
+      for (table, key) in postings.iter().cycle() {
+        if agreed == postings.len() {
+          break;
+        }
+
+        if examined == MAX_SEQUENCE_NUMBERS_EXAMINED {
+          return Ok((matches, Some(candidate + 1)));
+        }
+
+        examined += 1;
+
+        let Some(sequence_number) = table
+          .range((*key, range.start)..=(*key, candidate))?
+          .next_back()
+          .transpose()?
+          .map(|(key, _value)| key.value().1)
+        else {
+          return Ok((matches, None));
+        };
+
+        if sequence_number == candidate {
+          agreed += 1;
+        } else {
+          candidate = sequence_number;
+          agreed = 1;
+        }
+      }
+
+      if examined == MAX_SEQUENCE_NUMBERS_EXAMINED {
+        return Ok((matches, Some(candidate + 1)));
+      }
+
+      examined += 1;
+
+      if let Some(item) = f(candidate)? {
+        if matches.len() == limit {
+          return Ok((matches, Some(candidate + 1)));
+        }
+
+        matches.push(item);
+      }
+
+      end = candidate;
+    }
+  }
+
   pub fn get_inscriptions_in_block(&self, block_height: u32) -> Result<Vec<InscriptionId>> {
     let rtx = self.database.begin_read()?;
 
@@ -2256,6 +3156,12 @@ impl Index {
         .nth(satpoint.outpoint.vout.try_into().unwrap())
     };
 
+    let transfers = if self.index_inscription_history {
+      Some(self.get_inscription_transfers(&rtx, sequence_number, &transaction)?)
+    } else {
+      None
+    };
+
     let previous = if let Some(n) = sequence_number.checked_sub(1) {
       Some(
         InscriptionEntry::load(
@@ -2368,6 +3274,7 @@ impl Index {
         sat: entry.sat,
         satpoint,
         timestamp: timestamp(entry.timestamp.into()).timestamp(),
+        transfers,
         value: output.as_ref().map(|o| o.value.to_sat()),
         metaprotocol: inscription.metaprotocol().map(|s| s.to_string()),
       },
@@ -2376,6 +3283,64 @@ impl Index {
     )))
   }
 
+  fn get_inscription_transfers(
+    &self,
+    rtx: &redb::ReadTransaction,
+    sequence_number: u32,
+    reveal_transaction: &Transaction,
+  ) -> Result<Vec<api::InscriptionTransfer>> {
+    let address = |script_pubkey: &Script| {
+      self
+        .settings
+        .chain()
+        .address_from_script(script_pubkey)
+        .ok()
+        .map(|address| address.to_string())
+    };
+
+    let mut transfers = Vec::new();
+
+    // the sender of a transfer is the receiver of the previous one, and the
+    // sender of the first transfer is the reveal transaction output that
+    // received the inscription
+    let mut sender = None;
+
+    for (i, result) in rtx
+      .open_table(INSCRIPTION_TRANSFER)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+      .enumerate()
+    {
+      let (_, value) = result?;
+
+      let (height, txid, old_satpoint, new_satpoint, receiver) = value.value();
+
+      let old_satpoint = SatPoint::load(old_satpoint);
+      let new_satpoint = SatPoint::load(new_satpoint);
+
+      if i == 0 && old_satpoint.outpoint.txid == reveal_transaction.compute_txid() {
+        sender = reveal_transaction
+          .output
+          .get(old_satpoint.outpoint.vout.into_usize())
+          .and_then(|output| address(&output.script_pubkey));
+      }
+
+      let receiver = receiver.and_then(|receiver| address(Script::from_bytes(&receiver)));
+
+      transfers.push(api::InscriptionTransfer {
+        height,
+        new_satpoint,
+        old_satpoint,
+        receiver: receiver.clone(),
+        sender: sender.take(),
+        txid: Txid::load(txid),
+      });
+
+      sender = receiver;
+    }
+
+    Ok(transfers)
+  }
+
   pub fn get_inscription_entry(
     &self,
     inscription_id: InscriptionId,
@@ -2656,7 +3621,11 @@ impl Index {
 
     let inscriptions = self.get_inscriptions_for_output(outpoint)?;
 
-    let runes = self.get_rune_balances_for_output(outpoint)?;
+    let runes = if spent && self.index_rune_history {
+      self.get_rune_history_balances_for_output(outpoint)?
+    } else {
+      self.get_rune_balances_for_output(outpoint)?
+    };
 
     Ok(Some((
       api::Output::new(
@@ -2677,7 +3646,10 @@ impl Index {
 
 #[cfg(test)]
 mod tests {
-  use {super::*, crate::index::testing::Context};
+  use {
+    super::*,
+    crate::index::{event::EventKind, testing::Context},
+  };
 
   #[test]
   fn height_limit() {
@@ -6679,6 +7651,16 @@ mod tests {
     }
   }
 
+  fn next_event(receiver: &mut tokio::sync::mpsc::Receiver<Event>) -> Event {
+    loop {
+      let event = receiver.blocking_recv().unwrap();
+
+      if !event.is_block_event() {
+        return event;
+      }
+    }
+  }
+
   #[test]
   fn inscription_event_sender_channel() {
     let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
@@ -6700,7 +7682,7 @@ mod tests {
       txid: create_txid,
       index: 0,
     };
-    let create_event = event_receiver.blocking_recv().unwrap();
+    let create_event = next_event(&mut event_receiver);
     let expected_charms = if context.index.index_sats { 513 } else { 0 };
     assert_eq!(
       create_event,
@@ -6730,7 +7712,7 @@ mod tests {
 
     context.mine_blocks(1);
 
-    let transfer_event = event_receiver.blocking_recv().unwrap();
+    let transfer_event = next_event(&mut event_receiver);
     assert_eq!(
       transfer_event,
       Event::InscriptionTransferred {
@@ -6805,7 +7787,7 @@ mod tests {
     );
 
     assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
+      next_event(&mut event_receiver),
       Event::RuneEtched {
         block_height: 8,
         txid: txid0,
@@ -6858,7 +7840,7 @@ mod tests {
     );
 
     assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
+      next_event(&mut event_receiver),
       Event::RuneMinted {
         block_height: 9,
         txid: txid1,
@@ -6914,10 +7896,10 @@ mod tests {
       )],
     );
 
-    event_receiver.blocking_recv().unwrap();
+    next_event(&mut event_receiver);
 
     pretty_assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
+      next_event(&mut event_receiver),
       Event::RuneTransferred {
         block_height: 10,
         txid: txid2,
@@ -6979,10 +7961,10 @@ mod tests {
       )],
     );
 
-    event_receiver.blocking_recv().unwrap();
+    next_event(&mut event_receiver);
 
     pretty_assert_eq!(
-      event_receiver.blocking_recv().unwrap(),
+      next_event(&mut event_receiver),
       Event::RuneBurned {
         block_height: 11,
         txid: txid3,
@@ -6992,6 +7974,282 @@ mod tests {
     );
   }
 
+  #[test]
+  fn events_are_sent_with_script_pubkeys() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .script_pubkey_event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let (event, script_pubkey) = loop {
+      let (event, script_pubkey, _) = event_receiver.blocking_recv().unwrap();
+
+      if !event.is_block_event() {
+        break (event, script_pubkey);
+      }
+
+      assert_eq!(script_pubkey, None);
+    };
+
+    assert_eq!(event.kind(), EventKind::InscriptionCreated);
+
+    assert_eq!(
+      script_pubkey,
+      Some(
+        context.index.get_transaction(txid).unwrap().unwrap().output[0]
+          .script_pubkey
+          .clone()
+      ),
+    );
+  }
+
+  #[test]
+  fn block_committed_event_sender_channel() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder().event_sender(event_sender).build();
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_hash: context.index.block_hash(Some(0)).unwrap().unwrap(),
+        block_height: 0,
+      }
+    );
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    let block = context.mine_blocks(1).remove(0);
+
+    event_receiver.blocking_recv().unwrap();
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap().kind(),
+      EventKind::InscriptionCreated,
+    );
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_hash: block.block_hash(),
+        block_height: 2,
+      }
+    );
+
+    assert!(event_receiver.try_recv().is_err());
+
+    assert!(
+      context
+        .index
+        .inscription_exists(InscriptionId { txid, index: 0 })
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn events_are_sent_after_commit() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--commit-interval=10")
+      .event_sender(event_sender)
+      .build();
+
+    event_receiver.blocking_recv().unwrap();
+
+    let mut blocks = context.mine_blocks_with_update(1, false);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    blocks.extend(context.mine_blocks(1));
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_hash: blocks[0].block_hash(),
+        block_height: 1,
+      }
+    );
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap().kind(),
+      EventKind::InscriptionCreated,
+    );
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_hash: blocks[1].block_hash(),
+        block_height: 2,
+      }
+    );
+
+    assert!(event_receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn events_are_only_sent_while_subscribed() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let mut context = Context::builder().event_sender(event_sender).build();
+
+    event_receiver.blocking_recv().unwrap();
+
+    context.index = context
+      .index
+      .with_event_subscribers(EventSubscribers::default());
+
+    context.mine_blocks(1);
+
+    assert!(event_receiver.try_recv().is_err());
+
+    let subscription = context.index.subscribe_to_events().unwrap();
+
+    let block = context.mine_blocks(1).remove(0);
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted {
+        block_hash: block.block_hash(),
+        block_height: 2,
+      }
+    );
+
+    drop(subscription);
+
+    context.mine_blocks(1);
+
+    assert!(event_receiver.try_recv().is_err());
+  }
+
+  #[test]
+  fn block_reverted_event_sender_channel() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let mut context = Context::builder().event_sender(event_sender).build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(8);
+
+    let tip = context.index.block_hash(None).unwrap().unwrap();
+
+    while event_receiver.try_recv().is_ok() {}
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let events = std::iter::from_fn(|| event_receiver.try_recv().ok()).collect::<Vec<Event>>();
+
+    let reverted = events
+      .iter()
+      .take_while(|event| event.kind() == EventKind::BlockReverted)
+      .map(|event| match event {
+        Event::BlockReverted {
+          block_hash,
+          block_height,
+        } => (*block_height, *block_hash),
+        _ => unreachable!(),
+      })
+      .collect::<Vec<(u32, BlockHash)>>();
+
+    assert_eq!(reverted.first(), Some(&(8, tip)));
+
+    for window in reverted.windows(2) {
+      assert_eq!(window[0].0, window[1].0 + 1);
+    }
+
+    let committed = events[reverted.len()..]
+      .iter()
+      .map(|event| match event {
+        Event::BlockCommitted {
+          block_hash,
+          block_height,
+        } => (*block_height, *block_hash),
+        event => panic!("unexpected event: {event:?}"),
+      })
+      .collect::<Vec<(u32, BlockHash)>>();
+
+    assert_eq!(committed.first().unwrap().0, reverted.last().unwrap().0);
+    assert_eq!(committed.last().unwrap().0, 9);
+
+    for (height, hash) in committed {
+      assert_eq!(context.index.block_hash(Some(height)).unwrap(), Some(hash));
+    }
+  }
+
+  #[test]
+  fn events_are_stored_with_monotonic_cursors_across_reorgs() {
+    let mut context = Context::builder().arg("--index-events").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(8);
+
+    let (events, more) = context.index.get_events(None, usize::MAX).unwrap();
+
+    assert!(!more);
+    assert_eq!(events.len(), 9);
+
+    let last_cursor = events.last().unwrap().0;
+
+    let tip = context.index.block_hash(None).unwrap().unwrap();
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    let (events, _) = context
+      .index
+      .get_events(Some(last_cursor), usize::MAX)
+      .unwrap();
+
+    assert_eq!(events[0].0, last_cursor + 1);
+    assert_eq!(
+      events[0].1,
+      Event::BlockReverted {
+        block_hash: tip,
+        block_height: 8,
+      }
+    );
+
+    for window in events.windows(2) {
+      assert!(window[0].0 < window[1].0);
+    }
+
+    assert_eq!(
+      events.last().unwrap().1,
+      Event::BlockCommitted {
+        block_hash: context.index.block_hash(None).unwrap().unwrap(),
+        block_height: 9,
+      }
+    );
+  }
+
+  #[test]
+  fn events_are_not_stored_without_event_index() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_events(None, usize::MAX).unwrap(),
+      (Vec::new(), false)
+    );
+  }
+
   #[test]
   fn assert_schema_statistic_key_is_zero() {
     // other schema statistic keys may change when the schema changes, but for
@@ -7057,4 +8315,105 @@ mod tests {
       );
     }
   }
+
+  #[test]
+  fn offers_are_capped_per_inscription() {
+    let context = Context::builder().build();
+
+    let offer = |n: u32| {
+      Psbt::from_unsigned_tx(Transaction {
+        version: Version(2),
+        lock_time: LockTime::from_consensus(n),
+        input: Vec::new(),
+        output: Vec::new(),
+      })
+      .unwrap()
+    };
+
+    for n in 0..MAX_OFFERS_PER_INSCRIPTION.try_into().unwrap() {
+      assert_eq!(
+        context
+          .index
+          .insert_offer(offer(n), inscription_id(1), 1000)
+          .unwrap(),
+        OfferInsertion::Inserted,
+      );
+    }
+
+    assert_eq!(
+      context
+        .index
+        .insert_offer(offer(0), inscription_id(1), 1000)
+        .unwrap(),
+      OfferInsertion::Duplicate,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .insert_offer(offer(u32::MAX), inscription_id(1), 1000)
+        .unwrap(),
+      OfferInsertion::TooManyForInscription,
+    );
+
+    assert_eq!(
+      context
+        .index
+        .insert_offer(offer(u32::MAX), inscription_id(2), 1000)
+        .unwrap(),
+      OfferInsertion::Inserted,
+    );
+  }
+
+  #[test]
+  fn search_inscriptions_returns_cursor_to_resume_from() {
+    let context = Context::builder().arg("--index-search").build();
+
+    context.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for (i, body) in ["hello", "goodbye", "hello", "hello"]
+      .into_iter()
+      .enumerate()
+    {
+      let txid = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, inscription("text/plain", body).to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    assert_eq!(
+      context.index.search_inscriptions("hello", None, 1).unwrap(),
+      (vec![ids[3]], Some(3)),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .search_inscriptions("hello", Some(3), 1)
+        .unwrap(),
+      (vec![ids[2]], Some(1)),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .search_inscriptions("hello", Some(1), 1)
+        .unwrap(),
+      (vec![ids[0]], None),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .search_inscriptions("hello goodbye", None, 1)
+        .unwrap(),
+      (Vec::new(), None),
+    );
+  }
 }
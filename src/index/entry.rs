@@ -273,6 +273,44 @@ impl Entry for RuneEntry {
   }
 }
 
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub struct RuneActivityEntry {
+  pub burned: u128,
+  pub mints: u64,
+  pub receivers: u64,
+  pub transfers: u64,
+}
+
+pub(super) type RuneActivityValue = (
+  u128, // burned
+  u64,  // mints
+  u64,  // receivers
+  u64,  // transfers
+);
+
+impl Entry for RuneActivityEntry {
+  type Value = RuneActivityValue;
+
+  fn load((burned, mints, receivers, transfers): RuneActivityValue) -> Self {
+    Self {
+      burned,
+      mints,
+      receivers,
+      transfers,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.burned, self.mints, self.receivers, self.transfers)
+  }
+}
+
+pub(super) type RuneHistoryValue = (
+  u32,                      // height
+  Option<(TxidValue, u32)>, // spending txid and height
+  &'static [u8],            // balances
+);
+
 pub(super) type RuneIdValue = (u64, u32);
 
 impl Entry for RuneId {
@@ -420,6 +458,307 @@ impl Entry for InscriptionId {
   }
 }
 
+pub(super) type InscriptionTransferValue = (
+  u32,             // height
+  TxidValue,       // txid
+  SatPointValue,   // old satpoint
+  SatPointValue,   // new satpoint
+  Option<Vec<u8>>, // receiver script pubkey
+);
+
+type InscriptionEventValue = (
+  u16,                     // charms
+  InscriptionIdValue,      // inscription id
+  Option<SatPointValue>,   // new location
+  Option<SatPointValue>,   // old location
+  Vec<InscriptionIdValue>, // parent inscription ids
+  u32,                     // sequence number
+);
+
+type RuneEventValue = (
+  u128,                  // amount
+  Option<OutPointValue>, // outpoint
+  RuneIdValue,           // rune id
+  TxidValue,             // txid
+);
+
+pub(super) type EventValue = (
+  u8,                            // kind
+  u32,                           // block height
+  Option<[u8; 32]>,              // block hash
+  Option<InscriptionEventValue>, // inscription
+  Option<RuneEventValue>,        // rune
+);
+
+impl Entry for Event {
+  type Value = EventValue;
+
+  fn load((kind, block_height, block_hash, inscription, rune): EventValue) -> Self {
+    let block_hash = || BlockHash::from_byte_array(block_hash.unwrap());
+
+    let inscription = || {
+      let (
+        charms,
+        inscription_id,
+        new_location,
+        old_location,
+        parent_inscription_ids,
+        sequence_number,
+      ) = inscription.clone().unwrap();
+
+      (
+        charms,
+        InscriptionId::load(inscription_id),
+        new_location.map(SatPoint::load),
+        old_location.map(SatPoint::load),
+        parent_inscription_ids
+          .into_iter()
+          .map(InscriptionId::load)
+          .collect::<Vec<InscriptionId>>(),
+        sequence_number,
+      )
+    };
+
+    let rune = || {
+      let (amount, outpoint, rune_id, txid) = rune.unwrap();
+
+      (
+        amount,
+        outpoint.map(OutPoint::load),
+        RuneId::load(rune_id),
+        Txid::load(txid),
+      )
+    };
+
+    match kind {
+      0 => Self::BlockCommitted {
+        block_hash: block_hash(),
+        block_height,
+      },
+      1 => Self::BlockReverted {
+        block_hash: block_hash(),
+        block_height,
+      },
+      2 => {
+        let (charms, inscription_id, location, _, parent_inscription_ids, sequence_number) =
+          inscription();
+
+        Self::InscriptionCreated {
+          block_height,
+          charms,
+          inscription_id,
+          location,
+          parent_inscription_ids,
+          sequence_number,
+        }
+      }
+      3 => {
+        let (_, inscription_id, new_location, old_location, _, sequence_number) = inscription();
+
+        Self::InscriptionTransferred {
+          block_height,
+          inscription_id,
+          new_location: new_location.unwrap(),
+          old_location: old_location.unwrap(),
+          sequence_number,
+        }
+      }
+      4 => {
+        let (amount, _, rune_id, txid) = rune();
+
+        Self::RuneBurned {
+          amount,
+          block_height,
+          rune_id,
+          txid,
+        }
+      }
+      5 => {
+        let (_, _, rune_id, txid) = rune();
+
+        Self::RuneEtched {
+          block_height,
+          rune_id,
+          txid,
+        }
+      }
+      6 => {
+        let (amount, _, rune_id, txid) = rune();
+
+        Self::RuneMinted {
+          amount,
+          block_height,
+          rune_id,
+          txid,
+        }
+      }
+      7 => {
+        let (amount, outpoint, rune_id, txid) = rune();
+
+        Self::RuneTransferred {
+          amount,
+          block_height,
+          outpoint: outpoint.unwrap(),
+          rune_id,
+          txid,
+        }
+      }
+      _ => unreachable!("unknown event kind {kind}"),
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    match self {
+      Self::BlockCommitted {
+        block_hash,
+        block_height,
+      } => (
+        0,
+        block_height,
+        Some(block_hash.to_byte_array()),
+        None,
+        None,
+      ),
+      Self::BlockReverted {
+        block_hash,
+        block_height,
+      } => (
+        1,
+        block_height,
+        Some(block_hash.to_byte_array()),
+        None,
+        None,
+      ),
+      Self::InscriptionCreated {
+        block_height,
+        charms,
+        inscription_id,
+        location,
+        parent_inscription_ids,
+        sequence_number,
+      } => (
+        2,
+        block_height,
+        None,
+        Some((
+          charms,
+          inscription_id.store(),
+          location.map(SatPoint::store),
+          None,
+          parent_inscription_ids
+            .into_iter()
+            .map(InscriptionId::store)
+            .collect(),
+          sequence_number,
+        )),
+        None,
+      ),
+      Self::InscriptionTransferred {
+        block_height,
+        inscription_id,
+        new_location,
+        old_location,
+        sequence_number,
+      } => (
+        3,
+        block_height,
+        None,
+        Some((
+          0,
+          inscription_id.store(),
+          Some(new_location.store()),
+          Some(old_location.store()),
+          Vec::new(),
+          sequence_number,
+        )),
+        None,
+      ),
+      Self::RuneBurned {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      } => (
+        4,
+        block_height,
+        None,
+        None,
+        Some((amount, None, rune_id.store(), txid.store())),
+      ),
+      Self::RuneEtched {
+        block_height,
+        rune_id,
+        txid,
+      } => (
+        5,
+        block_height,
+        None,
+        None,
+        Some((0, None, rune_id.store(), txid.store())),
+      ),
+      Self::RuneMinted {
+        amount,
+        block_height,
+        rune_id,
+        txid,
+      } => (
+        6,
+        block_height,
+        None,
+        None,
+        Some((amount, None, rune_id.store(), txid.store())),
+      ),
+      Self::RuneTransferred {
+        amount,
+        block_height,
+        outpoint,
+        rune_id,
+        txid,
+      } => (
+        7,
+        block_height,
+        None,
+        None,
+        Some((
+          amount,
+          Some(outpoint.store()),
+          rune_id.store(),
+          txid.store(),
+        )),
+      ),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub(crate) struct OfferEntry {
+  pub(crate) amount: u64,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) received: u64,
+}
+
+pub(super) type OfferEntryValue = (
+  u64,                // amount
+  InscriptionIdValue, // inscription id
+  u64,                // received
+);
+
+impl Entry for OfferEntry {
+  type Value = OfferEntryValue;
+
+  fn load((amount, inscription_id, received): OfferEntryValue) -> Self {
+    Self {
+      amount,
+      inscription_id: InscriptionId::load(inscription_id),
+      received,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (self.amount, self.inscription_id.store(), self.received)
+  }
+}
+
 pub(super) type OutPointValue = [u8; 36];
 
 impl Entry for OutPoint {
@@ -547,6 +886,24 @@ mod tests {
     );
   }
 
+  #[test]
+  fn offer_entry() {
+    let inscription_id = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdefi0"
+      .parse::<InscriptionId>()
+      .unwrap();
+
+    let entry = OfferEntry {
+      amount: 1,
+      inscription_id,
+      received: 2,
+    };
+
+    let value = (1, inscription_id.store(), 2);
+
+    assert_eq!(entry.store(), value);
+    assert_eq!(OfferEntry::load(value), entry);
+  }
+
   #[test]
   fn parent_entry_index() {
     let inscription_id = "0000000000000000000000000000000000000000000000000000000000000000i1"
@@ -624,6 +981,76 @@ mod tests {
     assert_eq!(RuneId { block: 1, tx: 2 }, RuneId::load((1, 2)),);
   }
 
+  #[test]
+  fn event_entry() {
+    let rune_id = RuneId { block: 1, tx: 2 };
+
+    let satpoint = |n| SatPoint {
+      outpoint: outpoint(n),
+      offset: n.into(),
+    };
+
+    for event in [
+      Event::BlockCommitted {
+        block_hash: BlockHash::from_byte_array([1; 32]),
+        block_height: 1,
+      },
+      Event::BlockReverted {
+        block_hash: BlockHash::from_byte_array([2; 32]),
+        block_height: 2,
+      },
+      Event::InscriptionCreated {
+        block_height: 3,
+        charms: 4,
+        inscription_id: inscription_id(1),
+        location: Some(satpoint(1)),
+        parent_inscription_ids: vec![inscription_id(2), inscription_id(3)],
+        sequence_number: 5,
+      },
+      Event::InscriptionCreated {
+        block_height: 3,
+        charms: 0,
+        inscription_id: inscription_id(1),
+        location: None,
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 5,
+      },
+      Event::InscriptionTransferred {
+        block_height: 6,
+        inscription_id: inscription_id(4),
+        new_location: satpoint(2),
+        old_location: satpoint(3),
+        sequence_number: 7,
+      },
+      Event::RuneBurned {
+        amount: 8,
+        block_height: 9,
+        rune_id,
+        txid: txid(1),
+      },
+      Event::RuneEtched {
+        block_height: 10,
+        rune_id,
+        txid: txid(2),
+      },
+      Event::RuneMinted {
+        amount: 11,
+        block_height: 12,
+        rune_id,
+        txid: txid(3),
+      },
+      Event::RuneTransferred {
+        amount: 13,
+        block_height: 14,
+        outpoint: outpoint(4),
+        rune_id,
+        txid: txid(4),
+      },
+    ] {
+      assert_eq!(Event::load(event.clone().store()), event);
+    }
+  }
+
   #[test]
   fn header() {
     let expected = [
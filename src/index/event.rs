@@ -1,7 +1,20 @@
-use super::*;
+use {super::*, std::sync::atomic::AtomicUsize};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Event {
+  /// Sent after all other events for the block. Events are only sent once the
+  /// block they concern has been committed to the index.
+  BlockCommitted {
+    block_hash: BlockHash,
+    block_height: u32,
+  },
+  /// Sent when a block is rolled back during reorg handling. Events previously
+  /// sent for the block are no longer valid.
+  BlockReverted {
+    block_hash: BlockHash,
+    block_height: u32,
+  },
   InscriptionCreated {
     block_height: u32,
     charms: u16,
@@ -42,3 +55,194 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+  BlockCommitted,
+  BlockReverted,
+  InscriptionCreated,
+  InscriptionTransferred,
+  RuneBurned,
+  RuneEtched,
+  RuneMinted,
+  RuneTransferred,
+}
+
+impl Event {
+  pub fn kind(&self) -> EventKind {
+    match self {
+      Self::BlockCommitted { .. } => EventKind::BlockCommitted,
+      Self::BlockReverted { .. } => EventKind::BlockReverted,
+      Self::InscriptionCreated { .. } => EventKind::InscriptionCreated,
+      Self::InscriptionTransferred { .. } => EventKind::InscriptionTransferred,
+      Self::RuneBurned { .. } => EventKind::RuneBurned,
+      Self::RuneEtched { .. } => EventKind::RuneEtched,
+      Self::RuneMinted { .. } => EventKind::RuneMinted,
+      Self::RuneTransferred { .. } => EventKind::RuneTransferred,
+    }
+  }
+
+  pub fn inscription_id(&self) -> Option<InscriptionId> {
+    match self {
+      Self::InscriptionCreated { inscription_id, .. }
+      | Self::InscriptionTransferred { inscription_id, .. } => Some(*inscription_id),
+      Self::BlockCommitted { .. }
+      | Self::BlockReverted { .. }
+      | Self::RuneBurned { .. }
+      | Self::RuneEtched { .. }
+      | Self::RuneMinted { .. }
+      | Self::RuneTransferred { .. } => None,
+    }
+  }
+
+  pub fn rune_id(&self) -> Option<RuneId> {
+    match self {
+      Self::BlockCommitted { .. }
+      | Self::BlockReverted { .. }
+      | Self::InscriptionCreated { .. }
+      | Self::InscriptionTransferred { .. } => None,
+      Self::RuneBurned { rune_id, .. }
+      | Self::RuneEtched { rune_id, .. }
+      | Self::RuneMinted { rune_id, .. }
+      | Self::RuneTransferred { rune_id, .. } => Some(*rune_id),
+    }
+  }
+
+  /// The output that receives the inscription or runes, if any.
+  pub fn outpoint(&self) -> Option<OutPoint> {
+    match self {
+      Self::InscriptionCreated { location, .. } => location.map(|location| location.outpoint),
+      Self::InscriptionTransferred { new_location, .. } => Some(new_location.outpoint),
+      Self::RuneTransferred { outpoint, .. } => Some(*outpoint),
+      Self::BlockCommitted { .. }
+      | Self::BlockReverted { .. }
+      | Self::RuneBurned { .. }
+      | Self::RuneEtched { .. }
+      | Self::RuneMinted { .. } => None,
+    }
+  }
+
+  pub fn is_block_event(&self) -> bool {
+    matches!(
+      self,
+      Self::BlockCommitted { .. } | Self::BlockReverted { .. }
+    )
+  }
+}
+
+/// Where the updater sends events.
+pub(crate) enum EventSender {
+  Events(tokio::sync::mpsc::Sender<Event>),
+  /// Events along with the script pubkey of the output they concern, if any,
+  /// which the updater resolves from the block containing the output, so that
+  /// consumers filtering by address need not look up transactions, and their
+  /// number, if events are stored in the index.
+  WithScriptPubkeys(tokio::sync::mpsc::Sender<(Event, Option<ScriptBuf>, Option<u64>)>),
+}
+
+impl EventSender {
+  pub(crate) fn blocking_send(
+    &self,
+    event: Event,
+    script_pubkey: Option<ScriptBuf>,
+    number: Option<u64>,
+  ) -> Result {
+    match self {
+      Self::Events(sender) => sender.blocking_send(event)?,
+      Self::WithScriptPubkeys(sender) => sender.blocking_send((event, script_pubkey, number))?,
+    }
+
+    Ok(())
+  }
+}
+
+/// Counts current consumers of events. An index with event subscribers only
+/// builds and sends events while at least one subscription is held.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EventSubscribers(Arc<AtomicUsize>);
+
+impl EventSubscribers {
+  pub(crate) fn any(&self) -> bool {
+    self.0.load(atomic::Ordering::Relaxed) > 0
+  }
+
+  pub(crate) fn subscribe(&self) -> EventSubscription {
+    self.0.fetch_add(1, atomic::Ordering::Relaxed);
+    EventSubscription(self.0.clone())
+  }
+}
+
+#[derive(Debug)]
+pub(crate) struct EventSubscription(Arc<AtomicUsize>);
+
+impl Drop for EventSubscription {
+  fn drop(&mut self) {
+    self.0.fetch_sub(1, atomic::Ordering::Relaxed);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn serialization() {
+    let event = Event::RuneEtched {
+      block_height: 1,
+      rune_id: RuneId { block: 1, tx: 2 },
+      txid: txid(3),
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+
+    assert_eq!(
+      json,
+      format!(
+        r#"{{"type":"rune_etched","block_height":1,"rune_id":"1:2","txid":"{}"}}"#,
+        txid(3),
+      ),
+    );
+
+    assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+  }
+
+  #[test]
+  fn kind() {
+    assert_eq!(
+      Event::RuneBurned {
+        amount: 1,
+        block_height: 1,
+        rune_id: RuneId { block: 1, tx: 2 },
+        txid: txid(1),
+      }
+      .kind(),
+      EventKind::RuneBurned,
+    );
+
+    assert_eq!(
+      serde_json::from_str::<EventKind>(r#""inscription_transferred""#).unwrap(),
+      EventKind::InscriptionTransferred,
+    );
+  }
+
+  #[test]
+  fn subscribers() {
+    let subscribers = EventSubscribers::default();
+
+    assert!(!subscribers.any());
+
+    let a = subscribers.subscribe();
+    let b = subscribers.clone().subscribe();
+
+    assert!(subscribers.any());
+
+    drop(a);
+
+    assert!(subscribers.any());
+
+    drop(b);
+
+    assert!(!subscribers.any());
+  }
+}
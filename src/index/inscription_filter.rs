@@ -0,0 +1,190 @@
+use super::*;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct InscriptionFilter {
+  pub before: Option<u32>,
+  pub charm: Option<Charm>,
+  pub content_type: Option<String>,
+  pub from_height: Option<u32>,
+  pub metaprotocol: Option<String>,
+  pub rarity: Option<Rarity>,
+  pub to_height: Option<u32>,
+}
+
+impl InscriptionFilter {
+  pub(crate) fn is_empty(&self) -> bool {
+    self.before.is_none()
+      && self.charm.is_none()
+      && self.content_type.is_none()
+      && self.from_height.is_none()
+      && self.metaprotocol.is_none()
+      && self.rarity.is_none()
+      && self.to_height.is_none()
+  }
+
+  pub(crate) fn matches_entry(&self, entry: &InscriptionEntry) -> bool {
+    if let Some(charm) = self.charm
+      && !charm.is_set(entry.charms)
+    {
+      return false;
+    }
+
+    if self.from_height.is_some_and(|height| entry.height < height)
+      || self.to_height.is_some_and(|height| entry.height > height)
+    {
+      return false;
+    }
+
+    if let Some(rarity) = self.rarity
+      && entry.sat.map(Sat::rarity) != Some(rarity)
+    {
+      return false;
+    }
+
+    true
+  }
+
+  /// Content types are indexed by their essence, lowercased and without
+  /// parameters, so that `text/plain` matches `text/plain;charset=utf-8`, and
+  /// by their type with a wildcard subtype, so that `image/*` matches any
+  /// content type of that type.
+  pub(crate) fn content_type_keys(content_type: &str) -> Vec<String> {
+    let essence = Self::essence(content_type);
+
+    let mut keys = Vec::new();
+
+    if let Some((ty, _subtype)) = essence.split_once('/') {
+      keys.push(format!("{ty}/*"));
+    }
+
+    keys.push(essence);
+
+    keys
+  }
+
+  pub(crate) fn content_type_key(&self) -> Option<String> {
+    let filter = self.content_type.as_ref()?.trim().to_lowercase();
+
+    Some(if filter.ends_with("/*") {
+      filter
+    } else {
+      Self::essence(&filter)
+    })
+  }
+
+  fn essence(content_type: &str) -> String {
+    content_type
+      .split(';')
+      .next()
+      .unwrap_or_default()
+      .trim()
+      .to_lowercase()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn content_type(filter: &str, content_type: Option<&str>) -> bool {
+    let key = InscriptionFilter {
+      content_type: Some(filter.into()),
+      ..default()
+    }
+    .content_type_key()
+    .unwrap();
+
+    content_type
+      .is_some_and(|content_type| InscriptionFilter::content_type_keys(content_type).contains(&key))
+  }
+
+  #[test]
+  fn empty() {
+    assert!(InscriptionFilter::default().is_empty());
+    assert!(
+      !InscriptionFilter {
+        from_height: Some(0),
+        ..default()
+      }
+      .is_empty()
+    );
+  }
+
+  #[test]
+  fn content_type_matches_essence() {
+    assert!(content_type("text/plain", Some("text/plain")));
+    assert!(content_type("text/plain", Some("text/plain;charset=utf-8")));
+    assert!(content_type("TEXT/PLAIN", Some("text/plain")));
+    assert!(!content_type("text/plain", Some("text/html")));
+    assert!(!content_type("text/plain", None));
+  }
+
+  #[test]
+  fn content_type_keys() {
+    assert_eq!(
+      InscriptionFilter::content_type_keys("Text/Plain;charset=utf-8"),
+      ["text/*", "text/plain"],
+    );
+    assert_eq!(InscriptionFilter::content_type_keys("foo"), ["foo"]);
+  }
+
+  #[test]
+  fn content_type_matches_wildcard() {
+    assert!(content_type("image/*", Some("image/png")));
+    assert!(content_type("image/*", Some("image/svg+xml")));
+    assert!(!content_type("image/*", Some("text/plain")));
+  }
+
+  #[test]
+  fn entry_matches_charm_and_height() {
+    let mut charms = 0;
+    Charm::Cursed.set(&mut charms);
+
+    let entry = InscriptionEntry {
+      charms,
+      fee: 0,
+      height: 10,
+      hidden: false,
+      id: inscription_id(1),
+      inscription_number: -1,
+      parents: Vec::new(),
+      sat: None,
+      sequence_number: 0,
+      timestamp: 0,
+    };
+
+    assert!(
+      InscriptionFilter {
+        charm: Some(Charm::Cursed),
+        from_height: Some(10),
+        to_height: Some(10),
+        ..default()
+      }
+      .matches_entry(&entry)
+    );
+
+    assert!(
+      !InscriptionFilter {
+        charm: Some(Charm::Burned),
+        ..default()
+      }
+      .matches_entry(&entry)
+    );
+
+    assert!(
+      !InscriptionFilter {
+        from_height: Some(11),
+        ..default()
+      }
+      .matches_entry(&entry)
+    );
+
+    assert!(
+      !InscriptionFilter {
+        rarity: Some(Rarity::Common),
+        ..default()
+      }
+      .matches_entry(&entry)
+    );
+  }
+}
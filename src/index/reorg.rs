@@ -58,6 +58,10 @@ impl Reorg {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
+    // snapshot of the database before rolling back, used to create events for
+    // reverted blocks
+    let rtx = index.begin_read()?;
+
     let mut wtx = index.begin_write()?;
 
     let oldest_savepoint =
@@ -65,13 +69,59 @@ impl Reorg {
 
     wtx.restore_savepoint(&oldest_savepoint)?;
 
+    let block_count = wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, _)| height.value() + 1)
+      .unwrap_or(0);
+
+    let mut events = Vec::new();
+
+    let send_events = index.sends_events();
+
+    if send_events || index.index_events {
+      for result in rtx
+        .0
+        .open_table(HEIGHT_TO_BLOCK_HEADER)?
+        .range(block_count..)?
+        .rev()
+      {
+        let (height, header) = result?;
+
+        events.push(Event::BlockReverted {
+          block_hash: Header::load(*header.value()).block_hash(),
+          block_height: height.value(),
+        });
+      }
+    }
+
+    let next_number = if index.index_events {
+      // continue numbering after the last event in the snapshot so that
+      // cursors handed out before the reorg are never reused
+      let next_number = rtx
+        .0
+        .open_table(NUMBER_TO_EVENT)?
+        .last()?
+        .map(|(number, _)| number.value() + 1)
+        .unwrap_or(0);
+
+      Index::insert_events(&mut wtx.open_table(NUMBER_TO_EVENT)?, next_number, &events)?;
+
+      Some(next_number)
+    } else {
+      None
+    };
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.begin_read()?.block_count()?
-    );
+    log::info!("successfully rolled back database to height {block_count}");
+
+    if send_events && let Some(sender) = &index.event_sender {
+      for (event, i) in events.into_iter().zip(0..) {
+        sender.blocking_send(event, None, next_number.map(|number| number + i))?;
+      }
+    }
 
     Ok(())
   }
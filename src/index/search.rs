@@ -0,0 +1,155 @@
+use super::*;
+
+const MAX_TERM_LENGTH: usize = 64;
+const MAX_TEXT_LENGTH: usize = 16 * 1024;
+const MIN_TERM_LENGTH: usize = 2;
+
+pub(crate) fn terms(inscription: &Inscription) -> BTreeSet<String> {
+  let mut terms = BTreeSet::new();
+
+  if matches!(
+    inscription.media(),
+    Media::Code(_) | Media::Markdown | Media::Text
+  ) && let Some(body) = inscription.body()
+  {
+    let body = &body[..body.len().min(MAX_TEXT_LENGTH)];
+    terms.extend(tokenize(&String::from_utf8_lossy(body)));
+  }
+
+  let Properties { attributes, .. } = inscription.properties();
+
+  if let Some(title) = attributes.title {
+    terms.extend(tokenize(&title));
+  }
+
+  for (name, value) in attributes.traits.items {
+    terms.extend(tokenize(&name));
+
+    if let Trait::String(value) = value {
+      terms.extend(tokenize(&value));
+    }
+  }
+
+  if let Some(metadata) = inscription.metadata() {
+    metadata_keys(&metadata, &mut terms);
+  }
+
+  terms
+}
+
+pub(crate) fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|term| (MIN_TERM_LENGTH..=MAX_TERM_LENGTH).contains(&term.chars().count()))
+    .map(str::to_lowercase)
+}
+
+fn metadata_keys(value: &Value, terms: &mut BTreeSet<String>) {
+  match value {
+    Value::Array(values) => {
+      for value in values {
+        metadata_keys(value, terms);
+      }
+    }
+    Value::Map(entries) => {
+      for (key, value) in entries {
+        if let Value::Text(key) = key {
+          terms.extend(tokenize(key));
+        }
+
+        metadata_keys(value, terms);
+      }
+    }
+    Value::Tag(_, value) => metadata_keys(value, terms),
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn terms(inscription: Inscription) -> Vec<String> {
+    super::terms(&inscription).into_iter().collect()
+  }
+
+  #[test]
+  fn tokenize_splits_on_non_alphanumeric_characters() {
+    assert_eq!(
+      tokenize("Hello, World! foo-bar_baz").collect::<Vec<String>>(),
+      ["hello", "world", "foo", "bar", "baz"],
+    );
+  }
+
+  #[test]
+  fn tokenize_skips_short_and_long_terms() {
+    assert_eq!(
+      tokenize(&format!("a bc {}", "x".repeat(MAX_TERM_LENGTH + 1))).collect::<Vec<String>>(),
+      ["bc"],
+    );
+  }
+
+  #[test]
+  fn text_bodies_are_indexed() {
+    assert_eq!(
+      terms(inscription("text/plain;charset=utf-8", "Hello world")),
+      ["hello", "world"],
+    );
+  }
+
+  #[test]
+  fn binary_bodies_are_not_indexed() {
+    assert!(terms(inscription("image/png", "hello world")).is_empty());
+  }
+
+  #[test]
+  fn titles_and_traits_are_indexed() {
+    assert_eq!(
+      terms(Inscription {
+        properties: Properties {
+          attributes: Attributes {
+            title: Some("Blue Cat".into()),
+            traits: Traits {
+              items: vec![
+                ("eyes".into(), Trait::String("green".into())),
+                ("rare".into(), Trait::Bool(true)),
+              ],
+            },
+          },
+          ..default()
+        }
+        .to_inline_cbor(),
+        ..default()
+      }),
+      ["blue", "cat", "eyes", "green", "rare"],
+    );
+  }
+
+  #[test]
+  fn metadata_keys_are_indexed() {
+    let mut metadata = Vec::new();
+
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (
+          Value::Text("artist".into()),
+          Value::Map(vec![(
+            Value::Text("name".into()),
+            Value::Text("ignored".into()),
+          )]),
+        ),
+        (Value::Integer(1.into()), Value::Text("ignored".into())),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      terms(Inscription {
+        metadata: Some(metadata),
+        ..default()
+      }),
+      ["artist", "name"],
+    );
+  }
+}
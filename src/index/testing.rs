@@ -3,7 +3,7 @@ use {super::*, bitcoin::script::PushBytes, std::ffi::OsString, tempfile::TempDir
 pub(crate) struct ContextBuilder {
   args: Vec<OsString>,
   chain: Chain,
-  event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  event_sender: Option<EventSender>,
   tempdir: Option<TempDir>,
 }
 
@@ -32,7 +32,7 @@ impl ContextBuilder {
 
     let options = Options::try_parse_from(command.into_iter().chain(self.args)).unwrap();
 
-    let index = Index::open_with_event_sender(
+    let index = Index::open_with_sender(
       &Settings::from_options(options).or_defaults().unwrap(),
       self.event_sender,
     )?;
@@ -67,7 +67,15 @@ impl ContextBuilder {
   }
 
   pub(crate) fn event_sender(mut self, sender: tokio::sync::mpsc::Sender<Event>) -> Self {
-    self.event_sender = Some(sender);
+    self.event_sender = Some(EventSender::Events(sender));
+    self
+  }
+
+  pub(crate) fn script_pubkey_event_sender(
+    mut self,
+    sender: tokio::sync::mpsc::Sender<(Event, Option<ScriptBuf>, Option<u64>)>,
+  ) -> Self {
+    self.event_sender = Some(EventSender::WithScriptPubkeys(sender));
     self
   }
 }
@@ -33,6 +33,7 @@ impl From<Block> for BlockData {
 }
 
 pub(crate) struct Updater<'index> {
+  pub(super) committed_events: Vec<(Event, Option<ScriptBuf>, Option<u64>)>,
   pub(super) height: u32,
   pub(super) index: &'index Index,
   pub(super) outputs_cached: u64,
@@ -335,6 +336,10 @@ impl Updater<'_> {
       wtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
 
+    let send_events = self.index.sends_events();
+
+    let mut events = (send_events || self.index.index_events).then(Vec::new);
+
     if self.index.index_inscriptions || self.index.index_addresses || self.index.index_sats {
       self.index_utxo_entries(
         &block,
@@ -346,13 +351,23 @@ impl Updater<'_> {
         &mut statistic_to_count,
         &mut sat_ranges_written,
         &mut outputs_in_block,
+        events.as_mut(),
       )?;
     }
 
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut outpoint_to_rune_history = wtx.open_table(OUTPOINT_TO_RUNE_HISTORY)?;
+      let mut outpoint_to_rune_script_pubkey = wtx.open_table(OUTPOINT_TO_RUNE_SCRIPT_PUBKEY)?;
+      let mut rune_id_to_holder = wtx.open_multimap_table(RUNE_ID_TO_HOLDER)?;
+      let mut rune_id_and_height_to_rune_activity =
+        wtx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+      let mut rune_id_and_script_pubkey_to_rune_holding =
+        wtx.open_table(RUNE_ID_AND_SCRIPT_PUBKEY_TO_RUNE_HOLDING)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
+      let mut script_pubkey_to_rune_outpoint =
+        wtx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
       let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
 
@@ -362,20 +377,31 @@ impl Updater<'_> {
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
+        activity: HashMap::new(),
+        activity_table: &mut rune_id_and_height_to_rune_activity,
         block_time: block.header.time,
         burned: HashMap::new(),
         client: &self.index.client,
+        events: events.as_mut(),
         height: self.height,
+        holder_to_holding: &mut rune_id_and_script_pubkey_to_rune_holding,
         id_to_entry: &mut rune_id_to_rune_entry,
+        index_addresses: self.index.index_addresses,
+        index_rune_activity: self.index.index_rune_activity,
+        index_rune_history: self.index.index_rune_history,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
           self.index.settings.chain().network(),
           Height(self.height),
         ),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outpoint_to_history: &mut outpoint_to_rune_history,
+        outpoint_to_script_pubkey: &mut outpoint_to_rune_script_pubkey,
+        receivers: HashMap::new(),
         rune_to_id: &mut rune_to_rune_id,
+        rune_id_to_holder: &mut rune_id_to_holder,
         runes,
+        script_pubkey_to_outpoint: &mut script_pubkey_to_rune_outpoint,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_rune: &mut transaction_id_to_rune,
@@ -388,8 +414,50 @@ impl Updater<'_> {
       rune_updater.update()?;
     }
 
+    Index::remove_offers_spent_in_block(wtx, &block)?;
+
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
+    if let Some(mut events) = events {
+      events.push(Event::BlockCommitted {
+        block_hash: block.header.block_hash(),
+        block_height: self.height,
+      });
+
+      let next_number = if self.index.index_events {
+        let mut number_to_event = wtx.open_table(NUMBER_TO_EVENT)?;
+
+        let next_number = number_to_event
+          .last()?
+          .map(|(number, _)| number.value() + 1)
+          .unwrap_or(0);
+
+        Index::insert_events(&mut number_to_event, next_number, &events)?;
+
+        Some(next_number)
+      } else {
+        None
+      };
+
+      if send_events {
+        let transactions = block
+          .txdata
+          .iter()
+          .map(|(tx, txid)| (*txid, tx))
+          .collect::<HashMap<Txid, &Transaction>>();
+
+        // events are sent once the write transaction containing the block has
+        // been committed, so that events for blocks that are discarded before
+        // being committed, due to an error or reorg, are never sent
+        self
+          .committed_events
+          .extend(events.into_iter().zip(0..).map(|(event, i)| {
+            let script_pubkey = Self::event_script_pubkey(&transactions, &event);
+            (event, script_pubkey, next_number.map(|number| number + i))
+          }));
+      }
+    }
+
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -401,6 +469,21 @@ impl Updater<'_> {
     Ok(())
   }
 
+  /// Returns the script pubkey of the output `event` concerns, which, if the
+  /// event concerns an output, is created by one of the block's `transactions`.
+  fn event_script_pubkey(
+    transactions: &HashMap<Txid, &Transaction>,
+    event: &Event,
+  ) -> Option<ScriptBuf> {
+    let outpoint = event.outpoint()?;
+
+    transactions
+      .get(&outpoint.txid)?
+      .output
+      .get(outpoint.vout.into_usize())
+      .map(|output| output.script_pubkey.clone())
+  }
+
   fn index_utxo_entries<'wtx>(
     &mut self,
     block: &BlockData,
@@ -412,20 +495,26 @@ impl Updater<'_> {
     statistic_to_count: &mut Table<'wtx, u64, u64>,
     sat_ranges_written: &mut u64,
     outputs_in_block: &mut u64,
+    events: Option<&mut Vec<Event>>,
   ) -> Result {
     let mut collection_to_latest_child =
       wtx.open_table(COLLECTION_SEQUENCE_NUMBER_TO_LATEST_CHILD_SEQUENCE_NUMBER)?;
+    let mut content_type_sequence_numbers = wtx.open_table(CONTENT_TYPE_SEQUENCE_NUMBERS)?;
     let mut gallery_sequence_numbers = wtx.open_table(GALLERY_SEQUENCE_NUMBERS)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
     let mut inscription_number_to_sequence_number =
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let mut inscription_transfer = wtx.open_table(INSCRIPTION_TRANSFER)?;
     let mut latest_child_to_collection =
       wtx.open_multimap_table(LATEST_CHILD_SEQUENCE_NUMBER_TO_COLLECTION_SEQUENCE_NUMBER)?;
+    let mut metaprotocol_sequence_numbers = wtx.open_table(METAPROTOCOL_SEQUENCE_NUMBERS)?;
+    let mut metaprotocol_to_count = wtx.open_table(METAPROTOCOL_TO_COUNT)?;
     let mut outpoint_to_utxo_entry = wtx.open_table(OUTPOINT_TO_UTXO_ENTRY)?;
     let mut sat_to_satpoint = wtx.open_table(SAT_TO_SATPOINT)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
     let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    let mut search_term_sequence_numbers = wtx.open_table(SEARCH_TERM_SEQUENCE_NUMBERS)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -509,19 +598,29 @@ impl Updater<'_> {
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
       collection_to_latest_child: &mut collection_to_latest_child,
+      content_type_sequence_numbers: &mut content_type_sequence_numbers,
       cursed_inscription_count,
+      events,
       flotsam: Vec::new(),
       gallery_sequence_numbers: &mut gallery_sequence_numbers,
       height: self.height,
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: inscription_id_to_sequence_number,
+      index_inscription_history: self.index.index_inscription_history,
+      index_filters: self.index.index_filters,
+      index_metaprotocols: self.index.index_metaprotocols,
+      index_search: self.index.index_search,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
+      inscription_transfer: &mut inscription_transfer,
       latest_child_to_collection: &mut latest_child_to_collection,
       lost_sats,
+      metaprotocol_sequence_numbers: &mut metaprotocol_sequence_numbers,
+      metaprotocol_to_count: &mut metaprotocol_to_count,
       next_sequence_number,
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
+      search_term_sequence_numbers: &mut search_term_sequence_numbers,
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
       timestamp: block.header.time,
@@ -879,6 +978,12 @@ impl Updater<'_> {
 
     Reorg::update_savepoints(self.index, self.height)?;
 
+    if let Some(sender) = &self.index.event_sender {
+      for (event, script_pubkey, number) in self.committed_events.drain(..) {
+        sender.blocking_send(event, script_pubkey, number)?;
+      }
+    }
+
     Ok(())
   }
 }
@@ -23,37 +23,51 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    content_type: Option<String>,
     cursed: bool,
     fee: u64,
     gallery: bool,
     hidden: bool,
+    metaprotocol: Option<String>,
     parents: Vec<InscriptionId>,
     reinscription: bool,
+    search_terms: BTreeSet<String>,
     unbound: bool,
     vindicated: bool,
   },
   Old {
     sequence_number: u32,
     old_satpoint: SatPoint,
+    txid: Txid,
   },
 }
 
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
   pub(super) collection_to_latest_child: &'a mut Table<'tx, u32, u32>,
+  pub(super) content_type_sequence_numbers: &'a mut Table<'tx, (&'static str, u32), ()>,
   pub(super) cursed_inscription_count: u64,
+  pub(super) events: Option<&'a mut Vec<Event>>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) gallery_sequence_numbers: &'a mut Table<'tx, u32, ()>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
   pub(super) home_inscriptions: &'a mut Table<'tx, u32, InscriptionIdValue>,
   pub(super) id_to_sequence_number: &'a mut Table<'tx, InscriptionIdValue, u32>,
+  pub(super) index_filters: bool,
+  pub(super) index_metaprotocols: bool,
+  pub(super) index_inscription_history: bool,
+  pub(super) index_search: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'tx, i32, u32>,
+  pub(super) inscription_transfer: &'a mut Table<'tx, (u32, u32), InscriptionTransferValue>,
   pub(super) latest_child_to_collection: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) lost_sats: u64,
+  pub(super) metaprotocol_sequence_numbers: &'a mut Table<'tx, (&'static str, u32), ()>,
+  pub(super) metaprotocol_to_count: &'a mut Table<'tx, &'static str, u64>,
   pub(super) next_sequence_number: u32,
   pub(super) reward: u64,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
+  pub(super) search_term_sequence_numbers: &'a mut Table<'tx, (&'static str, u32), ()>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
   pub(super) timestamp: u32,
@@ -121,6 +135,7 @@ impl InscriptionUpdater<'_, '_> {
           origin: Origin::Old {
             sequence_number,
             old_satpoint,
+            txid,
           },
         });
 
@@ -200,12 +215,19 @@ impl InscriptionUpdater<'_, '_> {
           inscription_id,
           offset,
           origin: Origin::New {
+            content_type: inscription.payload.content_type().map(str::to_string),
             cursed: curse.is_some() && !jubilant,
             fee: 0,
             gallery: !inscription.payload.properties().gallery.is_empty(),
             hidden: inscription.payload.hidden(),
+            metaprotocol: inscription.payload.metaprotocol().map(str::to_string),
             parents: inscription.payload.parents(),
             reinscription: inscribed_offsets.contains_key(&offset),
+            search_terms: if self.index_search {
+              search::terms(&inscription.payload)
+            } else {
+              BTreeSet::new()
+            },
             unbound: input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
               || inscription.payload.unrecognized_even_field,
@@ -296,17 +318,13 @@ impl InscriptionUpdater<'_, '_> {
           offset: flotsam.offset - output_value,
         };
 
-        new_locations.push((
-          new_satpoint,
-          inscriptions.next().unwrap(),
-          txout.script_pubkey.is_op_return(),
-        ));
+        new_locations.push((new_satpoint, inscriptions.next().unwrap(), txout));
       }
 
       output_value = end;
     }
 
-    for (new_satpoint, flotsam, op_return) in new_locations.into_iter() {
+    for (new_satpoint, flotsam, txout) in new_locations.into_iter() {
       let output_utxo_entry =
         &mut output_utxo_entries[usize::try_from(new_satpoint.outpoint.vout).unwrap()];
 
@@ -314,7 +332,7 @@ impl InscriptionUpdater<'_, '_> {
         input_sat_ranges,
         flotsam,
         new_satpoint,
-        op_return,
+        Some(&txout.script_pubkey),
         Some(output_utxo_entry),
         utxo_cache,
         index,
@@ -331,7 +349,7 @@ impl InscriptionUpdater<'_, '_> {
           input_sat_ranges,
           flotsam,
           new_satpoint,
-          false,
+          None,
           None,
           utxo_cache,
           index,
@@ -374,16 +392,18 @@ impl InscriptionUpdater<'_, '_> {
     input_sat_ranges: Option<&Vec<&[u8]>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
-    op_return: bool,
+    script_pubkey: Option<&Script>,
     mut normal_output_utxo_entry: Option<&mut UtxoEntryBuf>,
     utxo_cache: &mut HashMap<OutPoint, UtxoEntryBuf>,
     index: &Index,
   ) -> Result {
+    let op_return = script_pubkey.is_some_and(|script_pubkey| script_pubkey.is_op_return());
     let inscription_id = flotsam.inscription_id;
     let (unbound, sequence_number) = match flotsam.origin {
       Origin::Old {
         sequence_number,
         old_satpoint,
+        txid,
       } => {
         if op_return {
           let entry = InscriptionEntry::load(
@@ -403,25 +423,49 @@ impl InscriptionUpdater<'_, '_> {
           )?;
         }
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
+        if self.index_inscription_history {
+          let transfer_number = self
+            .inscription_transfer
+            .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+            .next_back()
+            .transpose()?
+            .map(|(key, _)| key.value().1 + 1)
+            .unwrap_or(0);
+
+          self.inscription_transfer.insert(
+            (sequence_number, transfer_number),
+            (
+              self.height,
+              txid.store(),
+              old_satpoint.store(),
+              new_satpoint.store(),
+              script_pubkey.map(|script_pubkey| script_pubkey.to_bytes()),
+            ),
+          )?;
+        }
+
+        if let Some(events) = &mut self.events {
+          events.push(Event::InscriptionTransferred {
             block_height: self.height,
             inscription_id,
             new_location: new_satpoint,
             old_location: old_satpoint,
             sequence_number,
-          })?;
+          });
         }
 
         (false, sequence_number)
       }
       Origin::New {
+        content_type,
         cursed,
         fee,
         gallery,
         hidden,
+        metaprotocol,
         parents,
         reinscription,
+        search_terms,
         unbound,
         vindicated,
       } => {
@@ -482,6 +526,46 @@ impl InscriptionUpdater<'_, '_> {
           self.sat_to_sequence_number.insert(&n, &sequence_number)?;
         }
 
+        if self.index_filters
+          && let Some(content_type) = &content_type
+        {
+          for key in InscriptionFilter::content_type_keys(content_type) {
+            self
+              .content_type_sequence_numbers
+              .insert((key.as_str(), sequence_number), ())?;
+          }
+        }
+
+        // metaprotocol sequence numbers are used by both the metaprotocol
+        // filter and the metaprotocol index
+        if (self.index_filters || self.index_metaprotocols)
+          && let Some(metaprotocol) = &metaprotocol
+        {
+          self
+            .metaprotocol_sequence_numbers
+            .insert((metaprotocol.as_str(), sequence_number), ())?;
+        }
+
+        if self.index_metaprotocols
+          && let Some(metaprotocol) = &metaprotocol
+        {
+          let count = self
+            .metaprotocol_to_count
+            .get(metaprotocol.as_str())?
+            .map(|count| count.value())
+            .unwrap_or_default();
+
+          self
+            .metaprotocol_to_count
+            .insert(metaprotocol.as_str(), count + 1)?;
+        }
+
+        for term in &search_terms {
+          self
+            .search_term_sequence_numbers
+            .insert((term.as_str(), sequence_number), ())?;
+        }
+
         let mut parent_inscription_ids = Vec::new();
         let mut parent_sequence_numbers = Vec::new();
 
@@ -533,15 +617,15 @@ impl InscriptionUpdater<'_, '_> {
           self.gallery_sequence_numbers.insert(sequence_number, ())?;
         }
 
-        if let Some(ref sender) = index.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
+        if let Some(events) = &mut self.events {
+          events.push(Event::InscriptionCreated {
             block_height: self.height,
             charms,
             inscription_id,
             location: (!unbound).then_some(new_satpoint),
             parent_inscription_ids,
             sequence_number,
-          })?;
+          });
         }
 
         self.sequence_number_to_entry.insert(
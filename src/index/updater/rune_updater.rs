@@ -1,17 +1,29 @@
 use super::*;
 
 pub(super) struct RuneUpdater<'a, 'tx, 'client> {
+  pub(super) activity: HashMap<RuneId, RuneActivityEntry>,
+  pub(super) activity_table: &'a mut Table<'tx, (RuneIdValue, u32), RuneActivityValue>,
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a mpsc::Sender<Event>>,
+  pub(super) events: Option<&'a mut Vec<Event>>,
   pub(super) height: u32,
+  pub(super) holder_to_holding: &'a mut Table<'tx, (RuneIdValue, &'static [u8]), (u128, u64)>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) index_addresses: bool,
+  pub(super) index_rune_activity: bool,
+  pub(super) index_rune_history: bool,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_history: &'a mut Table<'tx, &'static OutPointValue, RuneHistoryValue>,
+  pub(super) outpoint_to_script_pubkey: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) receivers: HashMap<RuneId, HashSet<ScriptBuf>>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
+  pub(super) rune_id_to_holder: &'a mut MultimapTable<'tx, RuneIdValue, (u128, u64, &'static [u8])>,
   pub(super) runes: u64,
+  pub(super) script_pubkey_to_outpoint:
+    &'a mut MultimapTable<'tx, &'static [u8], (u32, OutPointValue)>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
@@ -21,7 +33,13 @@ impl RuneUpdater<'_, '_, '_> {
   pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
     let artifact = Runestone::decipher(tx);
 
-    let mut unallocated = self.unallocated(tx)?;
+    let mut unallocated = self.unallocated(tx, txid)?;
+
+    if self.index_rune_activity {
+      for id in unallocated.keys() {
+        self.activity.entry(*id).or_default().transfers += 1;
+      }
+    }
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
 
@@ -31,13 +49,17 @@ impl RuneUpdater<'_, '_, '_> {
       {
         *unallocated.entry(id).or_default() += amount;
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneMinted {
+        if self.index_rune_activity {
+          self.activity.entry(id).or_default().mints += 1;
+        }
+
+        if let Some(events) = &mut self.events {
+          events.push(Event::RuneMinted {
             block_height: self.height,
             txid,
             rune_id: id,
             amount: amount.n(),
-          })?;
+          });
         }
       }
 
@@ -198,46 +220,90 @@ impl RuneUpdater<'_, '_, '_> {
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
+        if self.index_rune_activity {
+          self
+            .receivers
+            .entry(id)
+            .or_default()
+            .insert(tx.output[vout].script_pubkey.clone());
+        }
+
+        if self.index_addresses {
+          self.receive_holding(id, tx.output[vout].script_pubkey.as_bytes(), balance.n())?;
+        }
+
+        if let Some(events) = &mut self.events {
+          events.push(Event::RuneTransferred {
             outpoint,
             block_height: self.height,
             txid,
             rune_id: id,
             amount: balance.0,
-          })?;
+          });
         }
       }
 
       self
         .outpoint_to_balances
         .insert(&outpoint.store(), buffer.as_slice())?;
+
+      if self.index_addresses {
+        self
+          .outpoint_to_script_pubkey
+          .insert(&outpoint.store(), tx.output[vout].script_pubkey.as_bytes())?;
+      }
+
+      if self.index_rune_history {
+        self
+          .outpoint_to_history
+          .insert(&outpoint.store(), (self.height, None, buffer.as_slice()))?;
+
+        self.script_pubkey_to_outpoint.insert(
+          tx.output[vout].script_pubkey.as_bytes(),
+          (self.height, outpoint.store()),
+        )?;
+      }
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
+      if self.index_rune_activity {
+        let activity = self.activity.entry(id).or_default();
+        activity.burned = activity.burned.checked_add(amount.n()).unwrap();
+      }
+
+      if let Some(events) = &mut self.events {
+        events.push(Event::RuneBurned {
           block_height: self.height,
           txid,
           rune_id: id,
           amount: amount.n(),
-        })?;
+        });
       }
     }
 
     Ok(())
   }
 
-  pub(super) fn update(self) -> Result {
+  pub(super) fn update(mut self) -> Result {
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
       self.id_to_entry.insert(&rune_id.store(), entry.store())?;
     }
 
+    for (rune_id, receivers) in &self.receivers {
+      self.activity.entry(*rune_id).or_default().receivers = receivers.len().try_into().unwrap();
+    }
+
+    for (rune_id, activity) in self.activity {
+      self
+        .activity_table
+        .insert((rune_id.store(), self.height), activity.store())?;
+    }
+
     Ok(())
   }
 
@@ -308,12 +374,12 @@ impl RuneUpdater<'_, '_, '_> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
+    if let Some(events) = &mut self.events {
+      events.push(Event::RuneEtched {
         block_height: self.height,
         txid,
         rune_id: id,
-      })?;
+      });
     }
 
     let inscription_id = InscriptionId { txid, index: 0 };
@@ -466,22 +532,116 @@ impl RuneUpdater<'_, '_, '_> {
     Ok(false)
   }
 
-  fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
+  fn receive_holding(&mut self, id: RuneId, script_pubkey: &[u8], amount: u128) -> Result {
+    let (balance, outputs) = self.remove_holder(id, script_pubkey)?.unwrap_or_default();
+
+    self.insert_holder(
+      id,
+      script_pubkey,
+      balance.checked_add(amount).unwrap(),
+      outputs + 1,
+    )
+  }
+
+  fn spend_holding(&mut self, id: RuneId, script_pubkey: &[u8], amount: u128) -> Result {
+    let (balance, outputs) = self.remove_holder(id, script_pubkey)?.unwrap();
+
+    if outputs > 1 {
+      self.insert_holder(
+        id,
+        script_pubkey,
+        balance.checked_sub(amount).unwrap(),
+        outputs - 1,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn insert_holder(
+    &mut self,
+    id: RuneId,
+    script_pubkey: &[u8],
+    balance: u128,
+    outputs: u64,
+  ) -> Result {
+    self
+      .holder_to_holding
+      .insert((id.store(), script_pubkey), (balance, outputs))?;
+
+    self
+      .rune_id_to_holder
+      .insert(id.store(), (balance, outputs, script_pubkey))?;
+
+    Ok(())
+  }
+
+  fn remove_holder(&mut self, id: RuneId, script_pubkey: &[u8]) -> Result<Option<(u128, u64)>> {
+    let Some((balance, outputs)) = self
+      .holder_to_holding
+      .remove((id.store(), script_pubkey))?
+      .map(|holding| holding.value())
+    else {
+      return Ok(None);
+    };
+
+    self
+      .rune_id_to_holder
+      .remove(id.store(), (balance, outputs, script_pubkey))?;
+
+    Ok(Some((balance, outputs)))
+  }
+
+  fn unallocated(&mut self, tx: &Transaction, txid: Txid) -> Result<HashMap<RuneId, Lot>> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
 
     // increment unallocated runes with the runes in tx inputs
     for input in &tx.input {
-      if let Some(guard) = self
+      let balances = self
         .outpoint_to_balances
         .remove(&input.previous_output.store())?
-      {
-        let buffer = guard.value();
+        .map(|guard| guard.value().to_vec());
+
+      if let Some(buffer) = balances {
+        let script_pubkey = if self.index_addresses {
+          self
+            .outpoint_to_script_pubkey
+            .remove(&input.previous_output.store())?
+            .map(|script_pubkey| script_pubkey.value().to_vec())
+        } else {
+          None
+        };
+
         let mut i = 0;
         while i < buffer.len() {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
           *unallocated.entry(id).or_default() += balance;
+
+          if let Some(script_pubkey) = &script_pubkey {
+            self.spend_holding(id, script_pubkey, balance)?;
+          }
+        }
+
+        if self.index_rune_history {
+          let outpoint = input.previous_output.store();
+
+          let history = self.outpoint_to_history.get(&outpoint)?.map(|history| {
+            let (height, _spent, balances) = history.value();
+            (height, balances.to_vec())
+          });
+
+          if let Some((height, balances)) = history {
+            self.outpoint_to_history.insert(
+              &outpoint,
+              (
+                height,
+                Some((txid.store(), self.height)),
+                balances.as_slice(),
+              ),
+            )?;
+          }
         }
       }
     }
@@ -56,10 +56,33 @@ pub struct Options {
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Store index events.")]
+  pub(crate) index_events: bool,
+  #[arg(long, help = "Index inscription content types and metaprotocols.")]
+  pub(crate) index_filters: bool,
+  #[arg(long, help = "Track transfer history of inscriptions.")]
+  pub(crate) index_inscription_history: bool,
+  #[arg(long, help = "Index inscriptions by metaprotocol.")]
+  pub(crate) index_metaprotocols: bool,
+  #[arg(
+    long,
+    help = "Track per-block activity of runes. Requires `--index-runes`."
+  )]
+  pub(crate) index_rune_activity: bool,
+  #[arg(
+    long,
+    help = "Track rune balances of spent outputs. Requires `--index-runes`."
+  )]
+  pub(crate) index_rune_history: bool,
   #[arg(long, help = "Track location of runes.")]
   pub(crate) index_runes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(
+    long,
+    help = "Index inscription text, titles, traits, and metadata keys for search."
+  )]
+  pub(crate) index_search: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
   #[arg(long, help = "Run in integration test mode.")]
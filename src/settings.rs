@@ -20,8 +20,15 @@ pub struct Settings {
   index: Option<PathBuf>,
   index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_events: bool,
+  index_filters: bool,
+  index_inscription_history: bool,
+  index_metaprotocols: bool,
+  index_rune_activity: bool,
+  index_rune_history: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
   index_transactions: bool,
   integration_test: bool,
   max_savepoints: Option<usize>,
@@ -30,6 +37,8 @@ pub struct Settings {
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  webhook_secret: Option<String>,
+  webhook_urls: Option<Vec<String>>,
 }
 
 impl Settings {
@@ -135,8 +144,15 @@ impl Settings {
       index: self.index.or(source.index),
       index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_events: self.index_events || source.index_events,
+      index_filters: self.index_filters || source.index_filters,
+      index_inscription_history: self.index_inscription_history || source.index_inscription_history,
+      index_metaprotocols: self.index_metaprotocols || source.index_metaprotocols,
+      index_rune_activity: self.index_rune_activity || source.index_rune_activity,
+      index_rune_history: self.index_rune_history || source.index_rune_history,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
+      index_search: self.index_search || source.index_search,
       index_transactions: self.index_transactions || source.index_transactions,
       integration_test: self.integration_test || source.integration_test,
       max_savepoints: self.max_savepoints.or(source.max_savepoints),
@@ -145,6 +161,16 @@ impl Settings {
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      webhook_secret: self.webhook_secret.or(source.webhook_secret),
+      webhook_urls: Some(
+        self
+          .webhook_urls
+          .iter()
+          .flatten()
+          .chain(source.webhook_urls.iter().flatten())
+          .cloned()
+          .collect(),
+      ),
     }
   }
 
@@ -173,8 +199,15 @@ impl Settings {
       index: options.index,
       index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_events: options.index_events,
+      index_filters: options.index_filters,
+      index_inscription_history: options.index_inscription_history,
+      index_metaprotocols: options.index_metaprotocols,
+      index_rune_activity: options.index_rune_activity,
+      index_rune_history: options.index_rune_history,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
+      index_search: options.index_search,
       index_transactions: options.index_transactions,
       integration_test: options.integration_test,
       max_savepoints: options.max_savepoints,
@@ -183,6 +216,8 @@ impl Settings {
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      webhook_secret: None,
+      webhook_urls: None,
     }
   }
 
@@ -221,6 +256,15 @@ impl Settings {
         })
     };
 
+    let get_strings = |key| {
+      env.get(key).map(|strings| {
+        strings
+          .split_whitespace()
+          .map(str::to_string)
+          .collect::<Vec<String>>()
+      })
+    };
+
     let get_u16 = |key| {
       env
         .get(key)
@@ -263,8 +307,15 @@ impl Settings {
       index: get_path("INDEX"),
       index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_events: get_bool("INDEX_EVENTS"),
+      index_filters: get_bool("INDEX_FILTERS"),
+      index_inscription_history: get_bool("INDEX_INSCRIPTION_HISTORY"),
+      index_metaprotocols: get_bool("INDEX_METAPROTOCOLS"),
+      index_rune_activity: get_bool("INDEX_RUNE_ACTIVITY"),
+      index_rune_history: get_bool("INDEX_RUNE_HISTORY"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
+      index_search: get_bool("INDEX_SEARCH"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
       integration_test: get_bool("INTEGRATION_TEST"),
       max_savepoints: get_usize("MAX_SAVEPOINTS")?,
@@ -273,6 +324,8 @@ impl Settings {
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      webhook_secret: get_string("WEBHOOK_SECRET"),
+      webhook_urls: get_strings("WEBHOOK_URLS"),
     })
   }
 
@@ -295,8 +348,15 @@ impl Settings {
       index: None,
       index_addresses: true,
       index_cache_size: None,
+      index_events: true,
+      index_filters: true,
+      index_inscription_history: true,
+      index_metaprotocols: true,
+      index_rune_activity: true,
+      index_rune_history: true,
       index_runes: true,
       index_sats: true,
+      index_search: true,
       index_transactions: false,
       integration_test: false,
       max_savepoints: None,
@@ -305,6 +365,8 @@ impl Settings {
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      webhook_secret: None,
+      webhook_urls: None,
     }
   }
 
@@ -371,8 +433,15 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_events: self.index_events,
+      index_filters: self.index_filters,
+      index_inscription_history: self.index_inscription_history,
+      index_metaprotocols: self.index_metaprotocols,
+      index_rune_activity: self.index_rune_activity,
+      index_rune_history: self.index_rune_history,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
+      index_search: self.index_search,
       index_transactions: self.index_transactions,
       integration_test: self.integration_test,
       max_savepoints: Some(self.max_savepoints.unwrap_or(2)),
@@ -381,6 +450,8 @@ impl Settings {
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      webhook_secret: self.webhook_secret,
+      webhook_urls: self.webhook_urls,
     })
   }
 
@@ -556,6 +627,30 @@ impl Settings {
     !self.no_index_inscriptions
   }
 
+  pub fn index_events_raw(&self) -> bool {
+    self.index_events
+  }
+
+  pub fn index_filters_raw(&self) -> bool {
+    self.index_filters
+  }
+
+  pub fn index_inscription_history_raw(&self) -> bool {
+    self.index_inscription_history
+  }
+
+  pub fn index_metaprotocols_raw(&self) -> bool {
+    self.index_metaprotocols
+  }
+
+  pub fn index_rune_activity_raw(&self) -> bool {
+    self.index_rune_activity
+  }
+
+  pub fn index_rune_history_raw(&self) -> bool {
+    self.index_rune_history
+  }
+
   pub fn index_runes_raw(&self) -> bool {
     self.index_runes
   }
@@ -568,6 +663,10 @@ impl Settings {
     self.index_sats
   }
 
+  pub fn index_search_raw(&self) -> bool {
+    self.index_search
+  }
+
   pub fn index_transactions_raw(&self) -> bool {
     self.index_transactions
   }
@@ -584,6 +683,14 @@ impl Settings {
       .unwrap_or_default()
   }
 
+  pub fn webhook_secret(&self) -> Option<&str> {
+    self.webhook_secret.as_deref()
+  }
+
+  pub fn webhook_urls(&self) -> &[String] {
+    self.webhook_urls.as_deref().unwrap_or_default()
+  }
+
   pub fn bitcoin_rpc_url(&self, wallet_name: Option<String>) -> String {
     let base_url = self.bitcoin_rpc_url.as_ref().unwrap();
     match wallet_name {
@@ -1091,6 +1198,13 @@ mod tests {
       ("INDEX", "index"),
       ("INDEX_ADDRESSES", "1"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_EVENTS", "1"),
+      ("INDEX_FILTERS", "1"),
+      ("INDEX_INSCRIPTION_HISTORY", "1"),
+      ("INDEX_METAPROTOCOLS", "1"),
+      ("INDEX_RUNE_ACTIVITY", "1"),
+      ("INDEX_RUNE_HISTORY", "1"),
+      ("INDEX_SEARCH", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
@@ -1101,6 +1215,8 @@ mod tests {
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
+      ("WEBHOOK_SECRET", "webhook secret"),
+      ("WEBHOOK_URLS", "http://foo.com/events http://bar.com/events"),
     ]
     .into_iter()
     .map(|(key, value)| (key.into(), value.into()))
@@ -1139,14 +1255,26 @@ mod tests {
         index: Some("index".into()),
         index_addresses: true,
         index_cache_size: Some(4),
+        index_events: true,
+        index_filters: true,
+        index_inscription_history: true,
+        index_metaprotocols: true,
+        index_rune_activity: true,
+        index_rune_history: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        webhook_secret: Some("webhook secret".into()),
+        webhook_urls: Some(vec![
+          "http://foo.com/events".into(),
+          "http://bar.com/events".into(),
+        ]),
       }
     );
   }
@@ -1173,6 +1301,13 @@ mod tests {
           "--height-limit=3",
           "--index-addresses",
           "--index-cache-size=4",
+          "--index-events",
+          "--index-filters",
+          "--index-inscription-history",
+          "--index-metaprotocols",
+          "--index-rune-activity",
+          "--index-rune-history",
+          "--index-search",
           "--index-runes",
           "--index-sats",
           "--index-transactions",
@@ -1204,14 +1339,23 @@ mod tests {
         index: Some("index".into()),
         index_addresses: true,
         index_cache_size: Some(4),
+        index_events: true,
+        index_filters: true,
+        index_inscription_history: true,
+        index_metaprotocols: true,
+        index_rune_activity: true,
+        index_rune_history: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
         index_transactions: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        webhook_secret: None,
+        webhook_urls: None,
       }
     );
   }
@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::index::event::EventSubscribers};
 
 pub mod balances;
 pub mod decode;
@@ -72,10 +72,21 @@ impl Subcommand {
       Self::Parse(parse) => parse.run(),
       Self::Runes => runes::run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        // events are only consumed by webhooks and `/events` subscribers
+        let (index, event_receiver) =
+          if settings.webhook_urls().is_empty() && server.disable_json_api {
+            (Index::open(&settings)?, None)
+          } else {
+            let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+            (
+              Index::open_with_script_pubkey_event_sender(&settings, event_sender)?
+                .with_event_subscribers(EventSubscribers::default()),
+              Some(event_receiver),
+            )
+          };
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(settings, index, handle, None)
+        server.run(settings, Arc::new(index), handle, None, event_receiver)
       }
       Self::Settings => settings::run(settings),
       Self::Subsidy(subsidy) => subsidy.run(),
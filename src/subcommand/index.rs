@@ -1,11 +1,14 @@
 use super::*;
 
+mod events;
 mod export;
 pub mod info;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
+  #[command(about = "Print stored index events")]
+  Events(events::Events),
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
   #[command(about = "Print index statistics")]
@@ -17,6 +20,7 @@ pub(crate) enum IndexSubcommand {
 impl IndexSubcommand {
   pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
     match self {
+      Self::Events(events) => events.run(settings),
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
       Self::Update => update::run(settings),
@@ -0,0 +1,33 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Events {
+  #[arg(long, help = "Only print events with cursor greater than <AFTER>")]
+  after: Option<u64>,
+  #[arg(long, default_value = "100", help = "Print at most <LIMIT> events")]
+  limit: usize,
+}
+
+impl Events {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_event_index(),
+      "`ord index events` requires index created with `--index-events` flag",
+    );
+
+    index.update()?;
+
+    let (events, more) = index.get_events(self.after, self.limit)?;
+
+    Ok(Some(Box::new(api::Events {
+      cursor: events.last().map(|(cursor, _event)| *cursor),
+      events: events
+        .into_iter()
+        .map(|(cursor, event)| api::EventEntry { cursor, event })
+        .collect(),
+      more,
+    })))
+  }
+}
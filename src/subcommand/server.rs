@@ -3,21 +3,30 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    webhook::Webhook,
   },
   super::*,
+  crate::index::{
+    OfferInsertion,
+    event::{Event, EventKind},
+  },
   crate::templates::{
     AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, GalleriesHtml,
     GalleryHtml, HomeHtml, InputHtml, InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml,
-    ItemHtml, OutputHtml, PageContent, PageHtml, ParentsHtml, PreviewAudioHtml, PreviewCodeHtml,
-    PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml,
-    PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RareTxt, RuneHtml, RuneNotFoundHtml,
-    RunesHtml, SatHtml, SatscardHtml, TransactionHtml,
+    ItemHtml, MetaprotocolHtml, MetaprotocolsHtml, OutputHtml, PageContent, PageHtml, ParentsHtml,
+    PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
+    PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
+    RareTxt, RuneHtml, RuneNotFoundHtml, RunesCalendarHtml, RunesHtml, SatHtml, SatscardHtml,
+    SearchHtml, TransactionHtml,
   },
   axum::{
     Router,
     extract::{DefaultBodyLimit, Extension, Json, Path, Query},
     http::{self, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri, header},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      IntoResponse, Redirect, Response,
+      sse::{self, KeepAlive, Sse},
+    },
     routing::{get, post},
   },
   axum_server::Handle,
@@ -28,7 +37,11 @@ use {
     axum::AxumAcceptor,
     caches::DirCache,
   },
-  tokio_stream::StreamExt,
+  tokio::sync::{broadcast, mpsc},
+  tokio_stream::{
+    StreamExt,
+    wrappers::{BroadcastStream, errors::BroadcastStreamRecvError},
+  },
   tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
@@ -45,7 +58,11 @@ mod error;
 pub mod query;
 mod r;
 mod server_config;
+mod webhook;
 
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+const MAX_EVENTS_EXAMINED: usize = 1000;
+const MAX_RUNE_ACTIVITY_BLOCKS: u32 = 10_000;
 const MEBIBYTE: usize = 1 << 20;
 const PAGE_SIZE: usize = 100;
 
@@ -76,6 +93,98 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct SearchQuery {
+  before: Option<u32>,
+  q: Option<String>,
+  query: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventsQuery {
+  after: Option<u64>,
+  address: Option<Address<NetworkUnchecked>>,
+  inscription: Option<InscriptionId>,
+  kind: Option<EventKind>,
+  rune: Option<RuneId>,
+}
+
+/// An event, along with the script pubkey of the output it concerns, if any,
+/// and its number, if events are stored in the index.
+#[derive(Clone, Debug)]
+pub(crate) struct BroadcastEvent {
+  pub(crate) event: Event,
+  number: Option<u64>,
+  script_pubkey: Option<ScriptBuf>,
+}
+
+#[derive(Clone)]
+struct EventBroadcast {
+  sender: broadcast::Sender<BroadcastEvent>,
+}
+
+#[derive(Clone)]
+struct EventFilter {
+  inscription: Option<InscriptionId>,
+  kind: Option<EventKind>,
+  rune: Option<RuneId>,
+  script_pubkey: Option<ScriptBuf>,
+}
+
+impl EventFilter {
+  fn new(query: &EventsQuery, network: Network) -> ServerResult<Self> {
+    Ok(Self {
+      inscription: query.inscription,
+      kind: query.kind,
+      rune: query.rune,
+      script_pubkey: query
+        .address
+        .clone()
+        .map(|address| {
+          address
+            .require_network(network)
+            .map(|address| address.script_pubkey())
+            .map_err(|err| ServerError::BadRequest(err.to_string()))
+        })
+        .transpose()?,
+    })
+  }
+
+  fn is_empty(&self) -> bool {
+    self.inscription.is_none()
+      && self.kind.is_none()
+      && self.rune.is_none()
+      && self.script_pubkey.is_none()
+  }
+
+  /// Returns whether `event` passes the filter. `script_pubkey` is only called
+  /// to get the script pubkey of the output the event concerns when filtering
+  /// by address.
+  fn matches(&self, event: &Event, script_pubkey: impl FnOnce() -> Option<ScriptBuf>) -> bool {
+    // block events are sent to all subscribers, so that they can roll back
+    // state derived from reverted blocks
+    if event.is_block_event() {
+      return true;
+    }
+
+    self.kind.is_none_or(|kind| kind == event.kind())
+      && self
+        .inscription
+        .is_none_or(|id| event.inscription_id() == Some(id))
+      && self.rune.is_none_or(|id| event.rune_id() == Some(id))
+      && self
+        .script_pubkey
+        .as_ref()
+        .is_none_or(|expected| script_pubkey().as_ref() == Some(expected))
+  }
+}
+
+#[derive(Deserialize)]
+struct RuneActivityQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -132,6 +241,13 @@ pub struct Server {
   pub(crate) redirect_http_to_https: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    requires = "accept_offers",
+    help = "Expire offers <OFFER_TTL> after they are received. Expired offers are removed when \
+    new blocks are indexed."
+  )]
+  pub(crate) offer_ttl: Option<humantime::Duration>,
   #[arg(
     long,
     help = "Proxy `/content/INSCRIPTION_ID` and other recursive endpoints to `<PROXY>` if the inscription is not present on current chain."
@@ -152,25 +268,77 @@ impl Server {
     index: Arc<Index>,
     handle: Handle<SocketAddr>,
     http_port_tx: Option<std::sync::mpsc::Sender<u16>>,
+    event_receiver: Option<mpsc::Receiver<(Event, Option<ScriptBuf>, Option<u64>)>>,
   ) -> SubcommandResult {
-    settings.runtime()?.block_on(async {
-      let index_clone = index.clone();
-      let integration_test = settings.integration_test();
+    let runtime = settings.runtime()?;
+    let integration_test = settings.integration_test();
 
-      if (cfg!(test) || integration_test) && !self.no_sync {
-        index.update().unwrap();
-      }
+    let event_broadcast = EventBroadcast {
+      sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+    };
+
+    if let Some(mut event_receiver) = event_receiver {
+      let event_broadcast = event_broadcast.clone();
+      runtime.spawn(async move {
+        while let Some((event, script_pubkey, number)) = event_receiver.recv().await {
+          // sending only fails if there are no subscribers
+          event_broadcast
+            .sender
+            .send(BroadcastEvent {
+              event,
+              number,
+              script_pubkey,
+            })
+            .ok();
+        }
+      });
+    }
+
+    for url in settings.webhook_urls() {
+      let webhook = Webhook::new(url.clone(), settings.webhook_secret().map(str::to_string))?;
+      let receiver = event_broadcast.sender.subscribe();
+      let subscription = index.subscribe_to_events();
+      runtime.spawn(async move {
+        let _subscription = subscription;
+        webhook.run(receiver).await;
+      });
+    }
+
+    // events are sent with `blocking_send`, so the initial update must not
+    // run inside the runtime
+    if (cfg!(test) || integration_test) && !self.no_sync {
+      index.update().unwrap();
+    }
+
+    runtime.block_on(async {
+      let index_clone = index.clone();
 
       let index_thread = thread::spawn(move || {
+        let mut pruned_at = None;
+
         loop {
           if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
             break;
           }
 
-          if !self.no_sync
-            && let Err(error) = index_clone.update()
-          {
-            log::warn!("Updating index: {error}");
+          if !self.no_sync {
+            if let Err(error) = index_clone.update() {
+              log::warn!("Updating index: {error}");
+            }
+
+            // expired offers are only pruned when new blocks are indexed
+            if self.accept_offers {
+              match index_clone.block_count() {
+                Ok(block_count) if pruned_at != Some(block_count) => {
+                  match index_clone.prune_offers(self.offer_ttl.map(Into::into)) {
+                    Ok(_) => pruned_at = Some(block_count),
+                    Err(error) => log::warn!("Pruning offers: {error}"),
+                  }
+                }
+                Ok(_) => {}
+                Err(error) => log::warn!("Pruning offers: {error}"),
+              }
+            }
           }
 
           thread::sleep(if integration_test {
@@ -194,6 +362,7 @@ impl Server {
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        offer_ttl: self.offer_ttl.map(Into::into),
         proxy: self.proxy.clone(),
       });
 
@@ -207,6 +376,14 @@ impl Server {
       let router = Router::new()
         .route("/", get(Self::home))
         .route("/address/{address}", get(Self::address))
+        .route(
+          "/address/{address}/runes/history",
+          get(Self::address_rune_history),
+        )
+        .route(
+          "/address/{address}/runes/history/{page}",
+          get(Self::address_rune_history_paginated),
+        )
         .route("/block/{query}", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blocks", get(Self::blocks))
@@ -220,6 +397,7 @@ impl Server {
         .route("/collections", get(Self::collections))
         .route("/collections/{page}", get(Self::collections_paginated))
         .route("/decode/{txid}", get(Self::decode))
+        .route("/events", get(Self::events))
         .route("/galleries", get(Self::galleries))
         .route("/galleries/{page}", get(Self::galleries_paginated))
         .route("/faq", get(Self::faq))
@@ -237,6 +415,10 @@ impl Server {
           "/inscription/{inscription_query}/{child}",
           get(Self::inscription_child),
         )
+        .route(
+          "/inscription/{inscription_query}/history",
+          get(Self::inscription_history),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route(
           "/inscriptions",
@@ -252,9 +434,21 @@ impl Server {
         )
         .route("/inscriptions/{page}", get(Self::inscriptions_paginated))
         .route("/install.sh", get(Self::install_script))
+        .route("/metaprotocol/{metaprotocol}", get(Self::metaprotocol))
+        .route(
+          "/metaprotocol/{metaprotocol}/{page}",
+          get(Self::metaprotocol_paginated),
+        )
+        .route("/metaprotocols", get(Self::metaprotocols))
         .route("/missing", post(Self::missing).layer(body_limit))
         .route("/offer", post(Self::offer))
-        .route("/offers", get(Self::offers))
+        .route(
+          "/offers",
+          get(Self::offers)
+            .post(Self::outputs_offers)
+            .layer(body_limit),
+        )
+        .route("/offers/{inscription_id}", get(Self::inscription_offers))
         .route("/ordinal/{sat}", get(Self::ordinal))
         .route("/output/{output}", get(Self::output))
         .route("/outputs", post(Self::outputs).layer(body_limit))
@@ -267,7 +461,14 @@ impl Server {
         .route("/preview/{inscription_id}", get(Self::preview))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/{rune}", get(Self::rune))
+        .route("/rune/{rune}/activity", get(Self::rune_activity))
+        .route("/rune/{rune}/holders", get(Self::rune_holders))
+        .route(
+          "/rune/{rune}/holders/{page}",
+          get(Self::rune_holders_paginated),
+        )
         .route("/runes", get(Self::runes))
+        .route("/runes/calendar", get(Self::runes_calendar))
         .route("/runes/{page}", get(Self::runes_paginated))
         .route("/sat/{sat}", get(Self::sat))
         .route("/satpoint/{satpoint}", get(Self::satpoint))
@@ -285,6 +486,15 @@ impl Server {
         .route("/blockhash/{height}", get(r::block_hash_from_height_string))
         .route("/blockheight", get(r::blockheight_string))
         .route("/blocktime", get(r::blocktime_string))
+        .route(
+          "/r/address/{address}/inscriptions",
+          get(r::address_inscriptions),
+        )
+        .route(
+          "/r/address/{address}/inscriptions/{page}",
+          get(r::address_inscriptions_paginated),
+        )
+        .route("/r/batch", post(r::batch).layer(body_limit))
         .route("/r/blockhash", get(r::blockhash))
         .route("/r/blockhash/{height}", get(r::blockhash_at_height))
         .route("/r/blockheight", get(r::blockheight_string))
@@ -311,6 +521,8 @@ impl Server {
           "/r/parents/{inscription_id}/inscriptions/{page}",
           get(r::parent_inscriptions_paginated),
         )
+        .route("/r/rune/{rune}", get(r::rune))
+        .route("/r/rune/{rune}/mintable", get(r::rune_mintable))
         .route("/r/sat/{sat_number}", get(r::sat))
         .route("/r/sat/{sat_number}/{page}", get(r::sat_paginated))
         .route("/r/tx/{txid}", get(r::tx))
@@ -328,6 +540,14 @@ impl Server {
           get(r::children_paginated),
         )
         .route("/r/inscription/{inscription_id}", get(r::inscription))
+        .route(
+          "/r/inscription/{inscription_id}/siblings",
+          get(r::inscription_siblings),
+        )
+        .route(
+          "/r/inscription/{inscription_id}/siblings/{page}",
+          get(r::inscription_siblings_paginated),
+        )
         .route("/r/metadata/{inscription_id}", get(r::metadata))
         .route("/r/sat/{sat_number}/at/{index}", get(r::sat_at_index))
         .route(
@@ -341,6 +561,7 @@ impl Server {
       let router = router
         .fallback(Self::fallback)
         .layer(Extension(index))
+        .layer(Extension(event_broadcast))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -980,15 +1201,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::resolve_rune(&index, rune_query)?;
 
       let Some((id, entry, parent)) = index.rune(rune)? else {
         return Ok(if accept_json {
@@ -1012,9 +1225,16 @@ impl Server {
 
       let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
 
+      let holders = if index.has_address_index() {
+        Some(usize::try_from(index.get_rune_holder_count(id)?).unwrap())
+      } else {
+        None
+      };
+
       Ok(if accept_json {
         Json(api::Rune {
           entry,
+          holders,
           id,
           mintable,
           parent,
@@ -1023,6 +1243,7 @@ impl Server {
       } else {
         RuneHtml {
           entry,
+          holders,
           id,
           mintable,
           parent,
@@ -1033,6 +1254,179 @@ impl Server {
     })
   }
 
+  async fn rune_activity(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(query): Query<RuneActivityQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      if !index.has_rune_activity_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune activity index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let height = index.block_height()?.unwrap_or(Height(0)).n();
+
+      // defaults cover at most `MAX_RUNE_ACTIVITY_BLOCKS` blocks, starting
+      // from `from` if given, and otherwise ending at `to` or the tip
+      let (from, to) = match (query.from, query.to) {
+        (Some(from), Some(to)) => (from, to),
+        (Some(from), None) => (
+          from,
+          height.min(from.saturating_add(MAX_RUNE_ACTIVITY_BLOCKS - 1)),
+        ),
+        (None, to) => {
+          let to = to.unwrap_or(height);
+          (
+            u32::try_from(entry.block)
+              .unwrap()
+              .max(to.saturating_sub(MAX_RUNE_ACTIVITY_BLOCKS - 1)),
+            to,
+          )
+        }
+      };
+
+      if from > to {
+        return Err(ServerError::BadRequest(format!(
+          "`from` height {from} is greater than `to` height {to}"
+        )));
+      }
+
+      if to - from >= MAX_RUNE_ACTIVITY_BLOCKS {
+        return Err(ServerError::BadRequest(format!(
+          "range from {from} to {to} exceeds maximum of {MAX_RUNE_ACTIVITY_BLOCKS} blocks"
+        )));
+      }
+
+      let blocks = index
+        .get_rune_activity(id, from, to)?
+        .into_iter()
+        .map(|(height, activity)| api::RuneBlockActivity {
+          burned: activity.burned,
+          height,
+          mints: activity.mints,
+          receivers: activity.receivers,
+          transfers: activity.transfers,
+        })
+        .collect();
+
+      Ok(
+        Json(api::RuneActivity {
+          blocks,
+          from,
+          id,
+          spaced_rune: entry.spaced_rune,
+          to,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  fn resolve_rune(index: &Index, rune_query: query::Rune) -> ServerResult<Rune> {
+    Ok(match rune_query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
+  }
+
+  async fn rune_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::rune_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((rune_query, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn rune_holders_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), page_index)): Path<(
+      DeserializeFromStr<query::Rune>,
+      usize,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      if !index.has_address_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let (holders, more) = index.get_rune_holders_paginated(id, PAGE_SIZE, page_index)?;
+
+      let holders = holders
+        .into_iter()
+        .map(|(script_pubkey, amount, outputs)| api::RuneHolder {
+          address: server_config
+            .chain
+            .address_from_script(&script_pubkey)
+            .ok()
+            .map(|address| uncheck(&address)),
+          balance: entry.pile(amount),
+          outputs,
+          script_pubkey,
+        })
+        .collect::<Vec<api::RuneHolder>>();
+
+      Ok(
+        Json(api::RuneHolders {
+          holders,
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1081,6 +1475,66 @@ impl Server {
     })
   }
 
+  async fn runes_calendar(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let network = server_config.chain.network();
+
+      let height = index.block_height()?;
+
+      let (etched, unrestricted, reserved) = index.get_etched_rune_counts()?;
+
+      let mut intervals = Vec::new();
+
+      // names unlock one length at a time, longest first, with the longest
+      // restricted names unlocking during the first interval
+      for length in (1..=Rune::UNLOCKED).rev() {
+        let first = "Z".repeat(length).parse::<Rune>().unwrap();
+        let minimum = "A".repeat(length).parse::<Rune>().unwrap();
+
+        let start = first.unlock_height(network).unwrap();
+        let end = minimum.unlock_height(network).unwrap();
+
+        intervals.push(api::RuneUnlockInterval {
+          end: end.n(),
+          end_time: index.block_time(end)?.timestamp(),
+          etched: etched.get(&length).copied().unwrap_or_default(),
+          length,
+          minimum,
+          names: 26u128.pow(length.try_into().unwrap()),
+          start: start.n(),
+          start_time: index.block_time(start)?.timestamp(),
+        });
+      }
+
+      let calendar = RunesCalendarHtml {
+        height: height.map(|height| height.n()),
+        intervals,
+        minimum: Rune::minimum_at_height(
+          network,
+          Height(height.map(|height| height.n() + 1).unwrap_or_default()),
+        ),
+        reserved,
+        unrestricted,
+      };
+
+      Ok(if accept_json {
+        Json(calendar).into_response()
+      } else {
+        calendar.page(server_config).into_response()
+      })
+    })
+  }
+
   async fn home(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1142,12 +1596,85 @@ impl Server {
       let offer = Psbt::deserialize(&offer)
         .map_err(|err| ServerError::BadRequest(format!("invalid offer PSBT: {err}")))?;
 
-      index.insert_offer(offer).map_err(ServerError::Internal)?;
+      let (inscription_id, amount) = Self::validate_offer(&index, &offer)?;
 
-      Ok("".into_response())
+      match index
+        .insert_offer(offer, inscription_id, amount)
+        .map_err(ServerError::Internal)?
+      {
+        OfferInsertion::Duplicate | OfferInsertion::Inserted => Ok("".into_response()),
+        OfferInsertion::TooMany => Err(ServerError::BadRequest(
+          "server is not accepting more offers".into(),
+        )),
+        OfferInsertion::TooManyForInscription => Err(ServerError::BadRequest(format!(
+          "server is not accepting more offers for inscription {inscription_id}"
+        ))),
+      }
     })
   }
 
+  /// Checks that `offer` spends exactly one unsigned seller input containing a
+  /// single inscription, that all other inputs are signed, and that no inputs
+  /// have been spent. Returns the inscription and the amount paid to the
+  /// seller, excluding the value of the seller input.
+  fn validate_offer(index: &Index, offer: &Psbt) -> ServerResult<(InscriptionId, u64)> {
+    if !index.has_inscription_index() {
+      return Err(ServerError::NotFound(
+        "this server has no inscription index".into(),
+      ));
+    }
+
+    let mut unsigned = Vec::new();
+
+    for (input, psbt_input) in offer.unsigned_tx.input.iter().zip(&offer.inputs) {
+      let outpoint = input.previous_output;
+
+      if index.is_output_spent(outpoint)? {
+        return Err(ServerError::BadRequest(format!(
+          "offer input {outpoint} is spent"
+        )));
+      }
+
+      if psbt_input.final_script_sig.is_none() && psbt_input.final_script_witness.is_none() {
+        unsigned.push(outpoint);
+      }
+    }
+
+    let [seller] = unsigned.as_slice() else {
+      return Err(ServerError::BadRequest(format!(
+        "offer must have exactly one unsigned seller input, found {}",
+        unsigned.len()
+      )));
+    };
+
+    let inscriptions = index
+      .get_inscriptions_for_output(*seller)?
+      .unwrap_or_default();
+
+    let [inscription_id] = inscriptions.as_slice() else {
+      return Err(ServerError::BadRequest(format!(
+        "offer seller input {seller} must contain exactly one inscription, found {}",
+        inscriptions.len()
+      )));
+    };
+
+    let seller_output = index
+      .get_transaction(seller.txid)?
+      .and_then(|tx| tx.output.into_iter().nth(seller.vout.into_usize()))
+      .ok_or_not_found(|| format!("output {seller}"))?;
+
+    let amount = offer
+      .unsigned_tx
+      .output
+      .iter()
+      .filter(|output| output.script_pubkey == seller_output.script_pubkey)
+      .map(|output| output.value.to_sat())
+      .sum::<u64>()
+      .saturating_sub(seller_output.value.to_sat());
+
+    Ok((*inscription_id, amount))
+  }
+
   async fn offers(
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
@@ -1170,22 +1697,68 @@ impl Server {
     })
   }
 
-  async fn address(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
+  async fn outputs_offers(
     Extension(index): Extension<Arc<Index>>,
-    Path(address): Path<Address<NetworkUnchecked>>,
     AcceptJson(accept_json): AcceptJson,
+    Json(outputs): Json<Vec<OutPoint>>,
   ) -> ServerResult {
-    task::block_in_place(|| {
-      let address = address
-        .require_network(server_config.chain.network())
-        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+    if !accept_json {
+      return Ok(StatusCode::NOT_FOUND.into_response());
+    }
 
-      let Some(info) = Self::address_info(&index, &address)? else {
-        return Err(ServerError::NotFound(
-          "this server has no address index".to_string(),
-        ));
-      };
+    task::block_in_place(|| {
+      Ok(
+        Json(api::Offers {
+          offers: index
+            .get_offers_for_outputs(&outputs)?
+            .into_iter()
+            .map(|offer| base64_encode(&offer))
+            .collect(),
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn inscription_offers(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    if !accept_json {
+      return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+
+    task::block_in_place(|| {
+      Ok(
+        Json(api::Offers {
+          offers: index
+            .get_offers_for_inscription(inscription_id)?
+            .into_iter()
+            .map(|(_amount, offer)| base64_encode(&offer))
+            .collect(),
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn address(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let Some(info) = Self::address_info(&index, &address)? else {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      };
 
       Ok(if accept_json {
         Json(info).into_response()
@@ -1211,6 +1784,56 @@ impl Server {
     })
   }
 
+  async fn address_rune_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::address_rune_history_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((address, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn address_rune_history_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((address, page_index)): Path<(Address<NetworkUnchecked>, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_rune_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune history index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let (history, more) =
+        index.get_rune_history_for_address_paginated(&address, PAGE_SIZE, page_index)?;
+
+      Ok(
+        Json(api::AddressRuneHistory {
+          history,
+          more,
+          page_index,
+        })
+        .into_response(),
+      )
+    })
+  }
+
   fn address_info(index: &Index, address: &Address) -> ServerResult<Option<api::AddressInfo>> {
     if !index.has_address_index() {
       return Ok(None);
@@ -1351,13 +1974,210 @@ impl Server {
     })
   }
 
+  async fn events(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Extension(event_broadcast): Extension<EventBroadcast>,
+    Query(query): Query<EventsQuery>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    if !server_config.json_api_enabled {
+      return Err(ServerError::NotFound("JSON API disabled".into()));
+    }
+
+    let filter = EventFilter::new(&query, server_config.chain.network())?;
+
+    if accept_json {
+      return task::block_in_place(|| {
+        if !index.has_event_index() {
+          return Err(ServerError::NotFound(
+            "this server has no event index".into(),
+          ));
+        }
+
+        let (stored, mut more) = index.get_events(
+          query.after,
+          if filter.is_empty() {
+            PAGE_SIZE
+          } else {
+            MAX_EVENTS_EXAMINED
+          },
+        )?;
+
+        let mut cursor = None;
+        let mut events = Vec::new();
+
+        for (number, event) in stored {
+          if events.len() == PAGE_SIZE {
+            more = true;
+            break;
+          }
+
+          cursor = Some(number);
+
+          if filter.matches(&event, || Self::event_script_pubkey(&index, &event)) {
+            events.push(api::EventEntry {
+              cursor: number,
+              event,
+            });
+          }
+        }
+
+        Ok(
+          Json(api::Events {
+            cursor,
+            events,
+            more,
+          })
+          .into_response(),
+        )
+      });
+    }
+
+    // subscribe before looking up the last stored event, so that events after
+    // it are received from the broadcast
+    let receiver = event_broadcast.sender.subscribe();
+
+    let subscription = index.subscribe_to_events();
+
+    let replay = match query.after {
+      Some(after) => task::block_in_place(|| {
+        if !index.has_event_index() {
+          return Err(ServerError::NotFound(
+            "this server has no event index".into(),
+          ));
+        }
+
+        Ok(index.last_event_number()?.map(|last| (after, last)))
+      })?,
+      None => None,
+    };
+
+    let replayed = replay.map(|(_, last)| last);
+
+    let stored = Self::stored_events(index, filter.clone(), replay);
+
+    let live = BroadcastStream::new(receiver).filter_map(move |result| {
+      // dropped along with the stream when the client disconnects
+      let _subscription = &subscription;
+
+      let event = match result {
+        Ok(event) => event,
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+          return Some(Ok::<_, Error>(
+            sse::Event::default()
+              .event("lagged")
+              .data(skipped.to_string()),
+          ));
+        }
+      };
+
+      // events up to the last stored event were replayed
+      if event
+        .number
+        .zip(replayed)
+        .is_some_and(|(number, replayed)| number <= replayed)
+      {
+        return None;
+      }
+
+      filter
+        .matches(&event.event, || event.script_pubkey.clone())
+        .then(|| Self::sse_event(&event.event, event.number))
+    });
+
+    Ok(
+      Sse::new(tokio_stream::iter(stored).chain(live))
+        .keep_alive(KeepAlive::default())
+        .into_response(),
+    )
+  }
+
+  /// Returns stored events which pass `filter`, with numbers in `range`,
+  /// excluding its start, a page at a time.
+  fn stored_events(
+    index: Arc<Index>,
+    filter: EventFilter,
+    mut range: Option<(u64, u64)>,
+  ) -> impl Iterator<Item = Result<sse::Event>> {
+    let mut page = Vec::new().into_iter();
+
+    std::iter::from_fn(move || {
+      loop {
+        let (after, last) = range.as_mut()?;
+
+        if let Some((number, event)) = page.next() {
+          if number > *last {
+            return None;
+          }
+
+          if task::block_in_place(|| {
+            filter.matches(&event, || Self::event_script_pubkey(&index, &event))
+          }) {
+            return Some(Self::sse_event(&event, Some(number)));
+          }
+
+          continue;
+        }
+
+        if *after >= *last {
+          return None;
+        }
+
+        match task::block_in_place(|| index.get_events(Some(*after), PAGE_SIZE)) {
+          Ok((events, _)) => {
+            *after = events.last().map(|(number, _)| *number)?;
+            page = events.into_iter();
+          }
+          Err(err) => {
+            range = None;
+            return Some(Err(err));
+          }
+        }
+      }
+    })
+  }
+
+  fn sse_event(event: &Event, number: Option<u64>) -> Result<sse::Event> {
+    let sse_event = sse::Event::default().json_data(event)?;
+
+    Ok(match number {
+      Some(number) => sse_event.id(number.to_string()),
+      None => sse_event,
+    })
+  }
+
+  fn event_script_pubkey(index: &Index, event: &Event) -> Option<ScriptBuf> {
+    let outpoint = event.outpoint()?;
+
+    match index.get_transaction(outpoint.txid) {
+      Ok(transaction) => transaction
+        .and_then(|transaction| {
+          transaction
+            .output
+            .into_iter()
+            .nth(outpoint.vout.into_usize())
+        })
+        .map(|output| output.script_pubkey),
+      Err(err) => {
+        log::warn!("failed to get output {outpoint} for event: {err}");
+        None
+      }
+    }
+  }
+
   async fn update(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(settings): Extension<Arc<Settings>>,
     Extension(index): Extension<Arc<Index>>,
   ) -> ServerResult {
     task::block_in_place(|| {
       if settings.integration_test() {
         index.update()?;
+
+        if server_config.accept_offers {
+          index.prune_offers(server_config.offer_ttl)?;
+        }
         Ok(index.block_count()?.to_string().into_response())
       } else {
         Ok(StatusCode::NOT_FOUND.into_response())
@@ -1383,10 +2203,57 @@ impl Server {
   }
 
   async fn search_by_query(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Query(search): Query<Search>,
-  ) -> ServerResult<Redirect> {
-    Self::search(index, search.query).await
+    AcceptJson(accept_json): AcceptJson,
+    Query(search): Query<SearchQuery>,
+  ) -> ServerResult {
+    match search {
+      SearchQuery {
+        q: Some(q), before, ..
+      } => Self::search_inscriptions(server_config, index, accept_json, q, before).await,
+      SearchQuery {
+        query: Some(query), ..
+      } => Ok(Self::search(index, query).await?.into_response()),
+      _ => Err(ServerError::BadRequest(
+        "missing `q` or `query` parameter".into(),
+      )),
+    }
+  }
+
+  async fn search_inscriptions(
+    server_config: Arc<ServerConfig>,
+    index: Arc<Index>,
+    accept_json: bool,
+    query: String,
+    before: Option<u32>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_search_index() {
+        return Err(ServerError::NotFound(
+          "this server has no search index".to_string(),
+        ));
+      }
+
+      let (inscriptions, cursor) = index.search_inscriptions(&query, before, PAGE_SIZE)?;
+
+      Ok(if accept_json {
+        Json(api::SearchResults {
+          cursor,
+          inscriptions,
+          more: cursor.is_some(),
+        })
+        .into_response()
+      } else {
+        SearchHtml {
+          inscriptions,
+          next: cursor,
+          query,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
   }
 
   async fn search_by_path(
@@ -1769,6 +2636,15 @@ impl Server {
 
         let properties = inscription.properties();
 
+        let mut offers = index
+          .get_offers_for_inscription(info.id)?
+          .into_iter()
+          .map(|(amount, _offer)| amount)
+          .collect::<Vec<u64>>();
+
+        offers.sort();
+        offers.reverse();
+
         InscriptionHtml {
           chain: server_config.chain,
           charms: Charm::Vindicated.unset(info.charms.iter().fold(0, |mut acc, charm| {
@@ -1783,6 +2659,7 @@ impl Server {
           inscription,
           next: info.next,
           number: info.number,
+          offers,
           output: txout,
           parents: info.parents,
           previous: info.previous,
@@ -1798,6 +2675,36 @@ impl Server {
     })
   }
 
+  async fn inscription_history(
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_inscription_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no inscription history index".to_string(),
+        ));
+      }
+
+      if !accept_json {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+      }
+
+      if let query::Inscription::Sat(_) = query
+        && !index.has_sat_index()
+      {
+        return Err(ServerError::NotFound("sat index required".into()));
+      }
+
+      let (info, _txout, _inscription) = index
+        .inscription_info(query, None)?
+        .ok_or_not_found(|| format!("inscription {query}"))?;
+
+      Ok(Json(info.transfers.unwrap_or_default()).into_response())
+    })
+  }
+
   async fn inscriptions_json(
     Extension(index): Extension<Arc<Index>>,
     AcceptJson(accept_json): AcceptJson,
@@ -1868,45 +2775,53 @@ impl Server {
     })
   }
 
-  async fn galleries(
+  async fn metaprotocol(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Path(metaprotocol): Path<String>,
     accept_json: AcceptJson,
   ) -> ServerResult {
-    Self::galleries_paginated(
+    Self::metaprotocol_paginated(
       Extension(server_config),
       Extension(index),
-      Path(0),
+      Path((metaprotocol, 0)),
       accept_json,
     )
     .await
   }
 
-  async fn galleries_paginated(
+  async fn metaprotocol_paginated(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path(page_index): Path<u32>,
+    Path((metaprotocol, page_index)): Path<(String, u32)>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let (galleries, more) = index.get_galleries_paginated(PAGE_SIZE, page_index.into_usize())?;
-
-      let prev = page_index.checked_sub(1);
+      if !index.has_metaprotocol_index() {
+        return Err(ServerError::NotFound(
+          "this server has no metaprotocol index".to_string(),
+        ));
+      }
 
-      let next = more.then_some(page_index + 1);
+      let (inscriptions, more) = index.get_metaprotocol_inscriptions_paginated(
+        &metaprotocol,
+        PAGE_SIZE,
+        page_index.into_usize(),
+      )?;
 
       Ok(if accept_json {
         Json(api::Inscriptions {
-          ids: galleries,
+          ids: inscriptions,
           page_index,
           more,
         })
         .into_response()
       } else {
-        GalleriesHtml {
-          inscriptions: galleries,
-          prev,
-          next,
+        MetaprotocolHtml {
+          inscriptions,
+          metaprotocol,
+          next: more.then_some(page_index.into_usize() + 1),
+          prev: page_index.into_usize().checked_sub(1),
         }
         .page(server_config)
         .into_response()
@@ -1914,33 +2829,109 @@ impl Server {
     })
   }
 
-  async fn gallery(
-    Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
-    Path(inscription_id): Path<InscriptionId>,
-    AcceptJson(accept_json): AcceptJson,
-  ) -> ServerResult {
-    Self::gallery_paginated(
-      Extension(server_config),
-      Extension(index),
-      Path((inscription_id, 0)),
-      AcceptJson(accept_json),
-    )
-    .await
-  }
-
-  async fn gallery_paginated(
+  async fn metaprotocols(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
-    Path((id, page)): Path<(InscriptionId, usize)>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
-      let inscription = index
-        .get_inscription_by_id(id)?
-        .ok_or_not_found(|| format!("inscription {id}"))?;
-
-      let number = index
+      if !index.has_metaprotocol_index() {
+        return Err(ServerError::NotFound(
+          "this server has no metaprotocol index".to_string(),
+        ));
+      }
+
+      let metaprotocols = index.get_metaprotocols()?;
+
+      Ok(if accept_json {
+        Json(
+          metaprotocols
+            .into_iter()
+            .map(|(name, count)| api::Metaprotocol { count, name })
+            .collect::<Vec<api::Metaprotocol>>(),
+        )
+        .into_response()
+      } else {
+        MetaprotocolsHtml { metaprotocols }
+          .page(server_config)
+          .into_response()
+      })
+    })
+  }
+
+  async fn galleries(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::galleries_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn galleries_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page_index): Path<u32>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let (galleries, more) = index.get_galleries_paginated(PAGE_SIZE, page_index.into_usize())?;
+
+      let prev = page_index.checked_sub(1);
+
+      let next = more.then_some(page_index + 1);
+
+      Ok(if accept_json {
+        Json(api::Inscriptions {
+          ids: galleries,
+          page_index,
+          more,
+        })
+        .into_response()
+      } else {
+        GalleriesHtml {
+          inscriptions: galleries,
+          prev,
+          next,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn gallery(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    Self::gallery_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((inscription_id, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn gallery_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((id, page)): Path<(InscriptionId, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let inscription = index
+        .get_inscription_by_id(id)?
+        .ok_or_not_found(|| format!("inscription {id}"))?;
+
+      let number = index
         .get_inscription_entry(id)?
         .ok_or_not_found(|| format!("inscription {id}"))?
         .inscription_number;
@@ -2045,12 +3036,14 @@ impl Server {
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    filter: Query<InscriptionFilter>,
     accept_json: AcceptJson,
   ) -> ServerResult {
     Self::inscriptions_paginated(
       Extension(server_config),
       Extension(index),
       Path(0),
+      filter,
       accept_json,
     )
     .await
@@ -2060,9 +3053,45 @@ impl Server {
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
     Path(page_index): Path<u32>,
+    Query(filter): Query<InscriptionFilter>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult {
     task::block_in_place(|| {
+      if !filter.is_empty() {
+        if filter.rarity.is_some() && !index.has_sat_index() {
+          return Err(ServerError::NotFound("sat index required".into()));
+        }
+
+        if (filter.content_type.is_some() || filter.metaprotocol.is_some())
+          && !index.has_filter_index()
+        {
+          return Err(ServerError::NotFound("filter index required".into()));
+        }
+
+        if page_index != 0 {
+          return Err(ServerError::BadRequest(
+            "filtered inscriptions are paged with `before`".into(),
+          ));
+        }
+
+        if !accept_json {
+          return Err(ServerError::BadRequest(
+            "inscription filters are only supported by the JSON API".into(),
+          ));
+        }
+
+        let (ids, cursor) = index.get_inscriptions_filtered(&filter, PAGE_SIZE)?;
+
+        return Ok(
+          Json(api::FilteredInscriptions {
+            cursor,
+            ids,
+            more: cursor.is_some(),
+          })
+          .into_response(),
+        );
+      }
+
       let (inscriptions, more) = index.get_inscriptions_paginated(100, page_index)?;
 
       let prev = page_index.checked_sub(1);
@@ -2232,6 +3261,7 @@ impl Server {
 mod tests {
   use {
     super::*,
+    crate::index::event::EventSubscribers,
     reqwest::{
       StatusCode, Url,
       header::{self, HeaderMap},
@@ -2246,6 +3276,7 @@ mod tests {
   struct Builder {
     core: Option<mockcore::Handle>,
     config: String,
+    durable: bool,
     ord_args: BTreeMap<String, Option<String>>,
     server_args: BTreeMap<String, Option<String>>,
   }
@@ -2363,9 +3394,23 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = mpsc::channel(EVENT_CHANNEL_CAPACITY);
+
+      let mut index = Index::open_with_script_pubkey_event_sender(&settings, event_sender)
+        .unwrap()
+        .with_event_subscribers(EventSubscribers::default());
+
+      if self.durable {
+        index.set_durability(redb::Durability::Immediate);
+      }
+
+      let index = Arc::new(index);
       let ord_server_handle = Handle::new();
 
+      if server.no_sync {
+        index.update().unwrap();
+      }
+
       let (tx, rx) = std::sync::mpsc::channel();
 
       {
@@ -2373,7 +3418,13 @@ mod tests {
         let ord_server_handle = ord_server_handle.clone();
         thread::spawn(|| {
           server
-            .run(settings, index, ord_server_handle, Some(tx))
+            .run(
+              settings,
+              index,
+              ord_server_handle,
+              Some(tx),
+              Some(event_receiver),
+            )
             .unwrap()
         });
       }
@@ -2393,6 +3444,14 @@ mod tests {
       }
     }
 
+    /// Commit index writes durably, which is required to handle reorgs.
+    fn durable(self) -> Self {
+      Self {
+        durable: true,
+        ..self
+      }
+    }
+
     fn https(self) -> Self {
       self.server_flag("--https")
     }
@@ -3014,6 +4073,161 @@ mod tests {
     );
   }
 
+  #[test]
+  fn search_inscriptions() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-search")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain", "Hello, world!").to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let hello_world = InscriptionId { txid, index: 0 };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          content_type: Some("image/png".into()),
+          body: Some("hello".into()),
+          properties: Properties {
+            attributes: Attributes {
+              title: Some("Hello Cat".into()),
+              ..default()
+            },
+            ..default()
+          }
+          .to_inline_cbor(),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let hello_cat = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<api::SearchResults>("/search?q=hello"),
+      api::SearchResults {
+        cursor: None,
+        inscriptions: vec![hello_cat, hello_world],
+        more: false,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SearchResults>("/search?q=hello&before=1"),
+      api::SearchResults {
+        cursor: None,
+        inscriptions: vec![hello_world],
+        more: false,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SearchResults>("/search?q=HELLO%20world"),
+      api::SearchResults {
+        cursor: None,
+        inscriptions: vec![hello_world],
+        more: false,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::SearchResults>("/search?q=dog"),
+      api::SearchResults {
+        cursor: None,
+        inscriptions: Vec::new(),
+        more: false,
+      },
+    );
+
+    server.assert_response_regex(
+      "/search?q=cat",
+      StatusCode::OK,
+      format!(".*<title>Search: cat</title>.*<a href=/inscription/{hello_cat}>.*"),
+    );
+
+    server.assert_redirect("/search?query=abc", "/sat/abc");
+  }
+
+  #[test]
+  fn search_skips_hidden_inscriptions() {
+    let core = mockcore::builder()
+      .network(Chain::Regtest.network())
+      .build();
+
+    core.mine_blocks(1);
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let hidden = InscriptionId { txid, index: 0 };
+
+    let txid = core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    core.mine_blocks(1);
+
+    let visible = InscriptionId { txid, index: 0 };
+
+    let server = TestServer::builder()
+      .core(core)
+      .ord_flag("--index-search")
+      .config(&format!("hidden: [{hidden}]"))
+      .build();
+
+    pretty_assert_eq!(
+      server.get_json::<api::SearchResults>("/search?q=hello"),
+      api::SearchResults {
+        cursor: None,
+        inscriptions: vec![visible],
+        more: false,
+      },
+    );
+  }
+
+  #[test]
+  fn search_inscriptions_requires_search_index() {
+    TestServer::new().assert_response(
+      "/search?q=hello",
+      StatusCode::NOT_FOUND,
+      "this server has no search index",
+    );
+  }
+
+  #[test]
+  fn search_requires_query() {
+    TestServer::new().assert_response(
+      "/search",
+      StatusCode::BAD_REQUEST,
+      "missing `q` or `query` parameter",
+    );
+  }
+
   #[test]
   fn search_by_satpoint_returns_sat() {
     let server = TestServer::builder()
@@ -3189,33 +4403,313 @@ mod tests {
   }
 
   #[test]
-  fn runes_can_be_queried_by_rune_number() {
+  fn rune_activity() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
+      .ord_flag("--index-rune-activity")
       .build();
 
     server.mine_blocks(1);
 
-    server.assert_response_regex("/rune/0", StatusCode::NOT_FOUND, ".*");
+    let rune = Rune(RUNE);
 
-    for i in 0..10 {
-      let rune = Rune(RUNE + i);
-      server.etch(
-        Runestone {
-          edicts: vec![Edict {
-            id: RuneId::default(),
-            amount: u128::MAX,
-            output: 0,
-          }],
-          etching: Some(Etching {
-            rune: Some(rune),
+    let (_txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(1000),
+          terms: Some(Terms {
+            amount: Some(100),
+            cap: Some(10),
             ..default()
           }),
           ..default()
-        },
-        1,
-        None,
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let etched = u32::try_from(id.block).unwrap();
+
+    server.mine_blocks(1);
+
+    for block in [id.block, id.block + 1] {
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(block.try_into().unwrap(), 0, 0, Witness::new())],
+        op_return: Some(
+          Runestone {
+            mint: Some(id),
+            ..default()
+          }
+          .encipher(),
+        ),
+        ..default()
+      });
+    }
+
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id,
+            amount: 100,
+            output: 1,
+          }],
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let spaced_rune = SpacedRune { rune, spacers: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneActivity>(format!("/rune/{rune}/activity")),
+      api::RuneActivity {
+        blocks: vec![
+          api::RuneBlockActivity {
+            burned: 0,
+            height: etched,
+            mints: 0,
+            receivers: 1,
+            transfers: 0,
+          },
+          api::RuneBlockActivity {
+            burned: 0,
+            height: etched + 2,
+            mints: 2,
+            receivers: 1,
+            transfers: 0,
+          },
+          api::RuneBlockActivity {
+            burned: 100,
+            height: etched + 3,
+            mints: 0,
+            receivers: 1,
+            transfers: 1,
+          },
+        ],
+        from: etched,
+        id,
+        spaced_rune,
+        to: etched + 3,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneActivity>(format!(
+        "/rune/{id}/activity?from={}&to={}",
+        etched + 1,
+        etched + 2
+      )),
+      api::RuneActivity {
+        blocks: vec![api::RuneBlockActivity {
+          burned: 0,
+          height: etched + 2,
+          mints: 2,
+          receivers: 1,
+          transfers: 0,
+        }],
+        from: etched + 1,
+        id,
+        spaced_rune,
+        to: etched + 2,
+      }
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/rune/{rune}/activity?from=10&to=9")))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "`from` height 10 is greater than `to` height 9",
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url(&format!("/rune/{rune}/activity?from=0&to=10000")))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "range from 0 to 10000 exceeds maximum of 10000 blocks",
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::RuneActivity>(format!("/rune/{id}/activity?from=0&to=9999"))
+        .to,
+      9999,
+    );
+  }
+
+  #[test]
+  fn rune_activity_requires_rune_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/rune/AAAAAAAAAAAAA/activity",
+        StatusCode::NOT_FOUND,
+        "this server has no rune index",
+      );
+  }
+
+  #[test]
+  fn rune_activity_requires_rune_activity_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build()
+      .assert_response(
+        "/rune/AAAAAAAAAAAAA/activity",
+        StatusCode::NOT_FOUND,
+        "this server has no rune activity index",
+      );
+  }
+
+  #[test]
+  fn runes_calendar() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    for rune in ["AAAAAAAAAAAAA", "ZZZZZZZZZZZZ"] {
+      server.etch(
+        Runestone {
+          etching: Some(Etching {
+            rune: Some(rune.parse().unwrap()),
+            ..default()
+          }),
+          ..default()
+        },
+        1,
+        None,
+      );
+    }
+
+    server.etch(
+      Runestone {
+        etching: Some(Etching::default()),
+        ..default()
+      },
+      1,
+      Some(Witness::default()),
+    );
+
+    let height = server.index.block_height().unwrap().unwrap();
+
+    let calendar = server.get_json::<api::RunesCalendar>("/runes/calendar");
+
+    assert_eq!(calendar.height, Some(height.n()));
+    assert_eq!(
+      calendar.minimum,
+      Rune::minimum_at_height(Network::Regtest, height + 1),
+    );
+    assert_eq!(calendar.reserved, 1);
+    assert_eq!(calendar.unrestricted, 1);
+    assert_eq!(calendar.intervals.len(), 12);
+
+    pretty_assert_eq!(
+      calendar.intervals[0],
+      api::RuneUnlockInterval {
+        end: 17499,
+        end_time: server.index.block_time(Height(17499)).unwrap().timestamp(),
+        etched: 1,
+        length: 12,
+        minimum: "AAAAAAAAAAAA".parse().unwrap(),
+        names: 95428956661682176,
+        start: 0,
+        start_time: server.index.block_time(Height(0)).unwrap().timestamp(),
+      },
+    );
+
+    pretty_assert_eq!(
+      calendar.intervals[11],
+      api::RuneUnlockInterval {
+        end: 209999,
+        end_time: server.index.block_time(Height(209999)).unwrap().timestamp(),
+        etched: 0,
+        length: 1,
+        minimum: Rune(0),
+        names: 26,
+        start: 193173,
+        start_time: server.index.block_time(Height(193173)).unwrap().timestamp(),
+      },
+    );
+
+    server.assert_response_regex(
+      "/runes/calendar",
+      StatusCode::OK,
+      ".*<title>Rune Calendar</title>.*
+  <tr>
+    <td>12</td>
+    <td>0 <time>.*</time></td>
+    <td>17499 <time>.*</time> \\(expected\\)</td>
+    <td>AAAAAAAAAAAA</td>
+    <td>1 of 95428956661682176</td>
+  </tr>.*",
+    );
+  }
+
+  #[test]
+  fn runes_calendar_requires_rune_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/runes/calendar",
+        StatusCode::NOT_FOUND,
+        "this server has no rune index",
+      );
+  }
+
+  #[test]
+  fn runes_can_be_queried_by_rune_number() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex("/rune/0", StatusCode::NOT_FOUND, ".*");
+
+    for i in 0..10 {
+      let rune = Rune(RUNE + i);
+      server.etch(
+        Runestone {
+          edicts: vec![Edict {
+            id: RuneId::default(),
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(rune),
+            ..default()
+          }),
+          ..default()
+        },
+        1,
+        None,
       );
 
       server.mine_blocks(1);
@@ -3366,106 +4860,437 @@ mod tests {
   }
 
   #[test]
-  fn runes_are_displayed_on_rune_page() {
+  fn address_rune_history() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
+      .ord_flag("--index-rune-history")
       .build();
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
-
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
-
     let (txid, id) = server.etch(
       Runestone {
         edicts: vec![Edict {
           id: RuneId::default(),
-          amount: u128::MAX,
+          amount: 1000,
           output: 0,
         }],
         etching: Some(Etching {
-          rune: Some(rune),
-          symbol: Some('%'),
-          premine: Some(u128::MAX),
-          turbo: true,
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
           ..default()
         }),
         ..default()
       },
       1,
-      Some(
-        Inscription {
-          content_type: Some("text/plain".into()),
-          body: Some("hello".into()),
-          rune: Some(rune.commitment()),
-          ..default()
-        }
-        .to_witness(),
-      ),
+      None,
     );
 
-    let entry = RuneEntry {
-      block: id.block,
-      etching: txid,
-      spaced_rune: SpacedRune { rune, spacers: 0 },
-      premine: u128::MAX,
-      symbol: Some('%'),
-      timestamp: id.block,
-      turbo: true,
+    let etched = OutPoint { txid, vout: 0 };
+
+    let address = server.core.address(etched);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
       ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let transferred = OutPoint {
+      txid: transfer,
+      vout: 0,
     };
 
-    assert_eq!(server.index.runes().unwrap(), [(id, entry)]);
+    assert_eq!(server.core.address(transferred), address);
 
-    assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
-    );
+    let runes = [(
+      SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      Pile {
+        amount: 1000,
+        divisibility: 0,
+        symbol: None,
+      },
+    )]
+    .into_iter()
+    .collect::<BTreeMap<SpacedRune, Pile>>();
 
-    let parent = InscriptionId { txid, index: 0 };
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRuneHistory>(format!("/address/{address}/runes/history")),
+      api::AddressRuneHistory {
+        history: vec![
+          api::RuneHistory {
+            height: id.block.try_into().unwrap(),
+            outpoint: etched,
+            runes: runes.clone(),
+            spent: Some(api::RuneSpend {
+              height: u32::try_from(id.block).unwrap() + 1,
+              txid: transfer,
+            }),
+          },
+          api::RuneHistory {
+            height: u32::try_from(id.block).unwrap() + 1,
+            outpoint: transferred,
+            runes,
+            spent: None,
+          },
+        ],
+        more: false,
+        page_index: 0,
+      },
+    );
 
-    server.assert_html(
-      format!("/rune/{rune}"),
-      RuneHtml {
-        id,
-        entry,
-        mintable: false,
-        parent: Some(parent),
+    pretty_assert_eq!(
+      server.get_json::<api::AddressRuneHistory>(format!("/address/{address}/runes/history/1")),
+      api::AddressRuneHistory {
+        history: Vec::new(),
+        more: false,
+        page_index: 1,
       },
     );
 
-    server.assert_response_regex(
-      format!("/inscription/{parent}"),
-      StatusCode::OK,
-      ".*
-<dl>
-  <dt>rune</dt>
-  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
-  .*
-</dl>
-.*",
+    assert_eq!(
+      server.index.get_rune_balances_for_output(etched).unwrap(),
+      Some(BTreeMap::new())
     );
   }
 
   #[test]
-  fn etched_runes_are_displayed_on_block_page() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_runes()
-      .build();
+  fn spent_output_runes_are_shown_with_rune_history() {
+    for index_rune_history in [false, true] {
+      let mut builder = TestServer::builder().chain(Chain::Regtest).index_runes();
 
-    server.mine_blocks(1);
+      if index_rune_history {
+        builder = builder.ord_flag("--index-rune-history");
+      }
 
-    let rune0 = Rune(RUNE);
+      let server = builder.build();
 
-    let (_txid, id) = server.etch(
-      Runestone {
-        edicts: vec![Edict {
-          id: RuneId::default(),
-          amount: u128::MAX,
-          output: 0,
+      server.mine_blocks(1);
+
+      let (txid, id) = server.etch(
+        Runestone {
+          edicts: vec![Edict {
+            id: RuneId::default(),
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            premine: Some(1000),
+            ..default()
+          }),
+          ..default()
+        },
+        1,
+        None,
+      );
+
+      let etched = OutPoint { txid, vout: 0 };
+
+      server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      let expected = if index_rune_history {
+        [(
+          SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 0,
+            symbol: None,
+          },
+        )]
+        .into_iter()
+        .collect()
+      } else {
+        BTreeMap::new()
+      };
+
+      let output = server.get_json::<api::Output>(format!("/output/{etched}"));
+
+      assert!(output.spent);
+      pretty_assert_eq!(output.runes, Some(expected.clone()));
+
+      let outputs = server.post_json::<Vec<api::Output>>("/outputs", &vec![etched.to_string()]);
+
+      assert_eq!(outputs.len(), 1);
+      assert!(outputs[0].spent);
+      pretty_assert_eq!(outputs[0].runes, Some(expected));
+    }
+  }
+
+  #[test]
+  fn address_rune_history_requires_rune_history_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build()
+      .assert_response(
+        "/address/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202/runes/history",
+        StatusCode::NOT_FOUND,
+        "this server has no rune history index",
+      );
+  }
+
+  #[test]
+  fn rune_holders() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![
+          Edict {
+            id: RuneId::default(),
+            amount: 700,
+            output: 0,
+          },
+          Edict {
+            id: RuneId::default(),
+            amount: 300,
+            output: 1,
+          },
+        ],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(1000),
+          ..default()
+        }),
+        ..default()
+      },
+      2,
+      None,
+    );
+
+    let script_pubkey = server.core.tx_by_id(txid).output[0].script_pubkey.clone();
+
+    let holder = |script_pubkey: ScriptBuf, amount, outputs| api::RuneHolder {
+      address: Some(uncheck(
+        &Address::from_script(&script_pubkey, Network::Regtest).unwrap(),
+      )),
+      balance: Pile {
+        amount,
+        divisibility: 0,
+        symbol: None,
+      },
+      outputs,
+      script_pubkey,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{id}/holders")),
+      api::RuneHolders {
+        holders: vec![holder(script_pubkey.clone(), 1000, 2)],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 1, Witness::new())],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfer_script_pubkey = server.core.tx_by_id(transfer).output[0]
+      .script_pubkey
+      .clone();
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{}/holders", Rune(RUNE))),
+      api::RuneHolders {
+        holders: vec![
+          holder(script_pubkey, 700, 1),
+          holder(transfer_script_pubkey, 300, 1),
+        ],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Rune>(format!("/rune/{id}")).holders,
+      Some(2),
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{id}"),
+      StatusCode::OK,
+      ".*<dt>holders</dt>\n  <dd>2</dd>.*",
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::RuneHolders>(format!("/rune/{id}/holders/1"))
+        .holders,
+      Vec::new(),
+    );
+
+    let consolidate = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (id.block.try_into().unwrap(), 1, 0, Witness::new()),
+        (usize::try_from(id.block).unwrap() + 1, 1, 0, Witness::new()),
+      ],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{id}/holders")),
+      api::RuneHolders {
+        holders: vec![holder(
+          server.core.tx_by_id(consolidate).output[0]
+            .script_pubkey
+            .clone(),
+          1000,
+          1,
+        )],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Rune>(format!("/rune/{id}")).holders,
+      Some(1),
+    );
+  }
+
+  #[test]
+  fn rune_holders_requires_address_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build()
+      .assert_response(
+        format!("/rune/{}/holders", Rune(RUNE)),
+        StatusCode::NOT_FOUND,
+        "this server has no address index",
+      );
+  }
+
+  #[test]
+  fn runes_are_displayed_on_rune_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          symbol: Some('%'),
+          premine: Some(u128::MAX),
+          turbo: true,
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      Some(
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("hello".into()),
+          rune: Some(rune.commitment()),
+          ..default()
+        }
+        .to_witness(),
+      ),
+    );
+
+    let entry = RuneEntry {
+      block: id.block,
+      etching: txid,
+      spaced_rune: SpacedRune { rune, spacers: 0 },
+      premine: u128::MAX,
+      symbol: Some('%'),
+      timestamp: id.block,
+      turbo: true,
+      ..default()
+    };
+
+    assert_eq!(server.index.runes().unwrap(), [(id, entry)]);
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    let parent = InscriptionId { txid, index: 0 };
+
+    server.assert_html(
+      format!("/rune/{rune}"),
+      RuneHtml {
+        entry,
+        holders: None,
+        id,
+        mintable: false,
+        parent: Some(parent),
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{parent}"),
+      StatusCode::OK,
+      ".*
+<dl>
+  <dt>rune</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+  .*
+</dl>
+.*",
+    );
+  }
+
+  #[test]
+  fn etched_runes_are_displayed_on_block_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune0 = Rune(RUNE);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
         }],
         etching: Some(Etching {
           rune: Some(rune0),
@@ -3788,7 +5613,612 @@ mod tests {
       .redirect_http_to_https()
       .https()
       .build()
-      .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
+      .assert_redirect("/", &format!("https://{}/", System::host_name().unwrap()));
+  }
+
+  #[test]
+  fn events() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::get(server.join_url("/events")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let mut events = io::BufRead::lines(BufReader::new(response)).filter_map(|line| {
+      line
+        .unwrap()
+        .strip_prefix("data: ")
+        .map(|data| serde_json::from_str::<Event>(data).unwrap())
+    });
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let block = server.mine_blocks(1).remove(0);
+
+    pretty_assert_eq!(
+      events.next().unwrap(),
+      Event::InscriptionCreated {
+        block_height: 2,
+        charms: 0,
+        inscription_id: InscriptionId { txid, index: 0 },
+        location: Some(SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        }),
+        parent_inscription_ids: Vec::new(),
+        sequence_number: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      events.next().unwrap(),
+      Event::BlockCommitted {
+        block_hash: block.block_hash(),
+        block_height: 2,
+      }
+    );
+  }
+
+  #[test]
+  fn events_are_filtered() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let response = reqwest::blocking::get(server.join_url(&format!(
+      "/events?kind=inscription_transferred&inscription={inscription_id}"
+    )))
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let lines = io::BufRead::lines(BufReader::new(response));
+
+    server.mine_blocks(1);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, inscription("text/plain", "world").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let event = lines
+      .filter_map(|line| {
+        line
+          .unwrap()
+          .strip_prefix("data: ")
+          .map(|data| serde_json::from_str::<Event>(data).unwrap())
+      })
+      .find(|event| !event.is_block_event())
+      .unwrap();
+
+    pretty_assert_eq!(
+      event,
+      Event::InscriptionTransferred {
+        block_height: 5,
+        inscription_id,
+        new_location: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer,
+            vout: 0
+          },
+          offset: 0,
+        },
+        old_location: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0,
+        },
+        sequence_number: 0,
+      }
+    );
+  }
+
+  #[test]
+  fn events_are_filtered_by_address() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let second = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let recipient = Address::from_script(&address(1).script_pubkey(), Network::Regtest).unwrap();
+
+    let response = reqwest::blocking::get(server.join_url(&format!(
+      "/events?kind=inscription_transferred&address={recipient}"
+    )))
+    .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let lines = io::BufRead::lines(BufReader::new(response));
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(4, 1, 0, Default::default())],
+      recipient: Some(recipient),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let event = lines
+      .filter_map(|line| {
+        line
+          .unwrap()
+          .strip_prefix("data: ")
+          .map(|data| serde_json::from_str::<Event>(data).unwrap())
+      })
+      .find(|event| !event.is_block_event())
+      .unwrap();
+
+    pretty_assert_eq!(
+      event,
+      Event::InscriptionTransferred {
+        block_height: 6,
+        inscription_id: InscriptionId {
+          txid: second,
+          index: 0
+        },
+        new_location: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer,
+            vout: 0
+          },
+          offset: 0,
+        },
+        old_location: SatPoint {
+          outpoint: OutPoint {
+            txid: second,
+            vout: 0
+          },
+          offset: 0,
+        },
+        sequence_number: 1,
+      }
+    );
+  }
+
+  #[test]
+  fn block_events_are_not_filtered_by_kind() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .durable()
+      .build();
+
+    server.mine_blocks(8);
+
+    let response = reqwest::blocking::get(server.join_url("/events?kind=rune_minted")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut events = io::BufRead::lines(BufReader::new(response)).filter_map(|line| {
+      line
+        .unwrap()
+        .strip_prefix("data: ")
+        .map(|data| serde_json::from_str::<Event>(data).unwrap())
+    });
+
+    let tip = server.core.invalidate_tip();
+
+    server.mine_blocks(2);
+
+    let reverted = Event::BlockReverted {
+      block_hash: tip,
+      block_height: 8,
+    };
+
+    pretty_assert_eq!(events.next().unwrap(), reverted);
+
+    assert!(
+      server
+        .get_json::<api::Events>("/events?kind=rune_minted")
+        .events
+        .into_iter()
+        .any(|entry| entry.event == reverted)
+    );
+  }
+
+  #[test]
+  fn events_stream_replays_stored_events_after_cursor() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = reqwest::blocking::get(server.join_url("/events?after=1")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut lines = io::BufRead::lines(BufReader::new(response));
+
+    let mut events = std::iter::from_fn(move || {
+      let mut id = None;
+      let mut event = None;
+
+      for line in lines.by_ref() {
+        let line = line.unwrap();
+
+        if let Some(data) = line.strip_prefix("data: ") {
+          event = Some(serde_json::from_str::<Event>(data).unwrap());
+        } else if let Some(number) = line.strip_prefix("id: ") {
+          id = Some(number.parse::<u64>().unwrap());
+        } else if line.is_empty() && event.is_some() {
+          break;
+        }
+      }
+
+      Some((id, event?))
+    });
+
+    pretty_assert_eq!(
+      events.next().unwrap(),
+      (
+        Some(2),
+        Event::InscriptionCreated {
+          block_height: 2,
+          charms: 0,
+          inscription_id: InscriptionId { txid, index: 0 },
+          location: Some(SatPoint {
+            outpoint: OutPoint { txid, vout: 0 },
+            offset: 0,
+          }),
+          parent_inscription_ids: Vec::new(),
+          sequence_number: 0,
+        },
+      ),
+    );
+
+    pretty_assert_eq!(
+      events.next().unwrap(),
+      (
+        Some(3),
+        Event::BlockCommitted {
+          block_hash: server.core.state().hashes[2],
+          block_height: 2,
+        },
+      ),
+    );
+
+    let block = server.mine_blocks(1).remove(0);
+
+    pretty_assert_eq!(
+      events.next().unwrap(),
+      (
+        Some(4),
+        Event::BlockCommitted {
+          block_hash: block.block_hash(),
+          block_height: 3,
+        },
+      ),
+    );
+  }
+
+  #[test]
+  fn events_stream_after_cursor_requires_event_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let response = reqwest::blocking::get(server.join_url("/events?after=1")).unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.text().unwrap(), "this server has no event index");
+  }
+
+  #[test]
+  fn events_json() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let events = server.get_json::<api::Events>("/events?after=1");
+
+    pretty_assert_eq!(
+      events,
+      api::Events {
+        cursor: Some(3),
+        events: vec![
+          api::EventEntry {
+            cursor: 2,
+            event: Event::InscriptionCreated {
+              block_height: 2,
+              charms: 0,
+              inscription_id: InscriptionId { txid, index: 0 },
+              location: Some(SatPoint {
+                outpoint: OutPoint { txid, vout: 0 },
+                offset: 0,
+              }),
+              parent_inscription_ids: Vec::new(),
+              sequence_number: 0,
+            },
+          },
+          api::EventEntry {
+            cursor: 3,
+            event: Event::BlockCommitted {
+              block_hash: server.core.state().hashes[2],
+              block_height: 2,
+            },
+          },
+        ],
+        more: false,
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Events>("/events?after=3"),
+      api::Events {
+        cursor: None,
+        events: Vec::new(),
+        more: false,
+      },
+    );
+  }
+
+  #[test]
+  fn events_json_are_filtered() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let block_committed = || api::EventEntry {
+      cursor: 3,
+      event: Event::BlockCommitted {
+        block_hash: server.core.state().hashes[2],
+        block_height: 2,
+      },
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Events>("/events?after=1&kind=inscription_created"),
+      api::Events {
+        cursor: Some(3),
+        events: vec![
+          api::EventEntry {
+            cursor: 2,
+            event: Event::InscriptionCreated {
+              block_height: 2,
+              charms: 0,
+              inscription_id: InscriptionId { txid, index: 0 },
+              location: Some(SatPoint {
+                outpoint: OutPoint { txid, vout: 0 },
+                offset: 0,
+              }),
+              parent_inscription_ids: Vec::new(),
+              sequence_number: 0,
+            },
+          },
+          block_committed(),
+        ],
+        more: false,
+      },
+    );
+
+    let recipient = Address::from_script(&address(1).script_pubkey(), Network::Regtest).unwrap();
+
+    assert_eq!(
+      server.get_json::<api::Events>(format!(
+        "/events?after=1&kind=inscription_created&address={recipient}"
+      )),
+      api::Events {
+        cursor: Some(3),
+        events: vec![block_committed()],
+        more: false,
+      },
+    );
+  }
+
+  #[test]
+  fn events_json_requires_event_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/events"))
+      .header(header::ACCEPT, "application/json")
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.text().unwrap(), "this server has no event index");
+  }
+
+  #[test]
+  fn webhooks() {
+    let requests = Arc::new(Mutex::new(Vec::<(HeaderMap, Vec<u8>)>::new()));
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    {
+      let requests = requests.clone();
+      thread::spawn(move || {
+        tokio::runtime::Runtime::new()
+          .unwrap()
+          .block_on(async move {
+            let router = Router::new().route(
+              "/",
+              post(
+                move |headers: HeaderMap, body: axum::body::Bytes| async move {
+                  // ignore requests from anything other than the webhook, which
+                  // may reach the port if it was recently used by another test
+                  if !headers.contains_key(webhook::SIGNATURE_HEADER) {
+                    return StatusCode::NOT_FOUND;
+                  }
+
+                  let mut requests = requests.lock().unwrap();
+                  requests.push((headers, body.to_vec()));
+
+                  // fail the first delivery to exercise retries
+                  if requests.len() == 1 {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                  } else {
+                    StatusCode::OK
+                  }
+                },
+              ),
+            );
+
+            axum::serve(tokio::net::TcpListener::from_std(listener).unwrap(), router)
+              .await
+              .unwrap();
+          });
+      });
+    }
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .config(&format!(
+        "webhook_secret: foo\nwebhook_urls:\n- http://127.0.0.1:{port}/\n"
+      ))
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    server.index.update().unwrap();
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+
+    let events = loop {
+      let events = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(headers, body)| {
+          assert_eq!(
+            headers[header::CONTENT_TYPE].to_str().unwrap(),
+            "application/json"
+          );
+          assert_eq!(
+            headers[webhook::SIGNATURE_HEADER].to_str().unwrap(),
+            Webhook::signature("foo", body),
+          );
+          serde_json::from_slice::<Event>(body).unwrap()
+        })
+        .collect::<Vec<Event>>();
+
+      if events.iter().any(|event| {
+        matches!(
+          event,
+          Event::BlockCommitted {
+            block_height: 2,
+            ..
+          }
+        )
+      }) {
+        break events;
+      }
+
+      assert!(
+        Instant::now() < deadline,
+        "timed out waiting for webhook deliveries: {events:?}"
+      );
+
+      thread::sleep(Duration::from_millis(50));
+    };
+
+    assert_eq!(events[0], events[1]);
+
+    assert!(events.contains(&Event::InscriptionCreated {
+      block_height: 2,
+      charms: 0,
+      inscription_id,
+      location: Some(SatPoint {
+        outpoint: OutPoint { txid, vout: 0 },
+        offset: 0,
+      }),
+      parent_inscription_ids: Vec::new(),
+      sequence_number: 0,
+    }));
+  }
+
+  #[test]
+  fn events_require_json_api() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--disable-json-api")
+      .build()
+      .assert_response("/events", StatusCode::NOT_FOUND, "JSON API disabled");
   }
 
   #[test]
@@ -3869,6 +6299,8 @@ mod tests {
   <dd>true</dd>
   <dt>rune index</dt>
   <dd>false</dd>
+  <dt>rune history index</dt>
+  <dd>false</dd>
   <dt>sat index</dt>
   <dd>false</dd>
   <dt>transaction index</dt>
@@ -5358,6 +7790,233 @@ mod tests {
     );
   }
 
+  #[test]
+  fn inscriptions_filtered() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .ord_flag("--index-filters")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let text = InscriptionId { txid, index: 0 };
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        Inscription {
+          content_type: Some("image/png".into()),
+          body: Some("hello".into()),
+          metaprotocol: Some("brc-20".into()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let image = InscriptionId { txid, index: 0 };
+
+    let ids = |query: &str| {
+      server
+        .get_json::<api::FilteredInscriptions>(format!("/inscriptions?{query}"))
+        .ids
+    };
+
+    assert_eq!(ids("content_type=text/plain"), [text]);
+    assert_eq!(ids("content_type=image/*"), [image]);
+    assert_eq!(ids("metaprotocol=brc-20"), [image]);
+    assert_eq!(ids("from_height=3"), [image]);
+    assert_eq!(ids("to_height=2"), [text]);
+    assert_eq!(ids("from_height=2&to_height=3"), [image, text]);
+    assert_eq!(ids("charm=uncommon"), [image, text]);
+    assert_eq!(ids("charm=cursed"), []);
+    assert_eq!(ids("rarity=uncommon&content_type=text/plain"), [text]);
+    assert_eq!(ids("rarity=common"), []);
+    assert_eq!(ids("content_type=image/png&metaprotocol=brc-20"), [image]);
+    assert_eq!(ids("content_type=text/plain&metaprotocol=brc-20"), []);
+    assert_eq!(ids("content_type=image/*&from_height=0&to_height=2"), []);
+    assert_eq!(ids("before=1"), [text]);
+    assert_eq!(ids("before=1&content_type=image/*"), []);
+
+    pretty_assert_eq!(
+      server.get_json::<api::FilteredInscriptions>("/inscriptions?charm=uncommon"),
+      api::FilteredInscriptions {
+        cursor: None,
+        ids: vec![image, text],
+        more: false,
+      },
+    );
+
+    server.assert_response(
+      "/inscriptions/1?from_height=0",
+      StatusCode::BAD_REQUEST,
+      "filtered inscriptions are paged with `before`",
+    );
+
+    server.assert_response(
+      "/inscriptions?charm=cursed",
+      StatusCode::BAD_REQUEST,
+      "inscription filters are only supported by the JSON API",
+    );
+  }
+
+  #[test]
+  fn metaprotocols() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-metaprotocols")
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut ids = Vec::new();
+
+    for (i, metaprotocol) in ["brc-20", "foo bar", "brc-20"].into_iter().enumerate() {
+      let txid = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          i + 1,
+          0,
+          0,
+          Inscription {
+            content_type: Some("text/plain".into()),
+            body: Some("hello".into()),
+            metaprotocol: Some(metaprotocol.into()),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+
+      ids.push(InscriptionId { txid, index: 0 });
+    }
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<api::Metaprotocol>>("/metaprotocols"),
+      [
+        api::Metaprotocol {
+          count: 2,
+          name: "brc-20".into(),
+        },
+        api::Metaprotocol {
+          count: 1,
+          name: "foo bar".into(),
+        },
+      ],
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/metaprotocol/brc-20"),
+      api::Inscriptions {
+        ids: vec![ids[2], ids[0]],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/metaprotocol/foo%20bar"),
+      api::Inscriptions {
+        ids: vec![ids[1]],
+        more: false,
+        page_index: 0,
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>("/metaprotocol/brc-20/1"),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+      },
+    );
+
+    server.assert_response_regex(
+      "/metaprotocols",
+      StatusCode::OK,
+      ".*<td><a href=/metaprotocol/brc-20>brc-20</a></td>\n    <td>2</td>.*",
+    );
+
+    server.assert_response_regex(
+      "/metaprotocol/foo%20bar",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Metaprotocol foo bar</h1>.*<a href=/inscription/{}>.*",
+        ids[1]
+      ),
+    );
+  }
+
+  #[test]
+  fn metaprotocols_require_metaprotocol_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-filters")
+      .build();
+
+    server.assert_response(
+      "/metaprotocols",
+      StatusCode::NOT_FOUND,
+      "this server has no metaprotocol index",
+    );
+
+    server.assert_response(
+      "/metaprotocol/brc-20",
+      StatusCode::NOT_FOUND,
+      "this server has no metaprotocol index",
+    );
+  }
+
+  #[test]
+  fn inscriptions_filtered_by_content_type_requires_filter_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/inscriptions?content_type=text/plain",
+      StatusCode::NOT_FOUND,
+      "filter index required",
+    );
+
+    server.assert_response(
+      "/inscriptions?metaprotocol=brc-20",
+      StatusCode::NOT_FOUND,
+      "filter index required",
+    );
+  }
+
+  #[test]
+  fn inscriptions_filtered_by_rarity_requires_sat_index() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/inscriptions?rarity=rare",
+        StatusCode::NOT_FOUND,
+        "sat index required",
+      );
+  }
+
   #[test]
   fn collections_page_prev_and_next() {
     let server = TestServer::builder()
@@ -6184,57 +8843,211 @@ next
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/collections",
-      StatusCode::OK,
-      format!(
-        ".*<h1>Collections</h1>\n<div class=thumbnails>\n  <a href=/inscription/{visible_parent}>.*</a>\n</div>.*"
-      ),
+    server.assert_response_regex(
+      "/collections",
+      StatusCode::OK,
+      format!(
+        ".*<h1>Collections</h1>\n<div class=thumbnails>\n  <a href=/inscription/{visible_parent}>.*</a>\n</div>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn responses_are_gzipped() {
+    let server = TestServer::new();
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+
+    let response = reqwest::blocking::Client::builder()
+      .default_headers(headers)
+      .build()
+      .unwrap()
+      .get(server.join_url("/"))
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_ENCODING).unwrap(),
+      "gzip"
+    );
+  }
+
+  #[test]
+  fn responses_are_brotlied() {
+    let server = TestServer::new();
+
+    let mut headers = HeaderMap::new();
+
+    headers.insert(header::ACCEPT_ENCODING, BROTLI.parse().unwrap());
+
+    let response = reqwest::blocking::Client::builder()
+      .default_headers(headers)
+      .brotli(false)
+      .build()
+      .unwrap()
+      .get(server.join_url("/"))
+      .send()
+      .unwrap();
+
+    assert_eq!(
+      response.headers().get(header::CONTENT_ENCODING).unwrap(),
+      BROTLI
+    );
+  }
+
+  #[test]
+  fn inscription_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-history")
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    assert_eq!(
+      server.get_json::<Vec<api::InscriptionTransfer>>(format!("/inscription/{id}/history")),
+      [],
+    );
+
+    let first = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let second = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let genesis = SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    };
+
+    let first = SatPoint {
+      outpoint: OutPoint {
+        txid: first,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let second = SatPoint {
+      outpoint: OutPoint {
+        txid: second,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let address = |satpoint: SatPoint| Some(server.core.address(satpoint.outpoint).to_string());
+
+    let transfers = vec![
+      api::InscriptionTransfer {
+        height: 3,
+        new_satpoint: first,
+        old_satpoint: genesis,
+        receiver: address(first),
+        sender: address(genesis),
+        txid: first.outpoint.txid,
+      },
+      api::InscriptionTransfer {
+        height: 4,
+        new_satpoint: second,
+        old_satpoint: first,
+        receiver: address(second),
+        sender: address(first),
+        txid: second.outpoint.txid,
+      },
+    ];
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<api::InscriptionTransfer>>(format!("/inscription/{id}/history")),
+      transfers,
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{id}"))
+        .transfers,
+      Some(transfers),
     );
   }
 
   #[test]
-  fn responses_are_gzipped() {
-    let server = TestServer::new();
+  fn inscription_history_records_txid_of_inscriptions_lost_to_fees() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-inscription-history")
+      .build();
 
-    let mut headers = HeaderMap::new();
+    server.mine_blocks(1);
 
-    headers.insert(header::ACCEPT_ENCODING, "gzip".parse().unwrap());
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
 
-    let response = reqwest::blocking::Client::builder()
-      .default_headers(headers)
-      .build()
-      .unwrap()
-      .get(server.join_url("/"))
-      .send()
-      .unwrap();
+    server.mine_blocks(1);
 
-    assert_eq!(
-      response.headers().get(header::CONTENT_ENCODING).unwrap(),
-      "gzip"
-    );
+    let id = InscriptionId { txid, index: 0 };
+
+    let lost = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..default()
+    });
+
+    server.mine_blocks_with_subsidy(1, 0);
+
+    let transfers =
+      server.get_json::<Vec<api::InscriptionTransfer>>(format!("/inscription/{id}/history"));
+
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].new_satpoint.outpoint, OutPoint::null());
+    assert_eq!(transfers[0].txid, lost);
   }
 
   #[test]
-  fn responses_are_brotlied() {
-    let server = TestServer::new();
+  fn inscription_history_requires_inscription_history_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
 
-    let mut headers = HeaderMap::new();
+    server.mine_blocks(1);
 
-    headers.insert(header::ACCEPT_ENCODING, BROTLI.parse().unwrap());
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
 
-    let response = reqwest::blocking::Client::builder()
-      .default_headers(headers)
-      .brotli(false)
-      .build()
-      .unwrap()
-      .get(server.join_url("/"))
-      .send()
-      .unwrap();
+    server.mine_blocks(1);
 
-    assert_eq!(
-      response.headers().get(header::CONTENT_ENCODING).unwrap(),
-      BROTLI
+    server.assert_response(
+      format!("/inscription/{}/history", InscriptionId::default()),
+      StatusCode::NOT_FOUND,
+      "this server has no inscription history index",
+    );
+
+    assert!(
+      server
+        .get_json::<serde_json::Value>(format!("/inscription/{}", InscriptionId { txid, index: 0 }))
+        .get("transfers")
+        .is_none()
     );
   }
 
@@ -7339,123 +10152,396 @@ next
   }
 
   #[test]
-  fn utxo_recursive_endpoint_all() {
-    let server = TestServer::builder()
-      .chain(Chain::Regtest)
-      .index_sats()
-      .index_runes()
-      .build();
-
-    let rune = Rune(RUNE);
+  fn utxo_recursive_endpoint_all() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .index_runes()
+      .build();
+
+    let rune = Rune(RUNE);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          divisibility: Some(1),
+          rune: Some(rune),
+          premine: Some(u128::MAX),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    pretty_assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          block: id.block,
+          divisibility: 1,
+          etching: txid,
+          spaced_rune: SpacedRune { rune, spacers: 0 },
+          premine: u128::MAX,
+          timestamp: id.block,
+          ..default()
+        }
+      )]
+    );
+
+    server.mine_blocks(1);
+
+    // merge rune with two inscriptions
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (6, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (7, 0, 0, inscription("text/plain", "bar").to_witness()),
+        (7, 1, 0, Witness::new()),
+      ],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+    let second_inscription_id = InscriptionId { txid, index: 1 };
+    let outpoint: OutPoint = OutPoint { txid, vout: 0 };
+
+    let utxo_recursive = server.get_json::<api::UtxoRecursive>(format!("/r/utxo/{outpoint}"));
+
+    pretty_assert_eq!(
+      utxo_recursive,
+      api::UtxoRecursive {
+        inscriptions: Some(vec![inscription_id, second_inscription_id]),
+        runes: Some(
+          [(
+            SpacedRune { rune, spacers: 0 },
+            Pile {
+              amount: u128::MAX,
+              divisibility: 1,
+              symbol: None
+            }
+          )]
+          .into_iter()
+          .collect()
+        ),
+        sat_ranges: Some(vec![
+          (6 * 50 * COIN_VALUE, 7 * 50 * COIN_VALUE),
+          (7 * 50 * COIN_VALUE, 8 * 50 * COIN_VALUE),
+          (50 * COIN_VALUE, 2 * 50 * COIN_VALUE)
+        ]),
+        value: 150 * COIN_VALUE,
+      }
+    );
+  }
+
+  #[test]
+  fn utxo_recursive_endpoint_only_inscriptions() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+    let outpoint: OutPoint = OutPoint { txid, vout: 0 };
+
+    let utxo_recursive = server.get_json::<api::UtxoRecursive>(format!("/r/utxo/{outpoint}"));
+
+    pretty_assert_eq!(
+      utxo_recursive,
+      api::UtxoRecursive {
+        inscriptions: Some(vec![inscription_id]),
+        runes: None,
+        sat_ranges: None,
+        value: 50 * COIN_VALUE,
+      }
+    );
+  }
+
+  #[test]
+  fn rune_recursive_endpoints() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response(
+      format!("/r/rune/{rune}"),
+      StatusCode::NOT_FOUND,
+      "rune AAAAAAAAAAAAA not found",
+    );
+
+    let (txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(rune),
+          premine: Some(5),
+          terms: Some(Terms {
+            amount: Some(1000),
+            cap: Some(1),
+            ..default()
+          }),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let expected = api::RuneRecursive {
+      block: id.block,
+      burned: 0,
+      divisibility: 0,
+      etching: txid,
+      id,
+      max_supply: 1005,
+      mints: 0,
+      number: 0,
+      parent: None,
+      premine: 5,
+      spaced_rune: SpacedRune { rune, spacers: 0 },
+      supply: 5,
+      symbol: None,
+      terms: Some(Terms {
+        amount: Some(1000),
+        cap: Some(1),
+        ..default()
+      }),
+      timestamp: id.block,
+      turbo: false,
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{rune}")),
+      expected,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{id}")),
+      expected,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneMintable>(format!("/r/rune/{rune}/mintable")),
+      api::RuneMintable {
+        amount: Some(1000),
+        height: u32::try_from(id.block).unwrap() + 1,
+        mintable: true,
+        reason: None,
+      },
+    );
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          mint: Some(id),
+          ..default()
+        }
+        .encipher(),
+      ),
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneRecursive>(format!("/r/rune/{rune}")),
+      api::RuneRecursive {
+        mints: 1,
+        supply: 1005,
+        ..expected
+      },
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneMintable>(format!("/r/rune/{rune}/mintable")),
+      api::RuneMintable {
+        amount: None,
+        height: u32::try_from(id.block).unwrap() + 2,
+        mintable: false,
+        reason: Some("limited to 1 mints".into()),
+      },
+    );
+  }
+
+  #[test]
+  fn rune_recursive_endpoints_require_rune_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
 
-    let (txid, id) = server.etch(
-      Runestone {
-        edicts: vec![Edict {
-          id: RuneId::default(),
-          amount: u128::MAX,
-          output: 0,
-        }],
-        etching: Some(Etching {
-          divisibility: Some(1),
-          rune: Some(rune),
-          premine: Some(u128::MAX),
-          ..default()
-        }),
-        ..default()
-      },
-      1,
-      None,
+    server.assert_response(
+      "/r/rune/AAAAAAAAAAAAA",
+      StatusCode::NOT_FOUND,
+      "this server has no rune index",
     );
 
-    pretty_assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          block: id.block,
-          divisibility: 1,
-          etching: txid,
-          spaced_rune: SpacedRune { rune, spacers: 0 },
-          premine: u128::MAX,
-          timestamp: id.block,
-          ..default()
-        }
-      )]
+    server.assert_response(
+      "/r/rune/AAAAAAAAAAAAA/mintable",
+      StatusCode::NOT_FOUND,
+      "this server has no rune index",
     );
+  }
 
-    server.mine_blocks(1);
+  #[test]
+  fn inscription_siblings_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
 
-    // merge rune with two inscriptions
     let txid = server.core.broadcast_tx(TransactionTemplate {
       inputs: &[
-        (6, 0, 0, inscription("text/plain", "foo").to_witness()),
-        (7, 0, 0, inscription("text/plain", "bar").to_witness()),
-        (7, 1, 0, Witness::new()),
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
       ],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-    let second_inscription_id = InscriptionId { txid, index: 1 };
-    let outpoint: OutPoint = OutPoint { txid, vout: 0 };
+    let first = InscriptionId { txid, index: 0 };
+    let second = InscriptionId { txid, index: 1 };
 
-    let utxo_recursive = server.get_json::<api::UtxoRecursive>(format!("/r/utxo/{outpoint}"));
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/inscription/{first}/siblings")),
+      api::Inscriptions {
+        ids: vec![second],
+        more: false,
+        page_index: 0,
+      }
+    );
 
     pretty_assert_eq!(
-      utxo_recursive,
-      api::UtxoRecursive {
-        inscriptions: Some(vec![inscription_id, second_inscription_id]),
-        runes: Some(
-          [(
-            SpacedRune { rune, spacers: 0 },
-            Pile {
-              amount: u128::MAX,
-              divisibility: 1,
-              symbol: None
-            }
-          )]
-          .into_iter()
-          .collect()
-        ),
-        sat_ranges: Some(vec![
-          (6 * 50 * COIN_VALUE, 7 * 50 * COIN_VALUE),
-          (7 * 50 * COIN_VALUE, 8 * 50 * COIN_VALUE),
-          (50 * COIN_VALUE, 2 * 50 * COIN_VALUE)
-        ]),
-        value: 150 * COIN_VALUE,
+      server.get_json::<api::Inscriptions>(format!("/r/inscription/{second}/siblings")),
+      api::Inscriptions {
+        ids: vec![first],
+        more: false,
+        page_index: 0,
       }
     );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/inscription/{first}/siblings/1")),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
+      }
+    );
+
+    server.assert_response(
+      format!(
+        "/r/inscription/{}/siblings",
+        InscriptionId { txid, index: 2 }
+      ),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {txid}i2 not found"),
+    );
   }
 
   #[test]
-  fn utxo_recursive_endpoint_only_inscriptions() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn address_inscriptions_recursive_endpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .build();
 
-    server.mine_blocks(1);
+    server.mine_blocks(3);
 
-    let txid = server.core.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+    let first = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (1, 0, 0, inscription("text/plain", "foo").to_witness()),
+        (2, 0, 0, inscription("text/plain", "bar").to_witness()),
+      ],
+      ..default()
+    });
+
+    let second = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 0, 0, inscription("text/plain", "baz").to_witness())],
       ..default()
     });
 
     server.mine_blocks(1);
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-    let outpoint: OutPoint = OutPoint { txid, vout: 0 };
+    let address = Address::from_script(
+      &server.core.tx_by_id(first).output[0].script_pubkey,
+      Network::Regtest,
+    )
+    .unwrap();
 
-    let utxo_recursive = server.get_json::<api::UtxoRecursive>(format!("/r/utxo/{outpoint}"));
+    let mut expected = vec![
+      InscriptionId {
+        txid: first,
+        index: 0,
+      },
+      InscriptionId {
+        txid: first,
+        index: 1,
+      },
+      InscriptionId {
+        txid: second,
+        index: 0,
+      },
+    ];
+
+    expected.sort();
+
+    let mut inscriptions =
+      server.get_json::<api::Inscriptions>(format!("/r/address/{address}/inscriptions"));
+
+    inscriptions.ids.sort();
 
     pretty_assert_eq!(
-      utxo_recursive,
-      api::UtxoRecursive {
-        inscriptions: Some(vec![inscription_id]),
-        runes: None,
-        sat_ranges: None,
-        value: 50 * COIN_VALUE,
+      inscriptions,
+      api::Inscriptions {
+        ids: expected,
+        more: false,
+        page_index: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::Inscriptions>(format!("/r/address/{address}/inscriptions/1")),
+      api::Inscriptions {
+        ids: Vec::new(),
+        more: false,
+        page_index: 1,
       }
     );
+
+    server.assert_response(
+      "/r/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4/inscriptions",
+      StatusCode::BAD_REQUEST,
+      "validation error",
+    );
+  }
+
+  #[test]
+  fn address_inscriptions_recursive_endpoint_requires_address_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.assert_response(
+      "/r/address/bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080/inscriptions",
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
   }
 
   #[test]
@@ -8474,27 +11560,169 @@ next
       }
     );
 
-    assert_eq!(
-      server_with_proxy.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
-      api::InscriptionRecursive {
-        charms: Vec::new(),
-        content_type: Some("text/html".into()),
-        content_length: Some(3),
-        delegate: None,
-        fee: 0,
-        height: 2,
-        id,
-        number: 0,
-        output: OutPoint { txid, vout: 0 },
-        sat: None,
-        satpoint: SatPoint {
-          outpoint: OutPoint { txid, vout: 0 },
-          offset: 0
+    assert_eq!(
+      server_with_proxy.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}")),
+      api::InscriptionRecursive {
+        charms: Vec::new(),
+        content_type: Some("text/html".into()),
+        content_length: Some(3),
+        delegate: None,
+        fee: 0,
+        height: 2,
+        id,
+        number: 0,
+        output: OutPoint { txid, vout: 0 },
+        sat: None,
+        satpoint: SatPoint {
+          outpoint: OutPoint { txid, vout: 0 },
+          offset: 0
+        },
+        timestamp: 2,
+        value: Some(50 * COIN_VALUE),
+        address: Some("bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202".to_string())
+      }
+    );
+  }
+
+  #[test]
+  fn batch_recursive_endpoint() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(2);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer("bar", &mut metadata).unwrap();
+
+    let first = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metadata: Some(metadata.clone()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    let second = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "baz").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let first = InscriptionId {
+      txid: first,
+      index: 0,
+    };
+
+    let second = InscriptionId {
+      txid: second,
+      index: 0,
+    };
+
+    let missing = InscriptionId {
+      txid: second.txid,
+      index: 1,
+    };
+
+    pretty_assert_eq!(
+      server.post_json::<Vec<api::BatchInscription>>("/r/batch", &vec![first, second, missing]),
+      vec![
+        api::BatchInscription {
+          id: first,
+          inscription: Some(
+            server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{first}"))
+          ),
+          metadata: Some(hex::encode(metadata)),
+        },
+        api::BatchInscription {
+          id: second,
+          inscription: Some(
+            server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{second}"))
+          ),
+          metadata: None,
+        },
+        api::BatchInscription {
+          id: missing,
+          inscription: None,
+          metadata: None,
+        },
+      ]
+    );
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/r/batch"))
+      .json(&vec![first; 1001])
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+      response.text().unwrap(),
+      "batch of 1001 inscriptions exceeds maximum of 1000",
+    );
+  }
+
+  #[test]
+  fn batch_proxy() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+    ciborium::into_writer("bar", &mut metadata).unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        Inscription {
+          content_type: Some("text/plain".into()),
+          body: Some("foo".into()),
+          metadata: Some(metadata.clone()),
+          ..default()
+        }
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    let missing = InscriptionId { txid, index: 1 };
+
+    let server_with_proxy = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_option("--proxy", server.url.as_ref())
+      .build();
+
+    server_with_proxy.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server_with_proxy.post_json::<Vec<api::BatchInscription>>("/r/batch", &vec![id, missing]),
+      vec![
+        api::BatchInscription {
+          id,
+          inscription: Some(
+            server.get_json::<api::InscriptionRecursive>(format!("/r/inscription/{id}"))
+          ),
+          metadata: Some(hex::encode(metadata)),
         },
-        timestamp: 2,
-        value: Some(50 * COIN_VALUE),
-        address: Some("bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202".to_string())
-      }
+        api::BatchInscription {
+          id: missing,
+          inscription: None,
+          metadata: None,
+        },
+      ]
     );
   }
 
@@ -9129,26 +12357,67 @@ next
     );
   }
 
+  fn offer(server: &TestServer, seller: OutPoint, buyer: OutPoint, amount: u64) -> String {
+    let seller_output = server.core.tx_by_id(seller.txid).output[seller.vout.into_usize()].clone();
+
+    let mut psbt = Psbt::from_unsigned_tx(Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: seller,
+          ..default()
+        },
+        TxIn {
+          previous_output: buyer,
+          ..default()
+        },
+      ],
+      output: vec![
+        TxOut {
+          value: seller_output.value,
+          script_pubkey: ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array([1; 20])),
+        },
+        TxOut {
+          value: seller_output.value + Amount::from_sat(amount),
+          script_pubkey: seller_output.script_pubkey,
+        },
+      ],
+    })
+    .unwrap();
+
+    psbt.inputs[1].final_script_witness = Some(Witness::from_slice(&[[1; 64]]));
+
+    base64_encode(&psbt.serialize())
+  }
+
+  fn inscribe_for_offer(server: &TestServer) -> (InscriptionId, OutPoint) {
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    (InscriptionId { txid, index: 0 }, OutPoint { txid, vout: 0 })
+  }
+
   #[test]
   fn offers_are_accepted() {
-    let server = TestServer::builder().server_flag("--accept-offers").build();
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
 
-    let psbt0 = base64_encode(
-      &Psbt {
-        unsigned_tx: Transaction {
-          version: Version(0),
-          lock_time: LockTime::ZERO,
-          input: Vec::new(),
-          output: Vec::new(),
-        },
-        version: 0,
-        xpub: BTreeMap::new(),
-        proprietary: BTreeMap::new(),
-        unknown: BTreeMap::new(),
-        inputs: Vec::new(),
-        outputs: Vec::new(),
-      }
-      .serialize(),
+    let (inscription_id, seller) = inscribe_for_offer(&server);
+
+    let psbt0 = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+      1000,
     );
 
     let response = server.post("offer", &psbt0, StatusCode::OK);
@@ -9164,22 +12433,13 @@ next
       },
     );
 
-    let psbt1 = base64_encode(
-      &Psbt {
-        unsigned_tx: Transaction {
-          version: Version(1),
-          lock_time: LockTime::ZERO,
-          input: Vec::new(),
-          output: Vec::new(),
-        },
-        version: 0,
-        xpub: BTreeMap::new(),
-        proprietary: BTreeMap::new(),
-        unknown: BTreeMap::new(),
-        inputs: Vec::new(),
-        outputs: Vec::new(),
-      }
-      .serialize(),
+    server.mine_blocks(1);
+
+    let psbt1 = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(3, 0).compute_txid(), 0),
+      2000,
     );
 
     let response = server.post("offer", &psbt1, StatusCode::OK);
@@ -9190,15 +12450,245 @@ next
 
     assert_eq!(
       offers,
+      api::Offers {
+        offers: vec![psbt0.clone(), psbt1.clone()],
+      },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Offers>(format!("/offers/{inscription_id}")),
+      api::Offers {
+        offers: vec![psbt0.clone(), psbt1.clone()],
+      },
+    );
+
+    assert_eq!(
+      server.post_json::<api::Offers>("/offers", &vec![seller, OutPoint::null()]),
       api::Offers {
         offers: vec![psbt0, psbt1],
       },
     );
+
+    assert_eq!(
+      server.post_json::<api::Offers>("/offers", &vec![OutPoint::null()]),
+      api::Offers { offers: Vec::new() },
+    );
+  }
+
+  #[test]
+  fn duplicate_offers_are_ignored() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
+
+    let (_inscription_id, seller) = inscribe_for_offer(&server);
+
+    let psbt = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+      1000,
+    );
+
+    server.post("offer", &psbt, StatusCode::OK);
+    server.post("offer", &psbt, StatusCode::OK);
+
+    assert_eq!(
+      server.get_json::<api::Offers>("/offers"),
+      api::Offers { offers: vec![psbt] },
+    );
+  }
+
+  #[test]
+  fn invalid_offers_are_rejected() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
+
+    let (_inscription_id, seller) = inscribe_for_offer(&server);
+
+    let buyer = OutPoint::new(server.core.tx(2, 0).compute_txid(), 0);
+
+    #[track_caller]
+    fn case(server: &TestServer, psbt: Psbt, message: &str) {
+      let response = server.post(
+        "offer",
+        &base64_encode(&psbt.serialize()),
+        StatusCode::BAD_REQUEST,
+      );
+
+      assert_eq!(response.text().unwrap(), message);
+    }
+
+    let valid =
+      Psbt::deserialize(&base64_decode(&offer(&server, seller, buyer, 1000)).unwrap()).unwrap();
+
+    {
+      let mut psbt = valid.clone();
+      psbt.inputs[0].final_script_witness = Some(Witness::from_slice(&[[1; 64]]));
+      case(
+        &server,
+        psbt,
+        "offer must have exactly one unsigned seller input, found 0",
+      );
+    }
+
+    {
+      let mut psbt = valid.clone();
+      psbt.inputs[1].final_script_witness = None;
+      case(
+        &server,
+        psbt,
+        "offer must have exactly one unsigned seller input, found 2",
+      );
+    }
+
+    {
+      let spent = OutPoint::new(server.core.tx(1, 0).compute_txid(), 0);
+      let mut psbt = valid.clone();
+      psbt.unsigned_tx.input[1].previous_output = spent;
+      case(&server, psbt, &format!("offer input {spent} is spent"));
+    }
+
+    server.mine_blocks(1);
+
+    {
+      let mut psbt = valid;
+      let cardinal = OutPoint::new(server.core.tx(3, 0).compute_txid(), 0);
+      psbt.unsigned_tx.input[0].previous_output = cardinal;
+      case(
+        &server,
+        psbt,
+        &format!("offer seller input {cardinal} must contain exactly one inscription, found 0"),
+      );
+    }
+  }
+
+  #[test]
+  fn offers_with_spent_inputs_are_pruned() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .server_flag("--no-sync")
+      .build();
+
+    let (inscription_id, seller) = inscribe_for_offer(&server);
+
+    let psbt = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+      1000,
+    );
+
+    server.post("offer", &psbt, StatusCode::OK);
+
+    assert_eq!(server.index.prune_offers(None).unwrap(), 0);
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::Offers>("/offers"),
+      api::Offers { offers: Vec::new() },
+    );
+
+    assert_eq!(
+      server.get_json::<api::Offers>(format!("/offers/{inscription_id}")),
+      api::Offers { offers: Vec::new() },
+    );
+
+    assert_eq!(server.index.prune_offers(None).unwrap(), 0);
+
+    server.post("offer", &psbt, StatusCode::BAD_REQUEST);
+  }
+
+  #[test]
+  fn offers_expire() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
+
+    let (_inscription_id, seller) = inscribe_for_offer(&server);
+
+    let psbt = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+      1000,
+    );
+
+    server.post("offer", &psbt, StatusCode::OK);
+
+    assert_eq!(
+      server
+        .index
+        .prune_offers(Some(Duration::from_secs(3600)))
+        .unwrap(),
+      0
+    );
+
+    assert_eq!(server.index.prune_offers(Some(Duration::ZERO)).unwrap(), 1);
+
+    assert_eq!(
+      server.get_json::<api::Offers>("/offers"),
+      api::Offers { offers: Vec::new() },
+    );
+  }
+
+  #[test]
+  fn inscription_page_shows_offers() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
+
+    let (inscription_id, seller) = inscribe_for_offer(&server);
+
+    server.post(
+      "offer",
+      &offer(
+        &server,
+        seller,
+        OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+        1000,
+      ),
+      StatusCode::OK,
+    );
+
+    server.mine_blocks(1);
+
+    server.post(
+      "offer",
+      &offer(
+        &server,
+        seller,
+        OutPoint::new(server.core.tx(3, 0).compute_txid(), 0),
+        2000,
+      ),
+      StatusCode::OK,
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{inscription_id}"),
+      StatusCode::OK,
+      r".*<dt>offers</dt>\s*<dd>2000</dd>\s*<dd>1000</dd>.*",
+    );
   }
 
   #[test]
   fn offers_are_rejected_if_not_valid_psbts() {
-    let server = TestServer::builder().server_flag("--accept-offers").build();
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--accept-offers")
+      .build();
     server.post("offer", "0", StatusCode::BAD_REQUEST);
   }
 
@@ -9230,26 +12720,18 @@ next
   #[test]
   fn offer_acceptance_does_not_require_json_api() {
     let server = TestServer::builder()
+      .chain(Chain::Regtest)
       .server_flag("--disable-json-api")
       .server_flag("--accept-offers")
       .build();
 
-    let psbt = base64_encode(
-      &Psbt {
-        unsigned_tx: Transaction {
-          version: Version(0),
-          lock_time: LockTime::ZERO,
-          input: Vec::new(),
-          output: Vec::new(),
-        },
-        version: 0,
-        xpub: BTreeMap::new(),
-        proprietary: BTreeMap::new(),
-        unknown: BTreeMap::new(),
-        inputs: Vec::new(),
-        outputs: Vec::new(),
-      }
-      .serialize(),
+    let (_inscription_id, seller) = inscribe_for_offer(&server);
+
+    let psbt = offer(
+      &server,
+      seller,
+      OutPoint::new(server.core.tx(2, 0).compute_txid(), 0),
+      1000,
     );
 
     server.post("offer", &psbt, StatusCode::OK);
@@ -1,4 +1,140 @@
-use super::*;
+use {super::*, std::sync::LazyLock};
+
+pub(super) async fn address_inscriptions(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<Address<NetworkUnchecked>>,
+) -> ServerResult {
+  address_inscriptions_paginated(
+    Extension(server_config),
+    Extension(index),
+    Path((address, 0)),
+  )
+  .await
+}
+
+pub(super) async fn address_inscriptions_paginated(
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Extension(index): Extension<Arc<Index>>,
+  Path((address, page)): Path<(Address<NetworkUnchecked>, usize)>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    if !index.has_address_index() {
+      return Err(ServerError::NotFound(
+        "this server has no address index".to_string(),
+      ));
+    }
+
+    let address = address
+      .require_network(server_config.chain.network())
+      .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+    let mut outputs = index.get_address_info(&address)?;
+
+    outputs.sort();
+
+    let ids = index
+      .get_inscriptions_for_outputs(&outputs)?
+      .ok_or_else(|| ServerError::NotFound("this server has no inscription index".to_string()))?;
+
+    Ok(Json(inscriptions_page(ids, page)?).into_response())
+  })
+}
+
+pub(super) async fn batch(
+  Extension(index): Extension<Arc<Index>>,
+  Extension(server_config): Extension<Arc<ServerConfig>>,
+  Json(inscription_ids): Json<Vec<InscriptionId>>,
+) -> ServerResult<Json<Vec<api::BatchInscription>>> {
+  const MAX_BATCH_SIZE: usize = 1000;
+
+  task::block_in_place(|| {
+    if inscription_ids.len() > MAX_BATCH_SIZE {
+      return Err(ServerError::BadRequest(format!(
+        "batch of {} inscriptions exceeds maximum of {MAX_BATCH_SIZE}",
+        inscription_ids.len(),
+      )));
+    }
+
+    let mut response = Vec::new();
+    let mut missing = Vec::new();
+
+    for id in inscription_ids {
+      let (inscription, metadata) = if let Some(inscription) = index.get_inscription_by_id(id)? {
+        (
+          Some(inscription_recursive(
+            &index,
+            &server_config,
+            id,
+            &inscription,
+          )?),
+          inscription.metadata.map(hex::encode),
+        )
+      } else {
+        missing.push(id);
+        (None, None)
+      };
+
+      response.push(api::BatchInscription {
+        id,
+        inscription,
+        metadata,
+      });
+    }
+
+    // mirror `proxy_layer`, which forwards requests for unknown inscriptions
+    // to the proxy, but forward all of them in a single batch request
+    if let Some(proxy) = &server_config.proxy
+      && !missing.is_empty()
+    {
+      let mut proxied = proxy_batch(proxy, &missing)?
+        .into_iter()
+        .map(|batch_inscription| (batch_inscription.id, batch_inscription))
+        .collect::<BTreeMap<InscriptionId, api::BatchInscription>>();
+
+      for batch_inscription in &mut response {
+        if batch_inscription.inscription.is_none()
+          && let Some(proxied) = proxied.remove(&batch_inscription.id)
+        {
+          *batch_inscription = proxied;
+        }
+      }
+    }
+
+    Ok(Json(response))
+  })
+}
+
+/// Requests `inscription_ids` from `/r/batch` on `proxy`, returning no
+/// inscriptions if the proxy does not serve `/r/batch`.
+fn proxy_batch(
+  proxy: &Url,
+  inscription_ids: &[InscriptionId],
+) -> ServerResult<Vec<api::BatchInscription>> {
+  static CLIENT: LazyLock<reqwest::blocking::Client> = LazyLock::new(|| {
+    reqwest::blocking::Client::builder()
+      .timeout(Duration::from_secs(10))
+      .build()
+      .unwrap()
+  });
+
+  let response = CLIENT
+    .post(format!("{proxy}r/batch"))
+    .json(inscription_ids)
+    .send()
+    .map_err(|err| anyhow!(err))?;
+
+  if response.status() == StatusCode::NOT_FOUND {
+    return Ok(Vec::new());
+  }
+
+  Ok(
+    response
+      .error_for_status()
+      .and_then(|response| response.json())
+      .map_err(|err| anyhow!(err))?,
+  )
+}
 
 pub(super) async fn blockhash(
   Extension(index): Extension<Arc<Index>>,
@@ -349,58 +485,105 @@ pub(super) async fn inscription(
       )));
     };
 
-    let entry = index
-      .get_inscription_entry(inscription_id)
-      .unwrap()
-      .unwrap();
+    Ok(
+      Json(inscription_recursive(
+        &index,
+        &server_config,
+        inscription_id,
+        &inscription,
+      )?)
+      .into_response(),
+    )
+  })
+}
 
-    let satpoint = index
-      .get_inscription_satpoint_by_id(inscription_id)
+fn inscription_recursive(
+  index: &Index,
+  server_config: &ServerConfig,
+  inscription_id: InscriptionId,
+  inscription: &Inscription,
+) -> ServerResult<api::InscriptionRecursive> {
+  let entry = index
+    .get_inscription_entry(inscription_id)
+    .unwrap()
+    .unwrap();
+
+  let satpoint = index
+    .get_inscription_satpoint_by_id(inscription_id)
+    .ok()
+    .flatten()
+    .unwrap();
+
+  let output = if satpoint.outpoint == unbound_outpoint() {
+    None
+  } else {
+    Some(
+      index
+        .get_transaction(satpoint.outpoint.txid)?
+        .ok_or_not_found(|| format!("inscription {inscription_id} current transaction"))?
+        .output
+        .into_iter()
+        .nth(satpoint.outpoint.vout.try_into().unwrap())
+        .ok_or_not_found(|| format!("inscription {inscription_id} current transaction output"))?,
+    )
+  };
+
+  let address = output.as_ref().and_then(|output| {
+    server_config
+      .chain
+      .address_from_script(&output.script_pubkey)
       .ok()
-      .flatten()
-      .unwrap();
+      .map(|address| address.to_string())
+  });
+
+  Ok(api::InscriptionRecursive {
+    charms: Charm::charms(entry.charms),
+    content_type: inscription.content_type().map(|s| s.to_string()),
+    content_length: inscription.content_length(),
+    delegate: inscription.delegate(),
+    fee: entry.fee,
+    height: entry.height,
+    id: inscription_id,
+    number: entry.inscription_number,
+    output: satpoint.outpoint,
+    value: output.as_ref().map(|o| o.value.to_sat()),
+    sat: entry.sat,
+    satpoint,
+    timestamp: timestamp(entry.timestamp.into()).timestamp(),
+    address,
+  })
+}
 
-    let output = if satpoint.outpoint == unbound_outpoint() {
-      None
+pub(super) async fn inscription_siblings(
+  Extension(index): Extension<Arc<Index>>,
+  Path(inscription_id): Path<InscriptionId>,
+) -> ServerResult {
+  inscription_siblings_paginated(Extension(index), Path((inscription_id, 0))).await
+}
+
+pub(super) async fn inscription_siblings_paginated(
+  Extension(index): Extension<Arc<Index>>,
+  Path((inscription_id, page)): Path<(InscriptionId, usize)>,
+) -> ServerResult {
+  task::block_in_place(|| {
+    let satpoint = index
+      .get_inscription_satpoint_by_id(inscription_id)?
+      .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+    // unbound inscriptions share a placeholder outpoint, but are not
+    // co-located with one another
+    let ids = if satpoint.outpoint == unbound_outpoint() {
+      Vec::new()
     } else {
-      Some(
-        index
-          .get_transaction(satpoint.outpoint.txid)?
-          .ok_or_not_found(|| format!("inscription {inscription_id} current transaction"))?
-          .output
-          .into_iter()
-          .nth(satpoint.outpoint.vout.try_into().unwrap())
-          .ok_or_not_found(|| format!("inscription {inscription_id} current transaction output"))?,
-      )
+      index
+        .get_inscriptions_for_output(satpoint.outpoint)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| *id != inscription_id)
+        .collect()
     };
 
-    let address = output.as_ref().and_then(|output| {
-      server_config
-        .chain
-        .address_from_script(&output.script_pubkey)
-        .ok()
-        .map(|address| address.to_string())
-    });
-
-    Ok(
-      Json(api::InscriptionRecursive {
-        charms: Charm::charms(entry.charms),
-        content_type: inscription.content_type().map(|s| s.to_string()),
-        content_length: inscription.content_length(),
-        delegate: inscription.delegate(),
-        fee: entry.fee,
-        height: entry.height,
-        id: inscription_id,
-        number: entry.inscription_number,
-        output: satpoint.outpoint,
-        value: output.as_ref().map(|o| o.value.to_sat()),
-        sat: entry.sat,
-        satpoint,
-        timestamp: timestamp(entry.timestamp.into()).timestamp(),
-        address,
-      })
-      .into_response(),
-    )
+    Ok(Json(inscriptions_page(ids, page)?).into_response())
   })
 }
 
@@ -489,6 +672,72 @@ pub(super) async fn parents_paginated(
   })
 }
 
+pub(super) async fn rune(
+  Extension(index): Extension<Arc<Index>>,
+  Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Rune>>,
+) -> ServerResult<Json<api::RuneRecursive>> {
+  task::block_in_place(|| {
+    let (id, entry, parent) = get_rune(&index, query)?;
+
+    Ok(Json(api::RuneRecursive {
+      block: entry.block,
+      burned: entry.burned,
+      divisibility: entry.divisibility,
+      etching: entry.etching,
+      id,
+      max_supply: entry.max_supply(),
+      mints: entry.mints,
+      number: entry.number,
+      parent,
+      premine: entry.premine,
+      spaced_rune: entry.spaced_rune,
+      supply: entry.supply(),
+      symbol: entry.symbol,
+      terms: entry.terms,
+      timestamp: entry.timestamp,
+      turbo: entry.turbo,
+    }))
+  })
+}
+
+pub(super) async fn rune_mintable(
+  Extension(index): Extension<Arc<Index>>,
+  Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Rune>>,
+) -> ServerResult<Json<api::RuneMintable>> {
+  task::block_in_place(|| {
+    let (_, entry, _) = get_rune(&index, query)?;
+
+    let height = index.block_height()?.unwrap_or(Height(0)).n() + 1;
+
+    let (amount, reason) = match entry.mintable(height.into()) {
+      Ok(amount) => (Some(amount), None),
+      Err(err) => (None, Some(err.to_string())),
+    };
+
+    Ok(Json(api::RuneMintable {
+      amount,
+      height,
+      mintable: amount.is_some(),
+      reason,
+    }))
+  })
+}
+
+fn get_rune(
+  index: &Index,
+  query: query::Rune,
+) -> ServerResult<(RuneId, RuneEntry, Option<InscriptionId>)> {
+  if !index.has_rune_index() {
+    return Err(ServerError::NotFound(
+      "this server has no rune index".to_string(),
+    ));
+  }
+
+  let rune = Server::resolve_rune(index, query)?;
+
+  index.rune(rune)?.ok_or_not_found(|| format!("rune {rune}"))
+}
+
 pub(super) async fn sat(
   Extension(index): Extension<Arc<Index>>,
   Path(sat): Path<u64>,
@@ -639,3 +888,23 @@ pub(super) async fn utxo(
     )
   })
 }
+
+fn inscriptions_page(ids: Vec<InscriptionId>, page: usize) -> ServerResult<api::Inscriptions> {
+  let page_index = u32::try_from(page).map_err(|_| anyhow!("page index {} out of range", page))?;
+
+  let mut ids = ids
+    .into_iter()
+    .skip(page.saturating_mul(PAGE_SIZE))
+    .take(PAGE_SIZE + 1)
+    .collect::<Vec<InscriptionId>>();
+
+  let more = ids.len() > PAGE_SIZE;
+
+  ids.truncate(PAGE_SIZE);
+
+  Ok(api::Inscriptions {
+    ids,
+    more,
+    page_index,
+  })
+}
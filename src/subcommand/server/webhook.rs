@@ -0,0 +1,182 @@
+use {
+  super::*,
+  bitcoin::hashes::{HashEngine, Hmac, HmacEngine, sha256},
+  tokio::sync::{
+    broadcast::error::RecvError,
+    mpsc::{self, error::TrySendError},
+  },
+};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_ATTEMPTS: u32 = 5;
+const QUEUE_CAPACITY: usize = 100_000;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-Ord-Signature";
+
+pub(crate) struct Webhook {
+  client: reqwest::Client,
+  secret: Option<String>,
+  url: String,
+}
+
+impl Webhook {
+  pub(crate) fn new(url: String, secret: Option<String>) -> Result<Self> {
+    Ok(Self {
+      client: reqwest::Client::builder().timeout(TIMEOUT).build()?,
+      secret,
+      url,
+    })
+  }
+
+  pub(crate) fn signature(secret: &str, body: &[u8]) -> String {
+    let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+    engine.input(body);
+    format!("sha256={}", Hmac::<sha256::Hash>::from_engine(engine))
+  }
+
+  /// Delivers events from `receiver` in order. Events are moved off the
+  /// broadcast channel into a per-webhook queue as soon as they arrive, so a
+  /// slow or failing endpoint delays its own deliveries without causing events
+  /// to be skipped.
+  pub(crate) async fn run(self, mut receiver: broadcast::Receiver<BroadcastEvent>) {
+    let url = self.url.clone();
+
+    let (sender, mut queue) = mpsc::channel::<Event>(QUEUE_CAPACITY);
+
+    tokio::spawn(async move {
+      while let Some(event) = queue.recv().await {
+        if let Err(err) = self.deliver(&event).await {
+          log::error!("failed to deliver event to webhook {}: {err}", self.url);
+        }
+      }
+    });
+
+    loop {
+      match receiver.recv().await {
+        Ok(event) => {
+          if let Err(TrySendError::Full(_)) = sender.try_send(event.event) {
+            log::error!("webhook {url} queue holds {QUEUE_CAPACITY} events, dropping event");
+          }
+        }
+        Err(RecvError::Lagged(skipped)) => {
+          log::error!("webhook {url} skipped {skipped} events");
+        }
+        Err(RecvError::Closed) => return,
+      }
+    }
+  }
+
+  async fn deliver(&self, event: &Event) -> Result {
+    let body = serde_json::to_vec(event)?;
+
+    let mut attempt = 1;
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+      let mut request = self
+        .client
+        .post(&self.url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.clone());
+
+      if let Some(secret) = &self.secret {
+        request = request.header(SIGNATURE_HEADER, Self::signature(secret, &body));
+      }
+
+      let err = match request.send().await {
+        Ok(response) if response.status().is_success() => return Ok(()),
+        Ok(response) => anyhow!("webhook returned status {}", response.status()),
+        Err(err) => err.into(),
+      };
+
+      if attempt == MAX_ATTEMPTS {
+        return Err(err.context(format!("giving up after {MAX_ATTEMPTS} attempts")));
+      }
+
+      log::warn!(
+        "webhook {} attempt {attempt} failed, retrying in {}ms: {err}",
+        self.url,
+        backoff.as_millis(),
+      );
+
+      tokio::time::sleep(backoff).await;
+
+      attempt += 1;
+      backoff *= 2;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn signature() {
+    assert_eq!(
+      Webhook::signature("key", b"The quick brown fox jumps over the lazy dog"),
+      "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+    );
+  }
+
+  #[tokio::test(flavor = "multi_thread")]
+  async fn slow_endpoint_does_not_cause_skipped_events() {
+    let delivered = Arc::new(Mutex::new(Vec::<u32>::new()));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    {
+      let delivered = delivered.clone();
+
+      let router = Router::new().route(
+        "/",
+        post(move |body: axum::body::Bytes| async move {
+          tokio::time::sleep(Duration::from_millis(100)).await;
+
+          if let Event::BlockCommitted { block_height, .. } =
+            serde_json::from_slice::<Event>(&body).unwrap()
+          {
+            delivered.lock().unwrap().push(block_height);
+          }
+
+          StatusCode::OK
+        }),
+      );
+
+      tokio::spawn(axum::serve(listener, router).into_future());
+    }
+
+    let (sender, receiver) = broadcast::channel(1);
+
+    tokio::spawn(
+      Webhook::new(format!("http://127.0.0.1:{port}/"), None)
+        .unwrap()
+        .run(receiver),
+    );
+
+    for block_height in 0..10 {
+      sender
+        .send(BroadcastEvent {
+          event: Event::BlockCommitted {
+            block_hash: BlockHash::all_zeros(),
+            block_height,
+          },
+          number: None,
+          script_pubkey: None,
+        })
+        .unwrap();
+
+      tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+
+    while delivered.lock().unwrap().len() < 10 && Instant::now() < deadline {
+      tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    assert_eq!(*delivered.lock().unwrap(), (0..10).collect::<Vec<u32>>());
+  }
+}
@@ -1,6 +1,9 @@
 use {
   super::*,
-  crate::wallet::{ListDescriptorsResult, Wallet, batch, wallet_constructor::WalletConstructor},
+  crate::wallet::{
+    ListDescriptorsResult, Wallet, batch, protection::Protection, transaction_builder,
+    wallet_constructor::WalletConstructor,
+  },
   shared_args::SharedArgs,
 };
 
@@ -11,6 +14,10 @@ pub mod burn;
 pub mod cardinals;
 pub mod create;
 pub mod dump;
+pub mod extract;
+pub mod finalize;
+pub mod freeze;
+pub mod history;
 pub mod inscribe;
 pub mod inscriptions;
 mod label;
@@ -29,6 +36,7 @@ pub mod sign;
 pub mod split;
 pub mod sweep;
 pub mod transactions;
+pub mod unfreeze;
 
 #[derive(Debug, Parser)]
 pub(crate) struct WalletCommand {
@@ -36,6 +44,27 @@ pub(crate) struct WalletCommand {
   pub(crate) name: String,
   #[arg(long, alias = "nosync", help = "Do not update index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    value_name = "CHARM",
+    help = "Never spend outputs containing sats with <CHARM> as cardinals. May be given \
+    multiple times. Requires index created with `--index-sats`."
+  )]
+  pub(crate) protect_charm: Vec<Charm>,
+  #[arg(
+    long,
+    value_name = "RARITY",
+    help = "Never spend outputs containing <RARITY> or rarer sats as cardinals. Requires index \
+    created with `--index-sats`."
+  )]
+  pub(crate) protect_rarity: Option<Rarity>,
+  #[arg(
+    long,
+    value_name = "FEE_RATE",
+    help = "Before running command, split protected sats into their own outputs in a \
+    transaction paying <FEE_RATE> sats/vB. Requires `--protect-rarity` or `--protect-charm`."
+  )]
+  pub(crate) protect_split: Option<FeeRate>,
   #[arg(
     long,
     help = "Use ord running at <SERVER_URL>. [default: http://localhost:80]"
@@ -62,6 +91,14 @@ pub(crate) enum Subcommand {
   Create(create::Create),
   #[command(about = "Dump wallet descriptors")]
   Dump,
+  #[command(about = "Extract rare sats into their own outputs")]
+  Extract(extract::Extract),
+  #[command(about = "Finalize and broadcast signed PSBT")]
+  Finalize(finalize::Finalize),
+  #[command(about = "Freeze outputs, inscriptions, or runes")]
+  Freeze(freeze::Freeze),
+  #[command(about = "See wallet transaction history annotated with inscriptions and runes")]
+  History(history::History),
   #[command(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
@@ -96,6 +133,8 @@ pub(crate) enum Subcommand {
   Sweep(sweep::Sweep),
   #[command(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
+  #[command(about = "Unfreeze outputs, inscriptions, or runes")]
+  Unfreeze(unfreeze::Unfreeze),
 }
 
 impl WalletCommand {
@@ -106,9 +145,17 @@ impl WalletCommand {
       _ => {}
     };
 
-    let wallet = WalletConstructor::construct(
+    let protection = Protection::new(self.protect_rarity, self.protect_charm.clone())?;
+
+    ensure!(
+      self.protect_split.is_none() || !protection.is_empty(),
+      "`--protect-split` requires `--protect-rarity` or `--protect-charm`"
+    );
+
+    let mut wallet = WalletConstructor::construct(
       self.name.clone(),
       self.no_sync,
+      protection.clone(),
       settings.clone(),
       self
         .server_url
@@ -120,7 +167,15 @@ impl WalletCommand {
         .context("invalid server URL")?,
     )?;
 
-    match self.subcommand {
+    if let Some(fee_rate) = self.protect_split
+      && let Some(txid) = extract::Extract::split_protected(&mut wallet, &protection, fee_rate)?
+    {
+      eprintln!("Split protected sats into their own outputs in transaction {txid}");
+    }
+
+    let protected = wallet.protected_utxos().len();
+
+    let result = match self.subcommand {
       Subcommand::Addresses => addresses::run(wallet),
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Batch(batch) => batch.run(wallet),
@@ -128,6 +183,10 @@ impl WalletCommand {
       Subcommand::Cardinals => cardinals::run(wallet),
       Subcommand::Create(_) | Subcommand::Restore(_) => unreachable!(),
       Subcommand::Dump => dump::run(wallet),
+      Subcommand::Extract(extract) => extract.run(wallet),
+      Subcommand::Finalize(finalize) => finalize.run(wallet),
+      Subcommand::Freeze(freeze) => freeze.run(wallet),
+      Subcommand::History(history) => history.run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
       Subcommand::Label => label::run(wallet),
@@ -144,9 +203,29 @@ impl WalletCommand {
       Subcommand::Split(split) => split.run(wallet),
       Subcommand::Sweep(sweep) => sweep.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
+      Subcommand::Unfreeze(unfreeze) => unfreeze.run(wallet),
+    };
+
+    match result {
+      Err(err) if protected > 0 && Self::is_cardinal_shortfall(&err) => Err(anyhow!(
+        "{err} ({protected} output(s) containing protected sats were excluded, run \
+          `ord wallet extract` or pass `--protect-split` to split protected sats into their own \
+          outputs)"
+      )),
+      result => result,
     }
   }
 
+  fn is_cardinal_shortfall(err: &Error) -> bool {
+    matches!(
+      err.downcast_ref::<transaction_builder::Error>(),
+      Some(transaction_builder::Error::NotEnoughCardinalUtxos)
+    ) || matches!(
+      err.downcast_ref::<SnafuError>(),
+      Some(SnafuError::NotEnoughCardinalUtxos | SnafuError::NoCardinalUtxos)
+    )
+  }
+
   fn parse_metadata(cbor: Option<PathBuf>, json: Option<PathBuf>) -> Result<Option<Vec<u8>>> {
     match (cbor, json) {
       (None, None) => Ok(None),
@@ -50,12 +50,12 @@ impl Batch {
       self.shared.compress,
     )?;
 
-    let mut locked_utxos = wallet.locked_utxos().clone();
+    let mut locked_utxos = wallet.locked_and_protected_utxos();
 
     locked_utxos.extend(
       reveal_satpoints
         .iter()
-        .map(|(satpoint, txout)| (satpoint.outpoint, txout.clone())),
+        .map(|(satpoint, _txout)| satpoint.outpoint),
     );
 
     if let Some(etching) = batchfile.etching {
@@ -67,15 +67,18 @@ impl Batch {
       destinations,
       dry_run: self.shared.dry_run,
       etching: batchfile.etching,
+      frozen_utxos: wallet.frozen_utxos()?,
       inscriptions,
       mode: batchfile.mode,
       no_backup: self.shared.no_backup,
       no_limit: self.shared.no_limit,
       parent_info,
       postages,
+      psbt_out: self.shared.psbt_out,
       reinscribe: batchfile.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints,
+      satisfaction_weight: wallet.satisfaction_weight(),
       satpoint: if let Some(sat) = batchfile.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
       } else {
@@ -83,7 +86,7 @@ impl Batch {
       },
     }
     .inscribe(
-      &locked_utxos.into_keys().collect(),
+      &locked_utxos,
       wallet.get_runic_outputs()?.unwrap_or_default(),
       utxos,
       &wallet,
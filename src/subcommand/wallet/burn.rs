@@ -28,6 +28,14 @@ pub struct Burn {
     you understand the implications."
   )]
   no_limit: bool,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write unsigned PSBT to <FILE> instead of signing and broadcasting transaction. Sign \
+    it externally and broadcast it with `ord wallet finalize`.",
+    value_name = "FILE"
+  )]
+  psbt_out: Option<PathBuf>,
   asset: Outgoing,
 }
 
@@ -126,6 +134,7 @@ impl Burn {
     let (txid, psbt, fee) = wallet.sign_and_broadcast_transaction(
       unsigned_transaction,
       self.dry_run,
+      self.psbt_out.as_deref(),
       Some(burn_amount),
     )?;
 
@@ -158,7 +167,7 @@ impl Burn {
         satpoint,
         wallet.inscriptions().clone(),
         wallet.utxos().clone(),
-        wallet.locked_utxos().clone().into_keys().collect(),
+        wallet.locked_and_protected_utxos(),
         runic_outputs,
         script_pubkey,
         change,
@@ -166,6 +175,8 @@ impl Burn {
         Target::ExactPostage(burn_amount),
         wallet.chain().network(),
       )
+      .satisfaction_weight(wallet.satisfaction_weight())
+      .frozen_utxos(wallet.frozen_utxos()?)
       .build_transaction()?,
     )
   }
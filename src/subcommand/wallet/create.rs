@@ -1,6 +1,7 @@
 use {
   super::*,
   bitcoin::secp256k1::rand::{self, RngCore},
+  restore::Timestamp,
 };
 
 #[derive(Serialize, Deserialize)]
@@ -9,7 +10,16 @@ pub struct Output {
   pub passphrase: Option<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct WatchOnlyOutput {
+  pub descriptors: Vec<String>,
+}
+
 #[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("watch")
+    .args(&["watch_only", "multisig"])
+))]
 pub(crate) struct Create {
   #[arg(
     long,
@@ -17,10 +27,79 @@ pub(crate) struct Create {
     help = "Use <PASSPHRASE> to derive wallet seed."
   )]
   pub(crate) passphrase: String,
+  #[arg(
+    long,
+    conflicts_with = "passphrase",
+    value_name = "XPUB|DESCRIPTOR",
+    help = "Create watch-only wallet without private keys from extended public key <XPUB> or \
+    taproot <DESCRIPTOR>. Commands that spend from the wallet return unsigned PSBTs."
+  )]
+  pub(crate) watch_only: Option<String>,
+  #[arg(
+    long,
+    conflicts_with = "passphrase",
+    requires = "cosigner",
+    value_name = "THRESHOLD",
+    help = "Create watch-only <THRESHOLD>-of-n multisig wallet from the extended public keys of \
+    n cosigners given with `--cosigner`. Commands that spend from the wallet return unsigned \
+    PSBTs for cosigners to sign."
+  )]
+  pub(crate) multisig: Option<usize>,
+  #[arg(
+    long,
+    requires = "multisig",
+    value_name = "XPUB",
+    help = "Add cosigner with extended public key <XPUB> to multisig wallet. May include key \
+    origin and derivation path, e.g. `[deadbeef/48'/0'/0'/2']xpub.../0`."
+  )]
+  pub(crate) cosigner: Vec<String>,
+  #[arg(
+    long,
+    requires = "multisig",
+    help = "Use P2WSH instead of taproot outputs for multisig wallet."
+  )]
+  pub(crate) p2wsh: bool,
+  #[arg(
+    long,
+    requires = "watch",
+    help = "Scan chain from <TIMESTAMP> onwards when creating a watch-only or multisig wallet. \
+    Can be a unix timestamp in seconds or the string `now`, to skip scanning"
+  )]
+  pub(crate) timestamp: Option<Timestamp>,
 }
 
 impl Create {
   pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    if let Some(threshold) = self.multisig {
+      return Ok(Some(Box::new(WatchOnlyOutput {
+        descriptors: Wallet::initialize_multisig(
+          name,
+          settings,
+          threshold,
+          &self.cosigner,
+          self.p2wsh,
+          self
+            .timestamp
+            .unwrap_or(Timestamp(bitcoincore_rpc::json::Timestamp::Time(0)))
+            .0,
+        )?,
+      })));
+    }
+
+    if let Some(key) = self.watch_only {
+      return Ok(Some(Box::new(WatchOnlyOutput {
+        descriptors: Wallet::initialize_watch_only(
+          name,
+          settings,
+          &key,
+          self
+            .timestamp
+            .unwrap_or(Timestamp(bitcoincore_rpc::json::Timestamp::Time(0)))
+            .0,
+        )?,
+      })));
+    }
+
     let mut entropy = [0; 16];
     rand::thread_rng().fill_bytes(&mut entropy);
 
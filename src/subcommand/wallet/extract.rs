@@ -0,0 +1,616 @@
+use {super::*, crate::wallet::protection::Protection};
+
+#[derive(Debug, PartialEq)]
+enum Error {
+  Dust {
+    sat: Sat,
+    value: u64,
+    dust: u64,
+  },
+  LeadingDust {
+    output: OutPoint,
+    value: u64,
+    dust: u64,
+  },
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Dust { sat, value, dust } => write!(
+        f,
+        "cannot extract sat {sat} into output with value {value} below dust limit {dust}"
+      ),
+      Self::LeadingDust {
+        output,
+        value,
+        dust,
+      } => write!(
+        f,
+        "cannot extract sats from output {output}, since the {value} sats before the first \
+        extracted sat are below dust limit {dust}"
+      ),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("selection")
+    .required(true)
+    .multiple(true)
+    .args(&["charm", "rarity", "sat"])
+))]
+pub(crate) struct Extract {
+  #[arg(
+    long,
+    value_name = "CHARM",
+    help = "Extract sats with <CHARM>. May be given multiple times."
+  )]
+  charm: Vec<Charm>,
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Include <AMOUNT> postage with each extracted sat. [default: 10000sat]",
+    value_name = "AMOUNT"
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write unsigned PSBT to <FILE> instead of signing and broadcasting transaction. Sign \
+    it externally and broadcast it with `ord wallet finalize`",
+    value_name = "FILE"
+  )]
+  psbt_out: Option<PathBuf>,
+  #[arg(long, value_name = "RARITY", help = "Extract <RARITY> or rarer sats.")]
+  rarity: Option<Rarity>,
+  #[arg(
+    long,
+    value_name = "SAT",
+    help = "Extract <SAT>. May be given multiple times."
+  )]
+  sat: Vec<Sat>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Extracted {
+  pub sat: Sat,
+  pub output: OutPoint,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+  pub extracted: Vec<Extracted>,
+}
+
+impl Extract {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_sat_index(),
+      "`ord wallet extract` requires index created with `--index-sats`",
+    );
+
+    let protection = Protection::new(self.rarity, self.charm.clone())?;
+
+    let postage = self.postage.unwrap_or(TARGET_POSTAGE);
+
+    let runic_outputs = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let frozen_utxos = wallet.frozen_utxos()?;
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    for sat in &self.sat {
+      let satpoint = wallet.find_sat_in_outputs(*sat)?;
+
+      ensure!(
+        !runic_outputs.contains(&satpoint.outpoint),
+        "cannot extract sat {sat} from runic output {}",
+        satpoint.outpoint,
+      );
+
+      ensure!(
+        !frozen_utxos.contains(&satpoint.outpoint),
+        "cannot extract sat {sat} from frozen output {}",
+        satpoint.outpoint,
+      );
+
+      ensure!(
+        !inscribed_outputs.contains(&satpoint.outpoint),
+        "cannot extract sat {sat} from inscribed output {}",
+        satpoint.outpoint,
+      );
+    }
+
+    let Some((unsigned_transaction, extracted)) =
+      Self::build(&wallet, &protection, &self.sat, postage, self.fee_rate)?
+    else {
+      bail!("wallet contains no sats to extract");
+    };
+
+    let (txid, psbt, fee) = wallet.sign_and_broadcast_transaction(
+      unsigned_transaction,
+      self.dry_run,
+      self.psbt_out.as_deref(),
+      None,
+    )?;
+
+    Ok(Some(Box::new(Output {
+      txid,
+      psbt,
+      fee,
+      extracted: extracted
+        .into_iter()
+        .map(|(sat, vout)| Extracted {
+          sat,
+          output: OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          },
+        })
+        .collect(),
+    })))
+  }
+
+  /// Splits sats protected by `protection` into their own outputs, paying
+  /// `fee_rate`, and replaces the outputs the split spends with the outputs it
+  /// creates in the wallet, so that the command which follows may spend the
+  /// change. Returns `None` if there are no protected sats to split.
+  pub(crate) fn split_protected(
+    wallet: &mut Wallet,
+    protection: &Protection,
+    fee_rate: FeeRate,
+  ) -> Result<Option<Txid>> {
+    ensure!(
+      !wallet.watch_only(),
+      "`--protect-split` cannot be used with watch-only wallets"
+    );
+
+    let Some((unsigned_transaction, extracted)) =
+      Self::build(wallet, protection, &[], TARGET_POSTAGE, fee_rate)?
+    else {
+      return Ok(None);
+    };
+
+    let (txid, _, _) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction.clone(), false, None, None)?;
+
+    wallet.apply_transaction(
+      &unsigned_transaction,
+      &extracted
+        .into_iter()
+        .map(|(_, vout)| vout)
+        .collect::<Vec<usize>>(),
+    );
+
+    Ok(Some(txid))
+  }
+
+  /// Builds a transaction moving sats protected by `protection`, and `sats`,
+  /// into their own outputs of at least `postage`, and returns it along with
+  /// the index of the output each extracted sat begins. Returns `None` if
+  /// the wallet contains no sats to extract.
+  fn build(
+    wallet: &Wallet,
+    protection: &Protection,
+    sats: &[Sat],
+    postage: Amount,
+    fee_rate: FeeRate,
+  ) -> Result<Option<(Transaction, Vec<(Sat, usize)>)>> {
+    let runic_outputs = wallet.get_runic_outputs()?.unwrap_or_default();
+
+    let frozen_utxos = wallet.frozen_utxos()?;
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let mut matching_outputs = BTreeSet::new();
+    let mut candidates = Vec::new();
+
+    for (outpoint, sat_ranges) in wallet.get_wallet_sat_ranges()? {
+      let matches = Self::matches(protection, sats, &sat_ranges);
+
+      if matches.is_empty() {
+        continue;
+      }
+
+      matching_outputs.insert(outpoint);
+
+      // runes would be transferred to the first output and inscriptions could
+      // be moved to a different output, so runic and inscribed outputs are
+      // left untouched
+      if runic_outputs.contains(&outpoint)
+        || inscribed_outputs.contains(&outpoint)
+        || frozen_utxos.contains(&outpoint)
+      {
+        continue;
+      }
+
+      let value = sat_ranges
+        .iter()
+        .map(|(start, end)| end - start)
+        .sum::<u64>();
+
+      // skip outputs containing a single sat which has already been extracted
+      if let [(0, end, _)] = matches.as_slice()
+        && value <= postage.to_sat().max(*end)
+      {
+        continue;
+      }
+
+      candidates.push((outpoint, value, matches));
+    }
+
+    if candidates.is_empty() {
+      return Ok(None);
+    }
+
+    // all change addresses share a script type, so the first one determines
+    // the dust limit for every output
+    let first_change = wallet.get_change_address()?;
+
+    let dust = first_change.script_pubkey().minimal_non_dust().to_sat();
+
+    let mut change = Some(first_change);
+
+    let mut layouts = Vec::new();
+
+    for (outpoint, value, matches) in candidates {
+      layouts.push((
+        outpoint,
+        Self::layout(value, &matches, postage.to_sat(), dust)?,
+      ));
+    }
+
+    // change before the first extracted sat of an input is merged into the
+    // last output of the preceding input, so inputs beginning with dust
+    // change are moved after inputs which don't
+    layouts
+      .sort_by_key(|(_, layout)| matches!(layout.first(), Some((value, None)) if *value < dust));
+
+    // the fee is paid from the last output, so the input with the largest
+    // trailing change is moved to the end
+    if let Some((i, _)) = layouts
+      .iter()
+      .enumerate()
+      .filter_map(|(i, (_, layout))| match layout.last() {
+        Some((value, None)) => Some((i, *value)),
+        _ => None,
+      })
+      .max_by_key(|(_, value)| *value)
+    {
+      let last = layouts.remove(i);
+      layouts.push(last);
+    }
+
+    let trailing_change = matches!(
+      layouts.last().and_then(|(_, layout)| layout.last()),
+      Some((_, None))
+    );
+
+    let mut inputs = Vec::new();
+    let mut extracted = Vec::new();
+    let mut output = Vec::<TxOut>::new();
+
+    for (outpoint, layout) in layouts {
+      inputs.push(outpoint);
+
+      for (i, (value, sat)) in layout.into_iter().enumerate() {
+        if i == 0 && sat.is_none() && value < dust {
+          let Some(last) = output.last_mut() else {
+            return Err(
+              Error::LeadingDust {
+                output: outpoint,
+                value,
+                dust,
+              }
+              .into(),
+            );
+          };
+
+          last.value += Amount::from_sat(value);
+
+          continue;
+        }
+
+        if let Some(sat) = sat {
+          extracted.push((sat, output.len()));
+        }
+
+        let address = match change.take() {
+          Some(address) => address,
+          None => wallet.get_change_address()?,
+        };
+
+        output.push(TxOut {
+          script_pubkey: address.script_pubkey(),
+          value: Amount::from_sat(value),
+        });
+      }
+    }
+
+    let transaction = Self::fund(
+      wallet,
+      fee_rate,
+      inputs,
+      output,
+      trailing_change,
+      &matching_outputs,
+      &inscribed_outputs,
+      &runic_outputs,
+    )?;
+
+    Ok(Some((transaction, extracted)))
+  }
+
+  /// Returns matching sats in `sat_ranges`, as ranges of offsets into the
+  /// output, along with the first matching sat of each range.
+  fn matches(
+    protection: &Protection,
+    sats: &[Sat],
+    sat_ranges: &[(u64, u64)],
+  ) -> Vec<(u64, u64, Sat)> {
+    let mut matches = Vec::new();
+    let mut offset = 0;
+
+    for (start, end) in sat_ranges.iter().copied() {
+      let mut ranges = protection.protected_ranges((start, end));
+
+      ranges.extend(
+        sats
+          .iter()
+          .filter(|sat| start <= sat.n() && sat.n() < end)
+          .map(|sat| (sat.n(), sat.n() + 1)),
+      );
+
+      ranges.sort();
+
+      for (range_start, range_end) in ranges {
+        if let Some((_, last_end, _)) = matches.last_mut()
+          && offset + range_start - start < *last_end
+        {
+          *last_end = (*last_end).max(offset + range_end - start);
+          continue;
+        }
+
+        matches.push((
+          offset + range_start - start,
+          offset + range_end - start,
+          Sat(range_start),
+        ));
+      }
+
+      offset += end - start;
+    }
+
+    matches
+  }
+
+  /// Splits an output with `value` into output values, such that each
+  /// matching sat begins an output of at least `postage`, and the sats in
+  /// between are returned as change. Change below `dust` is merged into the
+  /// preceding output. Leading change below `dust` has no preceding output,
+  /// and is returned as is, to be merged into the preceding output of the
+  /// transaction.
+  fn layout(
+    value: u64,
+    matches: &[(u64, u64, Sat)],
+    postage: u64,
+    dust: u64,
+  ) -> Result<Vec<(u64, Option<Sat>)>, Error> {
+    let mut outputs = Vec::new();
+    let mut cursor = 0;
+
+    for (i, (start, end, sat)) in matches.iter().enumerate() {
+      if *start > cursor {
+        outputs.push((start - cursor, None));
+      }
+
+      let next = matches
+        .get(i + 1)
+        .map(|(start, ..)| *start)
+        .unwrap_or(value);
+
+      let extract_end = (*end).max((start + postage).min(next));
+
+      outputs.push((extract_end - start, Some(*sat)));
+
+      cursor = extract_end;
+    }
+
+    if cursor < value {
+      outputs.push((value - cursor, None));
+    }
+
+    let mut layout = Vec::<(u64, Option<Sat>)>::new();
+
+    for (value, sat) in outputs {
+      if value < dust {
+        if let Some(sat) = sat {
+          return Err(Error::Dust { sat, value, dust });
+        }
+
+        if let Some((last, _)) = layout.last_mut() {
+          *last += value;
+          continue;
+        }
+      }
+
+      layout.push((value, sat));
+    }
+
+    Ok(layout)
+  }
+
+  /// Pays the fee from the last output if it is `trailing_change`, adding
+  /// cardinal inputs only if it would fall below dust, so that matching sats
+  /// are never spent as fees. Otherwise, adds cardinal inputs and a change
+  /// output to pay the fee.
+  fn fund(
+    wallet: &Wallet,
+    fee_rate: FeeRate,
+    inputs: Vec<OutPoint>,
+    mut output: Vec<TxOut>,
+    trailing_change: bool,
+    matching_outputs: &BTreeSet<OutPoint>,
+    inscribed_outputs: &BTreeSet<OutPoint>,
+    runic_outputs: &BTreeSet<OutPoint>,
+  ) -> Result<Transaction> {
+    let unspendable = wallet.locked_and_protected_utxos();
+
+    let frozen_utxos = wallet.frozen_utxos()?;
+
+    let mut cardinals = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _)| {
+        !matching_outputs.contains(outpoint)
+          && !inscribed_outputs.contains(outpoint)
+          && !runic_outputs.contains(outpoint)
+          && !unspendable.contains(outpoint)
+          && !frozen_utxos.contains(outpoint)
+      })
+      .map(|(outpoint, txout)| (txout.value, *outpoint))
+      .collect::<Vec<(Amount, OutPoint)>>();
+
+    cardinals.sort();
+
+    let mut funding = if trailing_change {
+      output.last().unwrap().value
+    } else {
+      output.push(TxOut {
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        value: Amount::ZERO,
+      });
+
+      Amount::ZERO
+    };
+
+    let dust = output.last().unwrap().script_pubkey.minimal_non_dust();
+
+    let mut input = inputs;
+
+    loop {
+      let fee = fee_rate.fee(TransactionBuilder::estimate_vbytes_with(
+        input.len(),
+        &output,
+        wallet.satisfaction_weight(),
+      ));
+
+      if funding >= fee + dust {
+        output.last_mut().unwrap().value = funding - fee;
+        break;
+      }
+
+      let Some((value, outpoint)) = cardinals.pop() else {
+        bail!("wallet does not contain enough cardinal UTXOs to pay fee");
+      };
+
+      input.push(outpoint);
+      funding += value;
+    }
+
+    Ok(Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: input
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn layout_extracts_first_sat() {
+    assert_eq!(
+      Extract::layout(100_000, &[(0, 1, Sat(5))], 10_000, 330).unwrap(),
+      [(10_000, Some(Sat(5))), (90_000, None)],
+    );
+  }
+
+  #[test]
+  fn layout_extracts_sat_in_middle_of_output() {
+    assert_eq!(
+      Extract::layout(100_000, &[(50_000, 50_001, Sat(5))], 10_000, 330).unwrap(),
+      [(50_000, None), (10_000, Some(Sat(5))), (40_000, None)],
+    );
+  }
+
+  #[test]
+  fn layout_merges_dust_change() {
+    assert_eq!(
+      Extract::layout(20_100, &[(100, 101, Sat(5))], 10_000, 330).unwrap(),
+      [(100, None), (10_000, Some(Sat(5))), (10_000, None)],
+    );
+
+    assert_eq!(
+      Extract::layout(10_100, &[(0, 1, Sat(5))], 10_000, 330).unwrap(),
+      [(10_100, Some(Sat(5)))],
+    );
+  }
+
+  #[test]
+  fn layout_shortens_postage_before_next_sat() {
+    assert_eq!(
+      Extract::layout(
+        100_000,
+        &[(0, 1, Sat(5)), (5_000, 5_001, Sat(10))],
+        10_000,
+        330
+      )
+      .unwrap(),
+      [
+        (5_000, Some(Sat(5))),
+        (10_000, Some(Sat(10))),
+        (85_000, None)
+      ],
+    );
+  }
+
+  #[test]
+  fn layout_extracts_ranges() {
+    assert_eq!(
+      Extract::layout(100_000, &[(1_000, 51_000, Sat(5))], 10_000, 330).unwrap(),
+      [(1_000, None), (50_000, Some(Sat(5))), (49_000, None)],
+    );
+  }
+
+  #[test]
+  fn layout_rejects_dust_extraction() {
+    assert_eq!(
+      Extract::layout(100_000, &[(0, 1, Sat(5)), (100, 101, Sat(10))], 10_000, 330),
+      Err(Error::Dust {
+        sat: Sat(5),
+        value: 100,
+        dust: 330,
+      }),
+    );
+  }
+}
@@ -0,0 +1,213 @@
+use {super::*, crate::wallet::psbt_assets::PsbtAssets};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txids: Vec<Txid>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Finalize {
+  #[arg(long, help = "Check and finalize <PSBT> but don't broadcast it.")]
+  dry_run: bool,
+  #[arg(
+    required = true,
+    help = "Finalize and broadcast signed <PSBT>. May be base64 or a path to a file. PSBTs for the \
+    same transaction, for example when cosigners of a multisig wallet each sign a copy, have their \
+    signatures combined. PSBTs for a transaction spending an output of an earlier one, like a \
+    reveal transaction spending its commit output, are finalized and broadcast after it."
+  )]
+  psbt: Vec<String>,
+}
+
+impl Finalize {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let mut psbts = Vec::<Psbt>::new();
+
+    for psbt in &self.psbt {
+      let psbt = Self::load(psbt)?;
+
+      let txid = psbt.unsigned_tx.compute_txid();
+
+      if let Some(existing) = psbts
+        .iter_mut()
+        .find(|existing| existing.unsigned_tx.compute_txid() == txid)
+      {
+        existing.combine(psbt).context("failed to combine PSBTs")?;
+      } else if psbts.is_empty()
+        || psbt.unsigned_tx.input.iter().any(|txin| {
+          psbts
+            .iter()
+            .any(|earlier| earlier.unsigned_tx.compute_txid() == txin.previous_output.txid)
+        })
+      {
+        psbts.push(psbt);
+      } else {
+        bail!("PSBT for transaction {txid} does not spend an output of an earlier PSBT");
+      }
+    }
+
+    let mut assetless = BTreeMap::new();
+    let mut txids = Vec::new();
+
+    for psbt in psbts {
+      let spends_assets = Self::check(&wallet, &psbt, &assetless, self.dry_run)?;
+
+      let result = wallet
+        .bitcoin_client()
+        .finalize_psbt(&base64_encode(&psbt.serialize()), None)?;
+
+      let signed_tx = match result.hex {
+        Some(hex) if result.complete => hex,
+        _ => bail!("PSBT is not fully signed"),
+      };
+
+      let txid = if self.dry_run {
+        consensus::encode::deserialize::<Transaction>(&signed_tx)?.compute_txid()
+      } else {
+        wallet.send_raw_transaction(&signed_tx, None)?
+      };
+
+      if !spends_assets {
+        assetless.insert(psbt.unsigned_tx.compute_txid(), psbt.unsigned_tx);
+      }
+
+      txids.push(txid);
+    }
+
+    Ok(Some(Box::new(Output { txids })))
+  }
+
+  fn load(psbt: &str) -> Result<Psbt> {
+    let psbt = if Path::new(psbt).is_file() {
+      fs::read_to_string(psbt).with_context(|| format!("failed to read PSBT from `{psbt}`"))?
+    } else {
+      psbt.into()
+    };
+
+    let psbt = base64_decode(psbt.trim()).context("failed to base64 decode PSBT")?;
+
+    Psbt::deserialize(&psbt).context("failed to deserialize PSBT")
+  }
+
+  /// Checks that every inscription and rune spent by `psbt` was present in
+  /// its input when `ord` created the PSBT, that no inscription is lost to
+  /// fees, and that runes are not burned by a cenotaph, and returns whether
+  /// it spends any inscriptions or runes. Every input is looked up in the ord
+  /// server, except, when `dry_run` is set, inputs spending `earlier`
+  /// transactions, which were not broadcast. Inputs that the ord server has
+  /// not indexed, like a reveal transaction's commit input, are only accepted
+  /// if they spend an output of one of the `earlier` transactions, which were
+  /// finalized in the same invocation and spend no inscriptions or runes.
+  fn check(
+    wallet: &Wallet,
+    psbt: &Psbt,
+    earlier: &BTreeMap<Txid, Transaction>,
+    dry_run: bool,
+  ) -> Result<bool> {
+    let tx = &psbt.unsigned_tx;
+
+    let expected = psbt
+      .inputs
+      .iter()
+      .map(PsbtAssets::load)
+      .collect::<Result<Vec<PsbtAssets>>>()?;
+
+    let output_info = wallet.get_output_info(
+      &tx
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .filter(|outpoint| !dry_run || !earlier.contains_key(&outpoint.txid))
+        .collect::<Vec<OutPoint>>(),
+    )?;
+
+    let mut offset = 0;
+    let mut inscription_offsets = Vec::new();
+    let mut runic = false;
+
+    for (txin, expected) in tx.input.iter().zip(expected) {
+      let outpoint = txin.previous_output;
+
+      let Some(info) = output_info.get(&outpoint).filter(|info| info.indexed) else {
+        let Some(txout) = earlier
+          .get(&outpoint.txid)
+          .and_then(|earlier| earlier.output.get(outpoint.vout.into_usize()))
+        else {
+          if expected.unbroadcast {
+            bail!(
+              "input {outpoint} spends an unbroadcast output, finalize it together with the PSBT \
+              that creates it"
+            );
+          }
+
+          bail!("input {outpoint} not found in ord server");
+        };
+
+        offset += txout.value.to_sat();
+
+        continue;
+      };
+
+      ensure! {
+        !info.spent,
+        "input {outpoint} already spent",
+      }
+
+      let Some(inscriptions) = &info.inscriptions else {
+        bail!("index must have inscription index to finalize PSBT");
+      };
+
+      for inscription_id in inscriptions {
+        ensure! {
+          expected.inscriptions.contains(inscription_id),
+          "input {outpoint} contains unexpected inscription {inscription_id}",
+        }
+
+        let satpoint = match wallet.inscription_info().get(inscription_id) {
+          Some(inscription) => inscription.satpoint,
+          None => {
+            wallet
+              .get_inscription(*inscription_id)?
+              .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?
+              .satpoint
+          }
+        };
+
+        inscription_offsets.push((*inscription_id, offset + satpoint.offset));
+      }
+
+      for (spaced_rune, pile) in info.runes.iter().flatten() {
+        ensure! {
+          expected.runes.get(&spaced_rune.rune) == Some(&pile.amount),
+          "input {outpoint} contains unexpected {spaced_rune} balance of {pile}",
+        }
+
+        runic = true;
+      }
+
+      offset += info.value;
+    }
+
+    let output_value = tx
+      .output
+      .iter()
+      .map(|txout| txout.value.to_sat())
+      .sum::<u64>();
+
+    let spends_assets = runic || !inscription_offsets.is_empty();
+
+    for (inscription_id, offset) in inscription_offsets {
+      ensure! {
+        offset < output_value,
+        "inscription {inscription_id} would be lost to fees",
+      }
+    }
+
+    ensure! {
+      !runic || !matches!(Runestone::decipher(tx), Some(Artifact::Cenotaph(_))),
+      "transaction would burn runes in a cenotaph",
+    }
+
+    Ok(spends_assets)
+  }
+}
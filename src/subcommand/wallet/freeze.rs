@@ -0,0 +1,29 @@
+use {super::*, crate::wallet::frozen::Frozen};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub frozen: BTreeMap<Frozen, BTreeSet<OutPoint>>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Freeze {
+  #[arg(
+    value_name = "OUTPOINT|INSCRIPTION|RUNE",
+    help = "Freeze <OUTPOINT>, <INSCRIPTION>, or every output holding <RUNE>, so that they are \
+    never spent until unfrozen. List frozen items and the wallet outputs they freeze if none are \
+    given."
+  )]
+  items: Vec<Frozen>,
+}
+
+impl Freeze {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    for item in self.items {
+      wallet.freeze(item)?;
+    }
+
+    Ok(Some(Box::new(Output {
+      frozen: wallet.frozen()?,
+    })))
+  }
+}
@@ -0,0 +1,497 @@
+use {
+  super::*,
+  crate::runes::MintError,
+  bitcoincore_rpc::json::{GetTransactionResult, GetTransactionResultDetailCategory},
+  std::collections::btree_map,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct History {
+  #[arg(long, help = "Also write history to comma-separated value file <CSV>.")]
+  csv: Option<PathBuf>,
+  #[arg(long, help = "Fetch at most <LIMIT> transactions.")]
+  limit: Option<u16>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub transaction: Txid,
+  pub confirmations: i32,
+  pub timestamp: u64,
+  pub net: i64,
+  pub inscriptions_received: Vec<InscriptionId>,
+  pub inscriptions_sent: Vec<InscriptionId>,
+  pub mint: Option<SpacedRune>,
+  pub runes_received: BTreeMap<SpacedRune, Decimal>,
+  pub runes_sent: BTreeMap<SpacedRune, Decimal>,
+  pub runes_burned: BTreeMap<SpacedRune, Decimal>,
+}
+
+impl Output {
+  const CSV_HEADER: &'static str = "transaction,confirmations,timestamp,net,inscriptions_received,\
+    inscriptions_sent,mint,runes_received,runes_sent,runes_burned";
+
+  fn csv_row(&self) -> String {
+    fn list<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+      items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join(";")
+    }
+
+    fn runes(runes: &BTreeMap<SpacedRune, Decimal>) -> String {
+      list(
+        runes
+          .iter()
+          .map(|(rune, decimal)| format!("{decimal}:{rune}")),
+      )
+    }
+
+    format!(
+      "{},{},{},{},{},{},{},{},{},{}",
+      self.transaction,
+      self.confirmations,
+      self.timestamp,
+      self.net,
+      list(&self.inscriptions_received),
+      list(&self.inscriptions_sent),
+      list(self.mint),
+      runes(&self.runes_received),
+      runes(&self.runes_sent),
+      runes(&self.runes_burned),
+    )
+  }
+}
+
+/// A wallet transaction, and which of its outputs belong to the wallet.
+struct WalletTransaction {
+  info: GetTransactionResult,
+  transaction: Transaction,
+  owned: BTreeSet<u32>,
+}
+
+/// Inscriptions moved by each transaction, with the output each was moved
+/// from, or `None` if it was revealed, and the output it was moved to.
+type Transfers = BTreeMap<Txid, Vec<(InscriptionId, Option<OutPoint>, OutPoint)>>;
+
+impl History {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let mut txids = Vec::new();
+
+    for tx in wallet.bitcoin_client().list_transactions(
+      None,
+      Some(self.limit.unwrap_or(u16::MAX).into()),
+      None,
+      None,
+    )? {
+      if !txids.contains(&tx.info.txid) {
+        txids.push(tx.info.txid);
+      }
+    }
+
+    let mut cache = BTreeMap::new();
+
+    let mut outpoints = Vec::new();
+
+    for txid in &txids {
+      if let Some(tx) = Self::wallet_transaction(&wallet, &mut cache, *txid)? {
+        outpoints.extend(
+          tx.transaction
+            .output
+            .iter()
+            .enumerate()
+            .filter(|(_, output)| !output.script_pubkey.is_op_return())
+            .map(|(vout, _)| OutPoint {
+              txid: *txid,
+              vout: vout.try_into().unwrap(),
+            }),
+        );
+      }
+    }
+
+    let outputs = if outpoints.is_empty() {
+      BTreeMap::new()
+    } else {
+      wallet.get_output_info(&outpoints)?
+    };
+
+    let inputs = Self::rune_inputs(&wallet, &cache)?;
+
+    let transfers = Self::inscription_transfers(&wallet, &cache, &outputs)?;
+
+    let mut history = Vec::new();
+
+    for txid in txids {
+      if let Some(output) = Self::annotate(
+        &wallet,
+        &mut cache,
+        &outputs,
+        inputs.as_ref(),
+        transfers.as_ref(),
+        txid,
+      )? {
+        history.push(output);
+      }
+    }
+
+    if let Some(path) = &self.csv {
+      let mut csv = String::from(Output::CSV_HEADER);
+      csv.push('\n');
+
+      for output in &history {
+        csv.push_str(&output.csv_row());
+        csv.push('\n');
+      }
+
+      fs::write(path, csv).with_context(|| format!("I/O error writing `{}`", path.display()))?;
+    }
+
+    Ok(Some(Box::new(history)))
+  }
+
+  /// Returns the outputs spent by confirmed wallet transactions, which are
+  /// used to find the runes those transactions burned. Returns `None` if the
+  /// index does not record the runes in spent outputs.
+  fn rune_inputs(
+    wallet: &Wallet,
+    cache: &BTreeMap<Txid, Option<WalletTransaction>>,
+  ) -> Result<Option<BTreeMap<OutPoint, api::Output>>> {
+    if !wallet.has_rune_index() || !wallet.has_rune_history_index() {
+      return Ok(None);
+    }
+
+    let outpoints = cache
+      .values()
+      .flatten()
+      .filter(|tx| tx.info.info.blockheight.is_some())
+      .flat_map(|tx| &tx.transaction.input)
+      .map(|input| input.previous_output)
+      .filter(|outpoint| !outpoint.is_null())
+      .collect::<BTreeSet<OutPoint>>()
+      .into_iter()
+      .collect::<Vec<OutPoint>>();
+
+    if outpoints.is_empty() {
+      return Ok(Some(BTreeMap::new()));
+    }
+
+    Ok(Some(wallet.get_output_info(&outpoints)?))
+  }
+
+  /// Returns the transfers recorded by the index of inscriptions in the
+  /// wallet, revealed by wallet transactions, or in the outputs of wallet
+  /// transactions. Returns `None` if the index does not record inscription
+  /// history.
+  fn inscription_transfers(
+    wallet: &Wallet,
+    cache: &BTreeMap<Txid, Option<WalletTransaction>>,
+    outputs: &BTreeMap<OutPoint, api::Output>,
+  ) -> Result<Option<Transfers>> {
+    let mut candidates = wallet
+      .inscriptions()
+      .values()
+      .flatten()
+      .copied()
+      .chain(
+        outputs
+          .values()
+          .flat_map(|output| output.inscriptions.clone().unwrap_or_default()),
+      )
+      .collect::<BTreeSet<InscriptionId>>();
+
+    for (txid, tx) in cache {
+      if let Some(tx) = tx {
+        for index in 0..ParsedEnvelope::from_transaction(&tx.transaction).len() {
+          candidates.insert(InscriptionId {
+            txid: *txid,
+            index: index.try_into().unwrap(),
+          });
+        }
+      }
+    }
+
+    let candidates = candidates.into_iter().collect::<Vec<InscriptionId>>();
+
+    let missing = wallet
+      .missing_inscriptions(&candidates)?
+      .into_iter()
+      .collect::<BTreeSet<InscriptionId>>();
+
+    let inscriptions = candidates
+      .into_iter()
+      .filter(|inscription_id| !missing.contains(inscription_id))
+      .collect::<Vec<InscriptionId>>();
+
+    let mut transfers = Transfers::new();
+
+    for inscription in wallet.get_inscriptions(&inscriptions)? {
+      let Some(records) = inscription.transfers else {
+        return Ok(None);
+      };
+
+      let revealed = records
+        .first()
+        .map(|record| record.old_satpoint)
+        .unwrap_or(inscription.satpoint);
+
+      transfers.entry(inscription.id.txid).or_default().push((
+        inscription.id,
+        None,
+        revealed.outpoint,
+      ));
+
+      for record in records {
+        transfers.entry(record.txid).or_default().push((
+          inscription.id,
+          Some(record.old_satpoint.outpoint),
+          record.new_satpoint.outpoint,
+        ));
+      }
+    }
+
+    Ok(Some(transfers))
+  }
+
+  /// Annotates wallet transaction `txid` with its net change in wallet
+  /// balance, and the inscriptions and runes it moved in or out of the wallet,
+  /// according to the index. Inscriptions are taken from `transfers` if the
+  /// index records inscription history, and from the current contents of
+  /// `outputs` otherwise. Burned runes are only found if `inputs` holds the
+  /// outputs spent by `txid`. Returns `None` if no inputs or outputs of `txid`
+  /// belong to the wallet.
+  fn annotate(
+    wallet: &Wallet,
+    cache: &mut BTreeMap<Txid, Option<WalletTransaction>>,
+    outputs: &BTreeMap<OutPoint, api::Output>,
+    inputs: Option<&BTreeMap<OutPoint, api::Output>>,
+    transfers: Option<&Transfers>,
+    txid: Txid,
+  ) -> Result<Option<Output>> {
+    let mut net = 0i64;
+    let mut spent = BTreeSet::new();
+
+    let Some(previous_outputs) = Self::wallet_transaction(wallet, cache, txid)?.map(|tx| {
+      tx.transaction
+        .input
+        .iter()
+        .filter(|input| !input.previous_output.is_null())
+        .map(|input| input.previous_output)
+        .collect::<Vec<OutPoint>>()
+    }) else {
+      return Ok(None);
+    };
+
+    for outpoint in previous_outputs {
+      if let Some(previous) = Self::wallet_transaction(wallet, cache, outpoint.txid)?
+        && previous.owned.contains(&outpoint.vout)
+      {
+        net -= i64::try_from(
+          previous.transaction.output[outpoint.vout.into_usize()]
+            .value
+            .to_sat(),
+        )?;
+        spent.insert(outpoint);
+      }
+    }
+
+    let spends_wallet_outputs = !spent.is_empty();
+
+    let tx = Self::wallet_transaction(wallet, cache, txid)?.unwrap();
+
+    if tx.owned.is_empty() && !spends_wallet_outputs {
+      return Ok(None);
+    }
+
+    for vout in &tx.owned {
+      net += i64::try_from(tx.transaction.output[vout.into_usize()].value.to_sat())?;
+    }
+
+    let mut inscriptions_received = Vec::new();
+    let mut inscriptions_sent = Vec::new();
+    let mut runes_received = BTreeMap::new();
+    let mut runes_sent = BTreeMap::new();
+
+    if let Some(transfers) = transfers {
+      for (inscription_id, from, to) in transfers.get(&txid).into_iter().flatten() {
+        if to.txid == txid && tx.owned.contains(&to.vout) {
+          inscriptions_received.push(*inscription_id);
+        } else if from.is_some_and(|from| spent.contains(&from)) {
+          inscriptions_sent.push(*inscription_id);
+        }
+      }
+    }
+
+    let mut landed = BTreeMap::<SpacedRune, u128>::new();
+
+    for (outpoint, output) in outputs.range(OutPoint::new(txid, 0)..=OutPoint::new(txid, u32::MAX))
+    {
+      for (rune, pile) in output.runes.iter().flatten() {
+        *landed.entry(*rune).or_default() += pile.amount;
+      }
+
+      let (inscriptions, runes) = if tx.owned.contains(&outpoint.vout) {
+        (&mut inscriptions_received, &mut runes_received)
+      } else if spends_wallet_outputs {
+        (&mut inscriptions_sent, &mut runes_sent)
+      } else {
+        continue;
+      };
+
+      if transfers.is_none() {
+        inscriptions.extend(output.inscriptions.clone().unwrap_or_default());
+      }
+
+      for (rune, pile) in output.runes.clone().unwrap_or_default() {
+        let decimal = runes.entry(rune).or_insert(Decimal {
+          value: 0,
+          scale: pile.divisibility,
+        });
+
+        decimal.value += pile.amount;
+      }
+    }
+
+    let mut mint = None;
+    let mut runes_burned = BTreeMap::new();
+
+    // runes are only minted and burned once the transaction is confirmed
+    if wallet.has_rune_index()
+      && let Some(height) = tx.info.info.blockheight
+    {
+      let artifact = Runestone::decipher(&tx.transaction);
+
+      let mut unallocated = BTreeMap::<SpacedRune, Decimal>::new();
+
+      for input in &tx.transaction.input {
+        let Some(output) = inputs.and_then(|inputs| inputs.get(&input.previous_output)) else {
+          continue;
+        };
+
+        for (rune, pile) in output.runes.iter().flatten() {
+          unallocated
+            .entry(*rune)
+            .or_insert(Decimal {
+              value: 0,
+              scale: pile.divisibility,
+            })
+            .value += pile.amount;
+        }
+      }
+
+      if let Some(id) = artifact.as_ref().and_then(Artifact::mint)
+        && let Some((_, entry, _)) = wallet.get_rune_by_id(id)?
+      {
+        let rune = entry.spaced_rune;
+
+        let minted = match entry.mintable(height.into()) {
+          Ok(amount) => Some(amount),
+          // mints only increase, so the cap may have been reached after this
+          // transaction, in which case the mint succeeded if the transaction's
+          // outputs hold more of the rune than its inputs
+          Err(MintError::Cap(_)) => (landed.get(&rune).copied().unwrap_or_default()
+            > unallocated
+              .get(&rune)
+              .map(|decimal| decimal.value)
+              .unwrap_or_default())
+          .then(|| {
+            entry
+              .terms
+              .and_then(|terms| terms.amount)
+              .unwrap_or_default()
+          }),
+          Err(MintError::End(_) | MintError::Start(_) | MintError::Unmintable) => None,
+        };
+
+        if let Some(amount) = minted {
+          unallocated
+            .entry(rune)
+            .or_insert(Decimal {
+              value: 0,
+              scale: entry.divisibility,
+            })
+            .value += amount;
+
+          if runes_received.contains_key(&rune) {
+            mint = Some(rune);
+          }
+        }
+      }
+
+      if let Some(Artifact::Runestone(Runestone {
+        etching: Some(Etching {
+          rune: Some(rune), ..
+        }),
+        ..
+      })) = artifact
+        && let Some((_, entry, _)) = wallet.get_rune(rune)?
+        && entry.etching == txid
+      {
+        unallocated
+          .entry(entry.spaced_rune)
+          .or_insert(Decimal {
+            value: 0,
+            scale: entry.divisibility,
+          })
+          .value += entry.premine;
+      }
+
+      // without the runes in spent outputs, runes which landed in outputs
+      // that have since been spent would be mistaken for burned runes
+      if inputs.is_some() {
+        for (rune, decimal) in unallocated {
+          let landed = landed.get(&rune).copied().unwrap_or_default();
+
+          if decimal.value > landed {
+            runes_burned.insert(
+              rune,
+              Decimal {
+                value: decimal.value - landed,
+                scale: decimal.scale,
+              },
+            );
+          }
+        }
+      }
+    }
+
+    Ok(Some(Output {
+      transaction: txid,
+      confirmations: tx.info.info.confirmations,
+      timestamp: tx.info.info.blocktime.unwrap_or(tx.info.info.time),
+      net,
+      inscriptions_received,
+      inscriptions_sent,
+      mint,
+      runes_received,
+      runes_sent,
+      runes_burned,
+    }))
+  }
+
+  fn wallet_transaction<'a>(
+    wallet: &Wallet,
+    cache: &'a mut BTreeMap<Txid, Option<WalletTransaction>>,
+    txid: Txid,
+  ) -> Result<Option<&'a WalletTransaction>> {
+    if let btree_map::Entry::Vacant(entry) = cache.entry(txid) {
+      let tx = match wallet.get_wallet_transaction(txid)? {
+        Some(info) => Some(WalletTransaction {
+          transaction: info.transaction()?,
+          owned: info
+            .details
+            .iter()
+            .filter(|detail| !matches!(detail.category, GetTransactionResultDetailCategory::Send))
+            .map(|detail| detail.vout)
+            .collect(),
+          info,
+        }),
+        None => None,
+      };
+
+      entry.insert(tx);
+    }
+
+    Ok(cache[&txid].as_ref())
+  }
+}
@@ -86,6 +86,7 @@ impl Inscribe {
       }],
       dry_run: self.shared.dry_run,
       etching: None,
+      frozen_utxos: wallet.frozen_utxos()?,
       inscriptions: vec![Inscription::new(
         chain,
         self.shared.compress,
@@ -117,9 +118,11 @@ impl Inscribe {
       no_limit: self.shared.no_limit,
       parent_info: wallet.get_parent_info(self.parent.as_slice())?,
       postages: vec![self.postage.unwrap_or(TARGET_POSTAGE)],
+      psbt_out: self.shared.psbt_out,
       reinscribe: self.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints: Vec::new(),
+      satisfaction_weight: wallet.satisfaction_weight(),
       satpoint: if let Some(sat) = self.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
       } else {
@@ -127,7 +130,7 @@ impl Inscribe {
       },
     }
     .inscribe(
-      &wallet.locked_utxos().clone().into_keys().collect(),
+      &wallet.locked_and_protected_utxos(),
       wallet.get_runic_outputs()?.unwrap_or_default(),
       wallet.utxos(),
       &wallet,
@@ -13,6 +13,13 @@ pub(crate) struct Mint {
   postage: Option<Amount>,
   #[clap(long, help = "Send minted runes to <DESTINATION>.")]
   destination: Option<Address<NetworkUnchecked>>,
+  #[clap(
+    long,
+    help = "Write unsigned PSBT to <FILE> instead of signing and broadcasting mint transaction. \
+    Sign it externally and broadcast it with `ord wallet finalize`.",
+    value_name = "FILE"
+  )]
+  psbt_out: Option<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -93,18 +100,32 @@ impl Mint {
     let unsigned_transaction =
       fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction, None)?;
 
-    let signed_transaction = bitcoin_client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
+    let transaction = if let Some(path) = &self.psbt_out {
+      let unsigned_transaction =
+        consensus::encode::deserialize::<Transaction>(&unsigned_transaction)?;
 
-    let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
+      assert_eq!(
+        Runestone::decipher(&unsigned_transaction),
+        Some(Artifact::Runestone(runestone)),
+      );
 
-    assert_eq!(
-      Runestone::decipher(&signed_transaction),
-      Some(Artifact::Runestone(runestone)),
-    );
+      Wallet::write_psbt(&wallet.unsigned_psbt(&unsigned_transaction)?, path)?;
 
-    let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
+      unsigned_transaction.compute_txid()
+    } else {
+      let signed_transaction = bitcoin_client
+        .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+        .hex;
+
+      let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
+
+      assert_eq!(
+        Runestone::decipher(&signed_transaction),
+        Some(Artifact::Runestone(runestone)),
+      );
+
+      bitcoin_client.send_raw_transaction(&signed_transaction)?
+    };
 
     Ok(Some(Box::new(Output {
       rune: self.rune,
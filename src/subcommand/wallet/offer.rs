@@ -1,21 +1,37 @@
 use super::*;
 
 pub mod accept;
+pub mod buy;
+pub mod cancel;
 pub mod create;
+pub mod list;
+pub mod list_for_sale;
 
 #[derive(Debug, Parser)]
 pub(crate) enum Offer {
   #[command(about = "Accept offer to buy inscription")]
   Accept(accept::Accept),
+  #[command(about = "Buy inscription listed for sale")]
+  Buy(buy::Buy),
+  #[command(about = "Cancel offer to buy inscription")]
+  Cancel(cancel::Cancel),
   #[command(about = "Create offer to buy inscription")]
   Create(create::Create),
+  #[command(about = "List offers to buy inscriptions in wallet")]
+  List(list::List),
+  #[command(about = "List inscription for sale")]
+  ListForSale(list_for_sale::ListForSale),
 }
 
 impl Offer {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
     match self {
       Self::Accept(accept) => accept.run(wallet),
+      Self::Buy(buy) => buy.run(wallet),
+      Self::Cancel(cancel) => cancel.run(wallet),
       Self::Create(create) => create.run(wallet),
+      Self::List(list) => list.run(wallet),
+      Self::ListForSale(list_for_sale) => list_for_sale.run(wallet),
     }
   }
 }
@@ -12,15 +12,26 @@ pub struct Output {
 }
 
 #[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("outgoing")
+    .required(true)
+    .args(&["inscription", "rune"])
+))]
 pub(crate) struct Accept {
   #[arg(long, help = "Assert offer is for <AMOUNT>")]
   amount: Amount,
   #[arg(long, help = "Don't sign or broadcast transaction")]
   dry_run: bool,
   #[arg(long, help = "Assert offer is for <INSCRIPTION>")]
-  inscription: InscriptionId,
+  inscription: Option<InscriptionId>,
   #[arg(long, help = "Accept <PSBT> offer")]
   psbt: String,
+  #[arg(
+    long,
+    help = "Assert offer is for <DECIMAL:RUNE>",
+    value_name = "DECIMAL:RUNE"
+  )]
+  rune: Option<Outgoing>,
 }
 
 impl Accept {
@@ -46,31 +57,15 @@ impl Accept {
       bail!("PSBT contains no inputs owned by wallet");
     };
 
-    if let Some(runes) = wallet.get_runes_balances_in_output(&outgoing)? {
-      ensure! {
-        runes.is_empty(),
-        "outgoing input {} contains runes", outgoing,
-      }
-    }
-
-    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
-      bail! {
-        "index must have inscription index to accept PSBT",
-      }
-    };
-
     ensure! {
-      inscriptions.len() <= 1,
-      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+      !wallet.frozen_utxos()?.contains(&outgoing),
+      "outgoing input {} is frozen", outgoing,
     }
 
-    let Some(inscription) = inscriptions.into_iter().next() else {
-      bail!("outgoing input contains no inscriptions");
-    };
-
-    ensure! {
-      inscription == self.inscription,
-      "unexpected outgoing inscription {inscription}",
+    if let Some(inscription) = self.inscription {
+      Self::check_inscription(&wallet, outgoing, inscription)?;
+    } else if let Some(rune) = &self.rune {
+      Self::check_runes(&wallet, &psbt, outgoing, rune)?;
     }
 
     let balance_change = wallet.simulate_transaction(&psbt.unsigned_tx)?;
@@ -149,6 +144,174 @@ impl Accept {
     Ok(Some(Box::new(Output { txid })))
   }
 
+  fn check_inscription(wallet: &Wallet, outgoing: OutPoint, expected: InscriptionId) -> Result {
+    if let Some(runes) = wallet.get_runes_balances_in_output(&outgoing)? {
+      ensure! {
+        runes.is_empty(),
+        "outgoing input {} contains runes", outgoing,
+      }
+    }
+
+    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
+      bail! {
+        "index must have inscription index to accept PSBT",
+      }
+    };
+
+    ensure! {
+      inscriptions.len() <= 1,
+      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+    }
+
+    let Some(inscription) = inscriptions.into_iter().next() else {
+      bail!("outgoing input contains no inscriptions");
+    };
+
+    ensure! {
+      inscription == expected,
+      "unexpected outgoing inscription {inscription}",
+    }
+
+    Ok(())
+  }
+
+  /// Checks that the only runes leaving the seller's `outgoing` input are the
+  /// offered runes, and that all other runes in `outgoing` are returned to
+  /// outputs with the same script pubkey as `outgoing`.
+  fn check_runes(wallet: &Wallet, psbt: &Psbt, outgoing: OutPoint, rune: &Outgoing) -> Result {
+    let Outgoing::Rune { decimal, rune } = rune else {
+      bail!("`--rune` must be a rune amount, for example `1000:EXAMPLE`");
+    };
+
+    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outgoing)? else {
+      bail! {
+        "index must have inscription index to accept PSBT",
+      }
+    };
+
+    ensure! {
+      inscriptions.is_empty(),
+      "outgoing input {} contains {} inscriptions", outgoing, inscriptions.len(),
+    }
+
+    let Some(balances) = wallet.get_runes_balances_in_output(&outgoing)? else {
+      bail! {
+        "index must have rune index to accept PSBT",
+      }
+    };
+
+    let buyer_inputs = psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .map(|input| input.previous_output)
+      .filter(|outpoint| *outpoint != outgoing)
+      .collect::<Vec<OutPoint>>();
+
+    if !buyer_inputs.is_empty() {
+      for (outpoint, output) in wallet.get_output_info(&buyer_inputs)? {
+        ensure! {
+          output.runes.unwrap_or_default().is_empty(),
+          "buyer input `{outpoint}` contains runes",
+        }
+      }
+    }
+
+    let Some((id, entry, _parent)) = wallet.get_rune(rune.rune)? else {
+      bail!("rune {rune} has not been etched");
+    };
+
+    let mut outgoing_runes = BTreeMap::from([(id, (*rune, entry.divisibility, 0))]);
+
+    for (spaced_rune, pile) in balances {
+      let Some((id, ..)) = wallet.get_rune(spaced_rune.rune)? else {
+        bail!("rune {spaced_rune} has not been etched");
+      };
+
+      outgoing_runes.insert(id, (spaced_rune, pile.divisibility, pile.amount));
+    }
+
+    let runestone = match Runestone::decipher(&psbt.unsigned_tx) {
+      Some(Artifact::Runestone(runestone)) => runestone,
+      Some(Artifact::Cenotaph(_)) => bail!("PSBT contains cenotaph"),
+      None => Runestone::default(),
+    };
+
+    ensure! {
+      runestone.etching.is_none() && runestone.mint.is_none(),
+      "PSBT runestone may only contain edicts",
+    }
+
+    let outputs = &psbt.unsigned_tx.output;
+
+    let mut unallocated = outgoing_runes
+      .iter()
+      .map(|(id, (_, _, amount))| (*id, *amount))
+      .collect::<BTreeMap<RuneId, u128>>();
+
+    let mut allocated = vec![BTreeMap::<RuneId, u128>::new(); outputs.len()];
+
+    for edict in runestone.edicts {
+      let output = edict.output.into_usize();
+
+      ensure! {
+        edict.amount > 0 && output < outputs.len(),
+        "PSBT runestone edicts must have nonzero amounts and transfer to a single output",
+      }
+
+      let balance = unallocated.entry(edict.id).or_default();
+      let amount = edict.amount.min(*balance);
+      *balance -= amount;
+      *allocated[output].entry(edict.id).or_default() += amount;
+    }
+
+    let default = runestone
+      .pointer
+      .map(|pointer| pointer.into_usize())
+      .or_else(|| {
+        outputs
+          .iter()
+          .position(|output| !output.script_pubkey.is_op_return())
+      });
+
+    if let Some(default) = default {
+      for (id, amount) in unallocated {
+        *allocated[default].entry(id).or_default() += amount;
+      }
+    }
+
+    let seller_script_pubkey = &wallet.utxos()[&outgoing].script_pubkey;
+
+    for (output, allocation) in outputs.iter().zip(allocated) {
+      if output.script_pubkey != *seller_script_pubkey {
+        continue;
+      }
+
+      for (id, amount) in allocation {
+        if let Some((_, _, balance)) = outgoing_runes.get_mut(&id) {
+          *balance = balance.saturating_sub(amount);
+        }
+      }
+    }
+
+    let expected = decimal.to_integer(entry.divisibility)?;
+
+    for (outgoing_id, (spaced_rune, divisibility, amount)) in outgoing_runes {
+      let expected = if outgoing_id == id { expected } else { 0 };
+
+      ensure! {
+        amount == expected,
+        "unexpected outgoing {} {spaced_rune}",
+        Decimal {
+          value: amount,
+          scale: divisibility,
+        },
+      }
+    }
+
+    Ok(())
+  }
+
   fn psbt_signatures(psbt: &Psbt) -> Result<Vec<Option<Signature>>> {
     psbt
       .inputs
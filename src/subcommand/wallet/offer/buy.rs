@@ -0,0 +1,259 @@
+use {super::*, bitcoin::sighash::EcdsaSighashType};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub fee: u64,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Buy {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Assert listing is for <INSCRIPTION>.")]
+  inscription: InscriptionId,
+  #[arg(long, help = "Assert listing is for <PRICE>.")]
+  price: Amount,
+  #[arg(long, help = "Buy <PSBT> listing.")]
+  psbt: String,
+}
+
+impl Buy {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let listing = base64_decode(&self.psbt).context("failed to base64 decode PSBT")?;
+
+    let listing = Psbt::deserialize(&listing).context("failed to deserialize PSBT")?;
+
+    ensure! {
+      listing.unsigned_tx.input.len() == 1 && listing.unsigned_tx.output.len() == 1,
+      "listing must have exactly one input and one output",
+    }
+
+    let seller_input = listing.unsigned_tx.input[0].clone();
+    let payment = listing.unsigned_tx.output[0].clone();
+    let outpoint = seller_input.previous_output;
+
+    ensure! {
+      !wallet.utxos().contains_key(&outpoint),
+      "listing input `{outpoint}` is owned by wallet",
+    }
+
+    let Some(seller_witness) = listing.inputs[0].final_script_witness.clone() else {
+      bail!("listing input `{outpoint}` is unsigned: listing input must be signed");
+    };
+
+    let sighash_type = seller_witness
+      .nth(0)
+      .and_then(|signature| signature.last())
+      .map(|sighash_type| u32::from(*sighash_type));
+
+    ensure! {
+      sighash_type == Some(EcdsaSighashType::SinglePlusAnyoneCanPay.to_u32()),
+      "listing input `{outpoint}` must be signed with SIGHASH_SINGLE|ANYONECANPAY",
+    }
+
+    let postage = Self::check_listing_input(&wallet, outpoint, self.inscription)?;
+
+    let price = payment.value.checked_sub(postage).ok_or_else(|| {
+      anyhow!(
+        "listing payment of {} is less than postage of {postage}",
+        payment.value
+      )
+    })?;
+
+    ensure! {
+      price == self.price,
+      "unexpected listing price of {price}",
+    }
+
+    let inscriptions = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?.unwrap_or_default();
+    let frozen = wallet.frozen_utxos()?;
+    let locked = wallet.locked_and_protected_utxos();
+
+    let Some((padding, padding_value)) = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _)| {
+        !inscriptions.contains(outpoint)
+          && !runic.contains(outpoint)
+          && !frozen.contains(outpoint)
+          && !locked.contains(outpoint)
+      })
+      .map(|(outpoint, txout)| (*outpoint, txout.value))
+      .min_by_key(|(outpoint, value)| (*value, *outpoint))
+    else {
+      bail!("wallet contains no cardinal outputs to pad listing input");
+    };
+
+    // The seller's signature commits only to its own input and the output
+    // with the same index, so the listing input must be at index 1, preceded
+    // by a padding input, for the inscription to land in the buyer's output.
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: padding,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+        seller_input.clone(),
+      ],
+      output: vec![
+        TxOut {
+          value: padding_value + postage,
+          script_pubkey: wallet.get_receive_address()?.script_pubkey(),
+        },
+        payment.clone(),
+      ],
+    };
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let input_weights = vec![fund_raw_transaction::InputWeight {
+      txid: outpoint.txid,
+      vout: outpoint.vout,
+      weight: TxIn {
+        witness: seller_witness.clone(),
+        ..seller_input.clone()
+      }
+      .segwit_weight()
+      .to_wu()
+      .try_into()
+      .unwrap(),
+    }];
+
+    let tx = fund_raw_transaction(
+      wallet.bitcoin_client(),
+      self.fee_rate,
+      &tx,
+      Some(input_weights),
+    )?;
+
+    let tx = consensus::encode::deserialize::<Transaction>(&tx)?;
+
+    ensure! {
+      tx.input.get(1) == Some(&seller_input) && tx.output.get(1) == Some(&payment),
+      "funded transaction does not preserve listing input and output",
+    }
+
+    let mut fee = postage;
+
+    for input in &tx.input {
+      if let Some(txout) = wallet.utxos().get(&input.previous_output) {
+        fee += txout.value;
+      }
+    }
+
+    for output in &tx.output {
+      fee = fee
+        .checked_sub(output.value)
+        .ok_or_else(|| anyhow!("funded transaction outputs exceed inputs"))?;
+    }
+
+    let balance_change = wallet.simulate_transaction(&tx)?;
+
+    ensure! {
+      balance_change == -(price + fee).to_signed()?,
+      "unexpected balance change of {balance_change}",
+    }
+
+    let txid = if self.dry_run {
+      tx.compute_txid()
+    } else {
+      let mut psbt = Psbt::from_unsigned_tx(tx)?;
+
+      psbt.inputs[1] = listing.inputs[0].clone();
+
+      let signed_psbt = wallet
+        .bitcoin_client()
+        .wallet_process_psbt(&base64_encode(&psbt.serialize()), Some(true), None, None)?
+        .psbt;
+
+      let signed_tx = wallet
+        .bitcoin_client()
+        .finalize_psbt(&signed_psbt, None)?
+        .hex
+        .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+
+      {
+        let signed_tx = Transaction::consensus_decode(&mut signed_tx.as_slice())
+          .context("unable to decode finalized transaction")?;
+
+        for (i, input) in signed_tx.input.iter().enumerate() {
+          let outpoint = input.previous_output;
+
+          if i == 1 {
+            ensure! {
+              input.witness == seller_witness,
+              "listing input `{outpoint}` signature changed after signing",
+            }
+          } else {
+            ensure! {
+              !input.witness.is_empty() || !input.script_sig.is_empty(),
+              "buyer input `{outpoint}` was not signed by wallet",
+            }
+          }
+        }
+      }
+
+      wallet.send_raw_transaction(&signed_tx, None)?
+    };
+
+    Ok(Some(Box::new(Output {
+      txid,
+      fee: fee.to_sat(),
+    })))
+  }
+
+  /// Checks that listing input `outpoint` is unspent, contains exactly the
+  /// `expected` inscription and no runes, and returns its value.
+  fn check_listing_input(
+    wallet: &Wallet,
+    outpoint: OutPoint,
+    expected: InscriptionId,
+  ) -> Result<Amount> {
+    let output = wallet
+      .get_output_info(&[outpoint])?
+      .into_values()
+      .next()
+      .unwrap();
+
+    ensure! {
+      !output.spent,
+      "listing input `{outpoint}` has been spent",
+    }
+
+    ensure! {
+      output.runes.unwrap_or_default().is_empty(),
+      "listing input `{outpoint}` contains runes",
+    }
+
+    let Some(inscriptions) = output.inscriptions else {
+      bail!("index must have inscription index to buy listing");
+    };
+
+    let [inscription] = inscriptions.as_slice() else {
+      bail!(
+        "listing input `{outpoint}` contains {} inscriptions",
+        inscriptions.len()
+      );
+    };
+
+    ensure! {
+      *inscription == expected,
+      "unexpected listing inscription {inscription}",
+    }
+
+    Ok(Amount::from_sat(output.value))
+  }
+}
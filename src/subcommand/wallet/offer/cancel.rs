@@ -0,0 +1,88 @@
+use super::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txid: Txid,
+  pub psbt: String,
+  pub fee: u64,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Cancel {
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Cancel <PSBT> offer.")]
+  psbt: String,
+}
+
+impl Cancel {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let psbt = base64_decode(&self.psbt).context("failed to base64 decode PSBT")?;
+
+    let psbt = Psbt::deserialize(&psbt).context("failed to deserialize PSBT")?;
+
+    let mut inputs = Vec::new();
+    let mut value = Amount::ZERO;
+
+    for (input, psbt_input) in psbt.unsigned_tx.input.iter().zip(&psbt.inputs) {
+      let outpoint = input.previous_output;
+
+      let Some(txout) = wallet.utxos().get(&outpoint) else {
+        continue;
+      };
+
+      ensure! {
+        psbt_input.final_script_sig.is_some() || psbt_input.final_script_witness.is_some(),
+        "seller input `{outpoint}` is owned by wallet: only buyers can cancel offers",
+      }
+
+      inputs.push(outpoint);
+      value += txout.value;
+    }
+
+    ensure!(
+      !inputs.is_empty(),
+      "PSBT contains no inputs owned by wallet"
+    );
+
+    let change = wallet.get_change_address()?;
+
+    let mut output = vec![TxOut {
+      script_pubkey: change.script_pubkey(),
+      value: Amount::ZERO,
+    }];
+
+    let fee = self.fee_rate.fee(TransactionBuilder::estimate_vbytes_with(
+      inputs.len(),
+      &output,
+      wallet.satisfaction_weight(),
+    ));
+
+    output[0].value = value
+      .checked_sub(fee)
+      .filter(|value| *value >= change.script_pubkey().minimal_non_dust())
+      .ok_or_else(|| anyhow!("buyer inputs do not contain enough value to pay fee"))?;
+
+    let unsigned_transaction = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output,
+    };
+
+    let (txid, psbt, fee) =
+      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None, None)?;
+
+    Ok(Some(Box::new(Output { txid, psbt, fee })))
+  }
+}
@@ -4,41 +4,122 @@ use super::*;
 pub struct Output {
   pub psbt: String,
   pub seller_address: Address<NetworkUnchecked>,
-  pub inscription: InscriptionId,
+  pub inscription: Option<InscriptionId>,
+  pub rune: Option<Outgoing>,
 }
 
 #[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("outgoing")
+    .required(true)
+    .args(&["inscription", "rune"])
+))]
 pub(crate) struct Create {
   #[arg(long, help = "<INSCRIPTION> to make offer for.")]
-  inscription: InscriptionId,
+  inscription: Option<InscriptionId>,
+  #[arg(
+    long,
+    requires = "utxo",
+    help = "<DECIMAL:RUNE> to make offer for.",
+    value_name = "DECIMAL:RUNE"
+  )]
+  rune: Option<Outgoing>,
+  #[arg(long, requires = "rune", help = "Buy runes from <UTXO>.")]
+  utxo: Option<OutPoint>,
   #[arg(long, help = "<AMOUNT> to offer.")]
   amount: Amount,
   #[arg(long, help = "<FEE_RATE> for finalized transaction.")]
   fee_rate: FeeRate,
-  #[arg(long, help = "Submit offer to <URL>.", value_name = "URL")]
+  #[arg(
+    long,
+    conflicts_with = "rune",
+    help = "Submit offer to <URL>. Only inscription offers may be submitted.",
+    value_name = "URL"
+  )]
   submit: Option<String>,
 }
 
 impl Create {
   pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let (tx, seller_address) = if let Some(inscription) = self.inscription {
+      self.create_inscription_offer(&wallet, inscription)?
+    } else if let (Some(rune), Some(utxo)) = (&self.rune, self.utxo) {
+      self.create_rune_offer(&wallet, rune, utxo)?
+    } else {
+      unreachable!()
+    };
+
+    wallet.lock_non_cardinal_outputs()?;
+
+    let tx = fund_raw_transaction(wallet.bitcoin_client(), self.fee_rate, &tx, None)?;
+
+    let tx = consensus::encode::deserialize::<Transaction>(&tx)?;
+
+    let psbt = Psbt::from_unsigned_tx(tx)?;
+
+    let result = wallet
+      .bitcoin_client()
+      .call::<String>("utxoupdatepsbt", &[base64_encode(&psbt.serialize()).into()])?;
+
+    let result = wallet
+      .bitcoin_client()
+      .wallet_process_psbt(&result, Some(true), None, None)?;
+
+    ensure! {
+      !result.complete,
+      "PSBT unexpectedly complete after processing with wallet",
+    }
+
+    if let Some(url) = &self.submit {
+      let response = reqwest::blocking::Client::new()
+        .post(url)
+        .body(result.psbt.as_bytes().to_vec())
+        .send()
+        .context("failed to submit PSBT")?;
+
+      let status = response.status();
+
+      let text = response
+        .text()
+        .context("failed to receive submission response body")?;
+
+      ensure! {
+        status == StatusCode::OK,
+        "submission failed with status code {status}: {text}",
+      }
+    }
+
+    Ok(Some(Box::new(Output {
+      psbt: result.psbt,
+      inscription: self.inscription,
+      rune: self.rune.clone(),
+      seller_address: seller_address.into_unchecked(),
+    })))
+  }
+
+  fn create_inscription_offer(
+    &self,
+    wallet: &Wallet,
+    inscription_id: InscriptionId,
+  ) -> Result<(Transaction, Address)> {
     ensure!(
-      !wallet.inscription_info().contains_key(&self.inscription),
+      !wallet.inscription_info().contains_key(&inscription_id),
       "inscription {} already in wallet",
-      self.inscription
+      inscription_id
     );
 
-    let Some(inscription) = wallet.get_inscription(self.inscription)? else {
-      bail!("inscription {} does not exist", self.inscription);
+    let Some(inscription) = wallet.get_inscription(inscription_id)? else {
+      bail!("inscription {} does not exist", inscription_id);
     };
 
     let Some(postage) = inscription.value else {
-      bail!("inscription {} unbound", self.inscription);
+      bail!("inscription {} unbound", inscription_id);
     };
 
     let Some(seller_address) = inscription.address else {
       bail!(
         "inscription {} script pubkey not valid address",
-        self.inscription,
+        inscription_id,
       );
     };
 
@@ -70,50 +151,114 @@ impl Create {
       ],
     };
 
-    wallet.lock_non_cardinal_outputs()?;
+    Ok((tx, seller_address))
+  }
 
-    let tx = fund_raw_transaction(wallet.bitcoin_client(), self.fee_rate, &tx, None)?;
+  /// Creates a transaction buying runes from `utxo`. The runestone transfers
+  /// the offered runes to the buyer's output, and any other runes in `utxo`
+  /// to the seller's output, which is also paid the value of `utxo`.
+  fn create_rune_offer(
+    &self,
+    wallet: &Wallet,
+    outgoing: &Outgoing,
+    utxo: OutPoint,
+  ) -> Result<(Transaction, Address)> {
+    let Outgoing::Rune { decimal, rune } = outgoing else {
+      bail!("`--rune` must be a rune amount, for example `1000:EXAMPLE`");
+    };
 
-    let tx = consensus::encode::deserialize::<Transaction>(&tx)?;
+    ensure!(
+      wallet.has_rune_index(),
+      "creating rune offers requires index created with `--index-runes` flag",
+    );
 
-    let psbt = Psbt::from_unsigned_tx(tx)?;
+    ensure!(
+      !wallet.utxos().contains_key(&utxo),
+      "utxo {utxo} already in wallet",
+    );
 
-    let result = wallet
-      .bitcoin_client()
-      .call::<String>("utxoupdatepsbt", &[base64_encode(&psbt.serialize()).into()])?;
+    let Some((id, entry, _parent)) = wallet.get_rune(rune.rune)? else {
+      bail!("rune {rune} has not been etched");
+    };
 
-    let result = wallet
-      .bitcoin_client()
-      .wallet_process_psbt(&result, Some(true), None, None)?;
+    let amount = decimal.to_integer(entry.divisibility)?;
 
-    ensure! {
-      !result.complete,
-      "PSBT unexpectedly complete after processing with wallet",
-    }
+    ensure!(amount > 0, "offer must be for a nonzero amount of runes");
 
-    if let Some(url) = &self.submit {
-      let response = reqwest::blocking::Client::new()
-        .post(url)
-        .body(result.psbt.as_bytes().to_vec())
-        .send()
-        .context("failed to submit PSBT")?;
+    let output = wallet
+      .get_output_info(&[utxo])?
+      .into_values()
+      .next()
+      .unwrap();
 
-      let status = response.status();
+    ensure!(!output.spent, "utxo {utxo} has been spent");
 
-      let text = response
-        .text()
-        .context("failed to receive submission response body")?;
+    ensure!(
+      output.inscriptions.unwrap_or_default().is_empty(),
+      "utxo {utxo} contains inscriptions",
+    );
 
-      ensure! {
-        status == StatusCode::OK,
-        "submission failed with status code {status}: {text}",
-      }
-    }
+    let balance = output
+      .runes
+      .unwrap_or_default()
+      .get(rune)
+      .map(|pile| pile.amount)
+      .unwrap_or_default();
 
-    Ok(Some(Box::new(Output {
-      psbt: result.psbt,
-      inscription: self.inscription,
-      seller_address: seller_address.into_unchecked(),
-    })))
+    ensure!(
+      balance >= amount,
+      "utxo {utxo} contains {} {rune}, but offer is for {}",
+      Decimal {
+        value: balance,
+        scale: entry.divisibility,
+      },
+      Decimal {
+        value: amount,
+        scale: entry.divisibility,
+      },
+    );
+
+    let Some(seller_address) = output.address else {
+      bail!("utxo {utxo} script pubkey not valid address");
+    };
+
+    let seller_address = seller_address.require_network(wallet.chain().network())?;
+
+    let runestone = Runestone {
+      edicts: vec![Edict {
+        id,
+        amount,
+        output: 1,
+      }],
+      pointer: Some(2),
+      ..default()
+    };
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: utxo,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![
+        TxOut {
+          value: Amount::ZERO,
+          script_pubkey: runestone.encipher(),
+        },
+        TxOut {
+          value: TARGET_POSTAGE,
+          script_pubkey: wallet.get_change_address()?.into(),
+        },
+        TxOut {
+          value: self.amount + Amount::from_sat(output.value),
+          script_pubkey: seller_address.clone().into(),
+        },
+      ],
+    };
+
+    Ok((tx, seller_address))
   }
 }
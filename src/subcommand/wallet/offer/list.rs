@@ -0,0 +1,92 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub amount: u64,
+  pub inscription: InscriptionId,
+  pub psbt: String,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct List {}
+
+impl List {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let mut offers = Vec::new();
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>()
+      .into_iter()
+      .collect::<Vec<OutPoint>>();
+
+    for offer in wallet.get_offers_for_outputs(&inscribed_outputs)? {
+      let psbt = base64_decode(&offer).context("failed to base64 decode PSBT")?;
+
+      let psbt = Psbt::deserialize(&psbt).context("failed to deserialize PSBT")?;
+
+      let outgoing = psbt
+        .unsigned_tx
+        .input
+        .iter()
+        .map(|input| input.previous_output)
+        .filter(|outpoint| wallet.utxos().contains_key(outpoint))
+        .collect::<Vec<OutPoint>>();
+
+      if outgoing.is_empty() {
+        continue;
+      }
+
+      // offers which don't pay the wallet, such as the wallet's own offers,
+      // are not offers to buy from the wallet
+      let Ok(amount) = wallet
+        .simulate_transaction(&psbt.unsigned_tx)?
+        .to_unsigned()
+      else {
+        continue;
+      };
+
+      let txid = psbt.unsigned_tx.compute_txid();
+
+      let [outgoing] = outgoing.as_slice() else {
+        eprintln!(
+          "warning: skipping offer {txid}: offer spends {} wallet outputs",
+          outgoing.len(),
+        );
+        continue;
+      };
+
+      if let Some(runes) = wallet.get_runes_balances_in_output(outgoing)?
+        && !runes.is_empty()
+      {
+        eprintln!("warning: skipping offer {txid}: seller input {outgoing} contains runes");
+        continue;
+      }
+
+      let inscriptions = wallet
+        .inscriptions()
+        .iter()
+        .filter(|(satpoint, _)| satpoint.outpoint == *outgoing)
+        .flat_map(|(_, inscriptions)| inscriptions)
+        .collect::<Vec<&InscriptionId>>();
+
+      let [inscription] = inscriptions.as_slice() else {
+        eprintln!(
+          "warning: skipping offer {txid}: seller input {outgoing} contains {} inscriptions",
+          inscriptions.len(),
+        );
+        continue;
+      };
+
+      offers.push(Output {
+        amount: amount.to_sat(),
+        inscription: **inscription,
+        psbt: offer,
+      });
+    }
+
+    Ok(Some(Box::new(offers)))
+  }
+}
@@ -0,0 +1,93 @@
+use {super::*, bitcoin::sighash::EcdsaSighashType, bitcoincore_rpc::json::SigHashType};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub inscription: InscriptionId,
+  pub price: u64,
+  pub psbt: String,
+  pub seller_address: Address<NetworkUnchecked>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct ListForSale {
+  #[arg(long, help = "List <INSCRIPTION> for sale.")]
+  inscription: InscriptionId,
+  #[arg(long, help = "Sell inscription for <PRICE>.")]
+  price: Amount,
+}
+
+impl ListForSale {
+  pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
+    let Some(inscription) = wallet.inscription_info().remove(&self.inscription) else {
+      bail!("inscription {} not in wallet", self.inscription);
+    };
+
+    let outpoint = inscription.satpoint.outpoint;
+
+    ensure! {
+      !wallet.frozen_utxos()?.contains(&outpoint),
+      "inscription {} is in frozen output {}", self.inscription, outpoint,
+    }
+
+    let Some(inscriptions) = wallet.get_inscriptions_in_output(&outpoint)? else {
+      bail!("index must have inscription index to list inscriptions for sale");
+    };
+
+    ensure! {
+      inscriptions.len() == 1,
+      "output {} contains {} inscriptions", outpoint, inscriptions.len(),
+    }
+
+    if let Some(runes) = wallet.get_runes_balances_in_output(&outpoint)? {
+      ensure! {
+        runes.is_empty(),
+        "output {} contains runes", outpoint,
+      }
+    }
+
+    let postage = wallet.utxos()[&outpoint].value;
+
+    let seller_address = wallet.get_change_address()?;
+
+    let tx = Transaction {
+      version: Version(2),
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: self.price + postage,
+        script_pubkey: seller_address.script_pubkey(),
+      }],
+    };
+
+    let psbt = wallet
+      .bitcoin_client()
+      .wallet_process_psbt(
+        &base64_encode(&Psbt::from_unsigned_tx(tx)?.serialize()),
+        Some(true),
+        Some(SigHashType::from(EcdsaSighashType::SinglePlusAnyoneCanPay)),
+        None,
+      )?
+      .psbt;
+
+    {
+      let psbt = Psbt::deserialize(&base64_decode(&psbt)?)?;
+
+      ensure! {
+        psbt.inputs[0].final_script_sig.is_some() || psbt.inputs[0].final_script_witness.is_some(),
+        "failed to sign seller input {outpoint}",
+      }
+    }
+
+    Ok(Some(Box::new(Output {
+      inscription: self.inscription,
+      price: self.price.to_sat(),
+      psbt,
+      seller_address: seller_address.into_unchecked(),
+    })))
+  }
+}
@@ -1,7 +1,7 @@
 use super::*;
 
 #[derive(Debug, Clone)]
-pub(crate) struct Timestamp(bitcoincore_rpc::json::Timestamp);
+pub(crate) struct Timestamp(pub(crate) bitcoincore_rpc::json::Timestamp);
 
 impl FromStr for Timestamp {
   type Err = Error;
@@ -6,6 +6,14 @@ pub(crate) struct Send {
   pub(crate) dry_run: bool,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
   fee_rate: FeeRate,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write unsigned PSBT to <FILE> instead of signing and broadcasting transaction. Sign \
+    it externally and broadcast it with `ord wallet finalize`",
+    value_name = "FILE"
+  )]
+  pub(crate) psbt_out: Option<PathBuf>,
   #[arg(
     long,
     help = "Target <AMOUNT> postage with sent inscriptions. [default: 10000 sat]",
@@ -76,8 +84,12 @@ impl Send {
       )?,
     };
 
-    let (txid, psbt, fee) =
-      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+    let (txid, psbt, fee) = wallet.sign_and_broadcast_transaction(
+      unsigned_transaction,
+      self.dry_run,
+      self.psbt_out.as_deref(),
+      None,
+    )?;
 
     Ok(Some(Box::new(Output {
       txid,
@@ -27,4 +27,13 @@ pub(super) struct SharedArgs {
     the implications."
   )]
   pub(crate) no_limit: bool,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write unsigned commit PSBT to <FILE> instead of signing and broadcasting transactions. \
+    Sign it externally and broadcast it with `ord wallet finalize`. The reveal PSBT is included in \
+    the output.",
+    value_name = "FILE"
+  )]
+  pub(crate) psbt_out: Option<PathBuf>,
 }
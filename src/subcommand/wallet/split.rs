@@ -65,6 +65,14 @@ pub(crate) struct Split {
   pub(crate) dry_run: bool,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
   fee_rate: FeeRate,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write unsigned PSBT to <FILE> instead of signing and broadcasting transaction. Sign \
+    it externally and broadcast it with `ord wallet finalize`",
+    value_name = "FILE"
+  )]
+  pub(crate) psbt_out: Option<PathBuf>,
   #[arg(
     long,
     help = "Include <AMOUNT> postage with change output. [default: 10000 sat]",
@@ -111,11 +119,13 @@ impl Split {
       .map(|satpoint| satpoint.outpoint)
       .collect::<HashSet<OutPoint>>();
 
+    let frozen_utxos = wallet.frozen_utxos()?;
+
     let balances = wallet
       .get_runic_outputs()?
       .unwrap_or_default()
       .into_iter()
-      .filter(|output| !inscribed_outputs.contains(output))
+      .filter(|output| !inscribed_outputs.contains(output) && !frozen_utxos.contains(output))
       .map(|output| {
         wallet.get_runes_balances_in_output(&output).map(|balance| {
           (
@@ -147,8 +157,12 @@ impl Split {
 
     let unsigned_transaction = consensus::encode::deserialize(&unsigned_transaction)?;
 
-    let (txid, psbt, fee) =
-      wallet.sign_and_broadcast_transaction(unsigned_transaction, self.dry_run, None)?;
+    let (txid, psbt, fee) = wallet.sign_and_broadcast_transaction(
+      unsigned_transaction,
+      self.dry_run,
+      self.psbt_out.as_deref(),
+      None,
+    )?;
 
     Ok(Some(Box::new(Output { txid, psbt, fee })))
   }
@@ -26,6 +26,14 @@ pub(crate) struct Sweep {
   dry_run: bool,
   #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
   fee_rate: FeeRate,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Write PSBT to <FILE> instead of signing wallet inputs and broadcasting transaction. \
+    Swept inputs are signed. Sign it externally and broadcast it with `ord wallet finalize`",
+    value_name = "FILE"
+  )]
+  psbt_out: Option<PathBuf>,
 }
 
 impl Sweep {
@@ -199,6 +207,16 @@ impl Sweep {
         witness.push(compressed_public_key.to_bytes());
       }
 
+      if let Some(path) = &self.psbt_out {
+        Wallet::write_psbt(&wallet.unsigned_psbt(&tx)?, path)?;
+
+        return Ok(Some(Box::new(Output {
+          address: address.into_unchecked(),
+          outputs: utxos.iter().map(|utxo| utxo.outpoint).collect(),
+          txid: tx.compute_txid(),
+        })));
+      }
+
       let result = wallet
         .bitcoin_client()
         .sign_raw_transaction_with_wallet(&tx, None, None)
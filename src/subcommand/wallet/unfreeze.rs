@@ -0,0 +1,23 @@
+use {super::*, crate::wallet::frozen::Frozen, freeze::Output};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Unfreeze {
+  #[arg(
+    required = true,
+    value_name = "OUTPOINT|INSCRIPTION|RUNE",
+    help = "Unfreeze <OUTPOINT>, <INSCRIPTION>, or <RUNE>."
+  )]
+  items: Vec<Frozen>,
+}
+
+impl Unfreeze {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    for item in self.items {
+      ensure!(wallet.unfreeze(item)?, "{item} is not frozen");
+    }
+
+    Ok(Some(Box::new(Output {
+      frozen: wallet.frozen()?,
+    })))
+  }
+}
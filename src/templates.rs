@@ -16,6 +16,8 @@ pub(crate) use {
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
+  metaprotocol::MetaprotocolHtml,
+  metaprotocols::MetaprotocolsHtml,
   output::OutputHtml,
   parents::ParentsHtml,
   preview::{
@@ -26,11 +28,13 @@ pub(crate) use {
   rune_not_found::RuneNotFoundHtml,
   sat::SatHtml,
   satscard::SatscardHtml,
+  search::SearchHtml,
 };
 
 pub use {
   blocks::BlocksHtml, inscription::InscriptionHtml, item::ItemHtml, rune::RuneHtml,
-  runes::RunesHtml, status::StatusHtml, transaction::TransactionHtml,
+  runes::RunesHtml, runes_calendar::RunesCalendarHtml, status::StatusHtml,
+  transaction::TransactionHtml,
 };
 
 pub mod address;
@@ -50,6 +54,8 @@ pub mod inscriptions;
 mod inscriptions_block;
 mod item;
 mod metadata;
+mod metaprotocol;
+mod metaprotocols;
 pub mod output;
 mod parents;
 mod preview;
@@ -57,8 +63,10 @@ mod rare;
 pub mod rune;
 pub mod rune_not_found;
 pub mod runes;
+pub mod runes_calendar;
 pub mod sat;
 mod satscard;
+mod search;
 pub mod status;
 pub mod transaction;
 
@@ -12,6 +12,7 @@ pub struct InscriptionHtml {
   pub inscription: Inscription,
   pub next: Option<InscriptionId>,
   pub number: i32,
+  pub offers: Vec<u64>,
   pub output: Option<TxOut>,
   pub parents: Vec<InscriptionId>,
   pub previous: Option<InscriptionId>,
@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct MetaprotocolHtml {
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) metaprotocol: String,
+  pub(crate) next: Option<usize>,
+  pub(crate) prev: Option<usize>,
+}
+
+impl PageContent for MetaprotocolHtml {
+  fn title(&self) -> String {
+    format!("Metaprotocol {}", self.metaprotocol)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_prev_and_next() {
+    assert_regex_match!(
+      MetaprotocolHtml {
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        metaprotocol: "brc-20".into(),
+        next: None,
+        prev: None,
+      },
+      "
+        <h1>Metaprotocol brc-20</h1>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        .*
+        prev
+        next
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      MetaprotocolHtml {
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        metaprotocol: "foo bar".into(),
+        next: Some(2),
+        prev: Some(0),
+      },
+      "
+        <h1>Metaprotocol foo bar</h1>
+        .*
+        <a class=prev href=/metaprotocol/foo%20bar/0>prev</a>
+        <a class=next href=/metaprotocol/foo%20bar/2>next</a>
+        .*
+      "
+      .unindent()
+    );
+  }
+}
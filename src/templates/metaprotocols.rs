@@ -0,0 +1,44 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct MetaprotocolsHtml {
+  pub(crate) metaprotocols: Vec<(String, u64)>,
+}
+
+impl PageContent for MetaprotocolsHtml {
+  fn title(&self) -> String {
+    "Metaprotocols".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      MetaprotocolsHtml {
+        metaprotocols: vec![("brc-20".into(), 2), ("foo bar".into(), 1)],
+      },
+      "
+        <h1>Metaprotocols</h1>
+        <table>
+          <tr>
+            <th>metaprotocol</th>
+            <th>inscriptions</th>
+          </tr>
+          <tr>
+            <td><a href=/metaprotocol/brc-20>brc-20</a></td>
+            <td>2</td>
+          </tr>
+          <tr>
+            <td><a href=/metaprotocol/foo%20bar>foo bar</a></td>
+            <td>1</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+}
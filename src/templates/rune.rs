@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneHtml {
   pub entry: RuneEntry,
+  pub holders: Option<usize>,
   pub id: RuneId,
   pub mintable: bool,
   pub parent: Option<InscriptionId>,
@@ -66,6 +67,7 @@ mod tests {
           timestamp: 0,
           turbo: true,
         },
+        holders: None,
         id: RuneId { block: 10, tx: 9 },
         mintable: true,
         parent: Some(InscriptionId {
@@ -151,6 +153,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: None,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -185,6 +188,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: None,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -224,6 +228,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: None,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
@@ -280,6 +285,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: None,
         id: RuneId { block: 0, tx: 0 },
         mintable: false,
         parent: Some(InscriptionId {
@@ -317,6 +323,7 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: None,
         id: RuneId { block: 0, tx: 0 },
         mintable: true,
         parent: Some(InscriptionId {
@@ -0,0 +1,100 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunesCalendarHtml {
+  pub height: Option<u32>,
+  pub intervals: Vec<api::RuneUnlockInterval>,
+  pub minimum: Rune,
+  pub reserved: u64,
+  pub unrestricted: u64,
+}
+
+impl RunesCalendarHtml {
+  fn suffix(&self, height: u32) -> &'static str {
+    if self.height.is_some_and(|current| height <= current) {
+      ""
+    } else {
+      " (expected)"
+    }
+  }
+}
+
+impl PageContent for RunesCalendarHtml {
+  fn title(&self) -> String {
+    "Rune Calendar".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RunesCalendarHtml {
+        height: Some(17499),
+        intervals: vec![
+          api::RuneUnlockInterval {
+            end: 17499,
+            end_time: DateTime::default(),
+            etched: 2,
+            length: 12,
+            minimum: Rune(3817158266467286),
+            names: 95428956661682176,
+            start: 0,
+            start_time: DateTime::default(),
+          },
+          api::RuneUnlockInterval {
+            end: 34999,
+            end_time: DateTime::default(),
+            etched: 0,
+            length: 11,
+            minimum: Rune(146813779479510),
+            names: 3670344486987776,
+            start: 17500,
+            start_time: DateTime::default(),
+          },
+        ],
+        minimum: Rune(146813779479510),
+        reserved: 3,
+        unrestricted: 4,
+      },
+      "
+        <h1>Rune Calendar</h1>
+        <dl>
+          <dt>minimum rune for next block</dt>
+          <dd>AAAAAAAAAAA</dd>
+          <dt>etched runes with 13 or more letters</dt>
+          <dd>4</dd>
+          <dt>etched reserved runes</dt>
+          <dd>3</dd>
+        </dl>
+        <table>
+          <tr>
+            <th>letters</th>
+            <th>first unlock</th>
+            <th>all unlocked</th>
+            <th>minimum</th>
+            <th>etched</th>
+          </tr>
+          <tr>
+            <td>12</td>
+            <td>0 <time>1970-01-01 00:00:00 UTC</time></td>
+            <td>17499 <time>1970-01-01 00:00:00 UTC</time></td>
+            <td>AAAAAAAAAAAA</td>
+            <td>2 of 95428956661682176</td>
+          </tr>
+          <tr>
+            <td>11</td>
+            <td>17500 <time>1970-01-01 00:00:00 UTC</time> \\(expected\\)</td>
+            <td>34999 <time>1970-01-01 00:00:00 UTC</time> \\(expected\\)</td>
+            <td>AAAAAAAAAAA</td>
+            <td>0 of 3670344486987776</td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct SearchHtml {
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) next: Option<u32>,
+  pub(crate) query: String,
+}
+
+impl PageContent for SearchHtml {
+  fn title(&self) -> String {
+    format!("Search: {}", self.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn without_results() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: Vec::new(),
+        next: None,
+        query: "foo".into(),
+      },
+      "
+        <h1>Search Results</h1>
+        <form action=/search method=get>
+          <input type=text name=q value=\"foo\">
+          <input type=submit value=Search>
+        </form>
+        <p>No inscriptions found.</p>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_more() {
+    assert_regex_match!(
+      SearchHtml {
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        next: Some(2),
+        query: "foo bar".into(),
+      },
+      "
+        <h1>Search Results</h1>
+        .*
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+        <a class=next href=\"/search\\?q=foo%20bar&amp;before=2\">more</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+}
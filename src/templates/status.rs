@@ -13,6 +13,7 @@ pub struct StatusHtml {
   pub json_api: bool,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
+  pub rune_history_index: bool,
   pub rune_index: bool,
   pub runes: u64,
   pub sat_index: bool,
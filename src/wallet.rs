@@ -2,19 +2,24 @@ use {
   super::*,
   batch::ParentInfo,
   bitcoin::{
-    bip32::{ChildNumber, DerivationPath, Xpriv},
+    NetworkKind,
+    bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
     secp256k1::Secp256k1,
   },
-  bitcoincore_rpc::json::ImportDescriptors,
+  bitcoincore_rpc::json::{GetTransactionResult, ImportDescriptors},
   entry::{EtchingEntry, EtchingEntryValue},
   fee_rate::FeeRate,
+  frozen::Frozen,
   index::entry::Entry,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
-  miniscript::descriptor::{DescriptorSecretKey, DescriptorXKey, KeyMap, Wildcard},
+  miniscript::descriptor::{
+    DescriptorPublicKey, DescriptorSecretKey, DescriptorXKey, KeyMap, Wildcard,
+  },
+  psbt_assets::PsbtAssets,
   redb::{
     Database, DatabaseError, ReadableDatabase, ReadableTable, RepairSession, StorageError,
-    TableDefinition,
+    TableDefinition, TableError,
   },
   std::sync::Once,
   transaction_builder::TransactionBuilder,
@@ -22,11 +27,20 @@ use {
 
 pub mod batch;
 pub mod entry;
+pub mod frozen;
+pub mod protection;
+pub mod psbt_assets;
 pub mod transaction_builder;
 pub mod wallet_constructor;
 
 const SCHEMA_VERSION: u64 = 1;
 
+/// BIP 341's provably unspendable NUMS point `H`, used as the internal key of
+/// taproot multisig descriptors.
+const UNSPENDABLE_INTERNAL_KEY: &str =
+  "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0";
+
+define_table! { FROZEN, &str, () }
 define_table! { RUNE_TO_ETCHING, u128, EtchingEntryValue }
 define_table! { STATISTICS, u64, u64 }
 
@@ -75,6 +89,7 @@ pub(crate) enum Maturity {
 pub(crate) struct Wallet {
   bitcoin_client: Client,
   database: Database,
+  has_rune_history_index: bool,
   has_rune_index: bool,
   has_sat_index: bool,
   rpc_url: Url,
@@ -84,7 +99,10 @@ pub(crate) struct Wallet {
   output_info: BTreeMap<OutPoint, api::Output>,
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
   locked_utxos: BTreeMap<OutPoint, TxOut>,
+  protected_utxos: BTreeSet<OutPoint>,
+  satisfaction_weight: Option<Weight>,
   settings: Settings,
+  watch_only: bool,
 }
 
 impl Wallet {
@@ -163,6 +181,96 @@ impl Wallet {
     &self.locked_utxos
   }
 
+  /// Returns wallet outputs containing sats protected by `--protect-rarity`
+  /// or `--protect-charm`, which are never spent as cardinals.
+  pub(crate) fn protected_utxos(&self) -> &BTreeSet<OutPoint> {
+    &self.protected_utxos
+  }
+
+  /// Returns wallet outputs that may not be spent as cardinals, because they
+  /// are locked or contain protected sats.
+  pub(crate) fn locked_and_protected_utxos(&self) -> BTreeSet<OutPoint> {
+    self
+      .locked_utxos
+      .keys()
+      .chain(&self.protected_utxos)
+      .cloned()
+      .collect()
+  }
+
+  /// Replaces the outputs spent by `transaction` with the outputs it creates,
+  /// since the wallet's view of its outputs is only updated when it is
+  /// reloaded. Outputs whose index is in `protected` are protected.
+  pub(crate) fn apply_transaction(&mut self, transaction: &Transaction, protected: &[usize]) {
+    let txid = transaction.compute_txid();
+
+    let mut sat_ranges = Vec::new();
+    let mut inscriptions = None;
+    let mut runes = None;
+
+    for input in &transaction.input {
+      self.utxos.remove(&input.previous_output);
+      self.locked_utxos.remove(&input.previous_output);
+      self.protected_utxos.remove(&input.previous_output);
+
+      if let Some(info) = self.output_info.remove(&input.previous_output) {
+        sat_ranges.extend(info.sat_ranges.unwrap_or_default());
+        inscriptions = inscriptions.or(info.inscriptions.map(|_| Vec::new()));
+        runes = runes.or(info.runes.map(|_| BTreeMap::new()));
+      }
+    }
+
+    let mut sat_ranges = sat_ranges.into_iter();
+    let mut remainder = None;
+
+    for (vout, tx_out) in transaction.output.iter().enumerate() {
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      let mut ranges = Vec::new();
+      let mut value = tx_out.value.to_sat();
+
+      while value > 0 {
+        let Some((start, end)) = remainder.take().or_else(|| sat_ranges.next()) else {
+          break;
+        };
+
+        let size = (end - start).min(value);
+
+        ranges.push((start, start + size));
+
+        if start + size < end {
+          remainder = Some((start + size, end));
+        }
+
+        value -= size;
+      }
+
+      self.utxos.insert(outpoint, tx_out.clone());
+
+      if protected.contains(&vout) {
+        self.protected_utxos.insert(outpoint);
+      }
+
+      self.output_info.insert(
+        outpoint,
+        api::Output::new(
+          self.chain(),
+          0,
+          inscriptions.clone(),
+          outpoint,
+          tx_out.clone(),
+          false,
+          runes.clone(),
+          Some(ranges),
+          false,
+        ),
+      );
+    }
+  }
+
   pub(crate) fn lock_non_cardinal_outputs(&self) -> Result {
     let inscriptions = self
       .inscriptions()
@@ -181,8 +289,12 @@ impl Wallet {
       .keys()
       .filter(|utxo| inscriptions.contains(utxo))
       .chain(self.get_runic_outputs()?.unwrap_or_default().iter())
+      .chain(self.frozen_utxos()?.iter())
+      .chain(self.protected_utxos().iter())
       .cloned()
       .filter(|utxo| !locked.contains(utxo))
+      .collect::<BTreeSet<OutPoint>>()
+      .into_iter()
       .collect::<Vec<OutPoint>>();
 
     if !self.bitcoin_client().lock_unspent(&outputs)? {
@@ -218,6 +330,32 @@ impl Wallet {
     Ok(inscription)
   }
 
+  pub(crate) fn get_inscriptions(
+    &self,
+    inscription_ids: &[InscriptionId],
+  ) -> Result<Vec<api::Inscription>> {
+    if inscription_ids.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let response = self
+      .ord_client
+      .post(self.rpc_url.join("/inscriptions").unwrap())
+      .json(&inscription_ids)
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!(
+        "failed to get inscriptions: {} {}",
+        response.status(),
+        response.text()?,
+      );
+    }
+
+    Ok(response.json()?)
+  }
+
   pub(crate) fn inscription_exists(&self, inscription_id: InscriptionId) -> Result<bool> {
     Ok(
       !self
@@ -274,6 +412,46 @@ impl Wallet {
     )
   }
 
+  pub(crate) fn get_offers_for_outputs(&self, outputs: &[OutPoint]) -> Result<Vec<String>> {
+    let response = self
+      .ord_client
+      .post(self.rpc_url.join("/offers").unwrap())
+      .json(&outputs)
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("wallet failed get offers: {}", response.text()?);
+    }
+
+    Ok(response.json::<api::Offers>()?.offers)
+  }
+
+  pub(crate) fn get_output_info(
+    &self,
+    outputs: &[OutPoint],
+  ) -> Result<BTreeMap<OutPoint, api::Output>> {
+    let response = self
+      .ord_client
+      .post(self.rpc_url.join("/outputs").unwrap())
+      .json(&outputs)
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("wallet failed get outputs: {}", response.text()?);
+    }
+
+    let response_outputs = response.json::<Vec<api::Output>>()?;
+
+    ensure! {
+      response_outputs.len() == outputs.len(),
+      "unexpected server `/outputs` response length",
+    }
+
+    Ok(outputs.iter().copied().zip(response_outputs).collect())
+  }
+
   pub(crate) fn get_parent_info(&self, parents: &[InscriptionId]) -> Result<Vec<ParentInfo>> {
     let mut parent_info = Vec::new();
     for parent_id in parents {
@@ -336,15 +514,24 @@ impl Wallet {
   pub(crate) fn get_rune(
     &self,
     rune: Rune,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(&SpacedRune { rune, spacers: 0 }.to_string())
+  }
+
+  pub(crate) fn get_rune_by_id(
+    &self,
+    id: RuneId,
+  ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
+    self.get_rune_by_query(&id.to_string())
+  }
+
+  fn get_rune_by_query(
+    &self,
+    query: &str,
   ) -> Result<Option<(RuneId, RuneEntry, Option<InscriptionId>)>> {
     let response = self
       .ord_client
-      .get(
-        self
-          .rpc_url
-          .join(&format!("/rune/{}", SpacedRune { rune, spacers: 0 }))
-          .unwrap(),
-      )
+      .get(self.rpc_url.join(&format!("/rune/{query}")).unwrap())
       .send()?;
 
     if response.status() == StatusCode::NOT_FOUND {
@@ -358,6 +545,15 @@ impl Wallet {
     Ok(Some((rune_json.id, rune_json.entry, rune_json.parent)))
   }
 
+  /// Returns wallet transaction `txid`, or `None` if `txid` is not a wallet
+  /// transaction.
+  pub(crate) fn get_wallet_transaction(&self, txid: Txid) -> Result<Option<GetTransactionResult>> {
+    self
+      .bitcoin_client()
+      .get_transaction(&txid, Some(false))
+      .into_option()
+  }
+
   pub(crate) fn get_change_address(&self) -> Result<Address> {
     Ok(
       self
@@ -386,6 +582,10 @@ impl Wallet {
     self.has_rune_index
   }
 
+  pub(crate) fn has_rune_history_index(&self) -> bool {
+    self.has_rune_history_index
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     self.settings.chain()
   }
@@ -394,6 +594,35 @@ impl Wallet {
     self.settings.integration_test()
   }
 
+  pub(crate) fn watch_only(&self) -> bool {
+    self.watch_only
+  }
+
+  pub(crate) fn satisfaction_weight(&self) -> Weight {
+    self
+      .satisfaction_weight
+      .unwrap_or(TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT)
+  }
+
+  /// Returns the weight of the largest witness that spends an output of one
+  /// of `descriptors`, if any of them are spent other than by a single
+  /// Schnorr signature, as is the case for multisig wallets.
+  pub(crate) fn descriptor_satisfaction_weight(descriptors: &[Descriptor]) -> Option<Weight> {
+    descriptors
+      .iter()
+      .filter_map(|descriptor| {
+        descriptor
+          .desc
+          .parse::<miniscript::Descriptor<DescriptorPublicKey>>()
+          .ok()
+      })
+      .filter(|descriptor| {
+        !matches!(descriptor, miniscript::Descriptor::Tr(tr) if tr.tap_tree().is_none())
+      })
+      .filter_map(|descriptor| descriptor.max_weight_to_satisfy().ok())
+      .max()
+  }
+
   fn is_above_minimum_at_height(&self, rune: Rune) -> Result<bool> {
     Ok(
       rune
@@ -464,11 +693,9 @@ impl Wallet {
           progress.finish_with_message("Rune matured, submitting...");
           break;
         }
-        Maturity::ConfirmationsPending(remaining) => {
-          if remaining < pending_confirmations {
-            pending_confirmations = remaining;
-            progress.inc(1);
-          }
+        Maturity::ConfirmationsPending(remaining) if remaining < pending_confirmations => {
+          pending_confirmations = remaining;
+          progress.inc(1);
         }
         Maturity::CommitSpent(txid) => {
           self.clear_etching(rune)?;
@@ -644,6 +871,228 @@ impl Wallet {
     }
   }
 
+  pub(crate) fn initialize_watch_only(
+    name: String,
+    settings: &Settings,
+    key: &str,
+    timestamp: bitcoincore_rpc::json::Timestamp,
+  ) -> Result<Vec<String>> {
+    let descriptors = Self::watch_only_descriptors(key, settings.chain().network())?;
+
+    Self::import_watch_only(name, settings, &descriptors, timestamp)?;
+
+    Ok(descriptors)
+  }
+
+  pub(crate) fn initialize_multisig(
+    name: String,
+    settings: &Settings,
+    threshold: usize,
+    cosigners: &[String],
+    p2wsh: bool,
+    timestamp: bitcoincore_rpc::json::Timestamp,
+  ) -> Result<Vec<String>> {
+    let descriptors =
+      Self::multisig_descriptors(threshold, cosigners, p2wsh, settings.chain().network())?;
+
+    Self::import_watch_only(name, settings, &descriptors, timestamp)?;
+
+    Ok(descriptors)
+  }
+
+  fn import_watch_only(
+    name: String,
+    settings: &Settings,
+    descriptors: &[String],
+    timestamp: bitcoincore_rpc::json::Timestamp,
+  ) -> Result {
+    Self::check_version(settings.bitcoin_rpc_client(None)?)?.create_wallet(
+      &name,
+      Some(true),
+      Some(true),
+      None,
+      None,
+    )?;
+
+    settings
+      .bitcoin_rpc_client(Some(name.clone()))?
+      .call::<serde_json::Value>(
+        "importdescriptors",
+        &[serde_json::to_value(
+          descriptors
+            .iter()
+            .zip([false, true])
+            .map(|(descriptor, change)| ImportDescriptors {
+              descriptor: descriptor.clone(),
+              timestamp,
+              active: Some(true),
+              range: None,
+              next_index: None,
+              internal: Some(change),
+              label: None,
+            })
+            .collect::<Vec<ImportDescriptors>>(),
+        )?],
+      )
+      .with_context(|| format!("Failed to import descriptors for wallet {name}"))?;
+
+    Ok(())
+  }
+
+  /// Returns receive and change descriptors for an m-of-n multisig wallet.
+  /// Each cosigner key is an extended public key, optionally with key origin
+  /// information and a derivation path, to which `/0/*` and `/1/*` are
+  /// appended. Taproot descriptors use an unspendable internal key, so that
+  /// outputs can only be spent with `threshold` signatures.
+  fn multisig_descriptors(
+    threshold: usize,
+    cosigners: &[String],
+    p2wsh: bool,
+    network: Network,
+  ) -> Result<Vec<String>> {
+    ensure!(
+      threshold >= 1 && threshold <= cosigners.len(),
+      "multisig threshold must be between 1 and the number of cosigners, {}",
+      cosigners.len(),
+    );
+
+    let mut keys = cosigners
+      .iter()
+      .map(|cosigner| {
+        let DescriptorPublicKey::XPub(xkey) = cosigner
+          .parse::<DescriptorPublicKey>()
+          .with_context(|| format!("`{cosigner}` is not an extended public key"))?
+        else {
+          bail!("cosigner `{cosigner}` must be an extended public key");
+        };
+
+        ensure!(
+          xkey.wildcard == Wildcard::None,
+          "cosigner `{cosigner}` must not end in a wildcard",
+        );
+
+        ensure!(
+          xkey.xkey.network == NetworkKind::from(network),
+          "extended public key is not valid for {network}",
+        );
+
+        Ok(xkey)
+      })
+      .collect::<Result<Vec<DescriptorXKey<Xpub>>>>()?;
+
+    // `multi_a` keys are ordered by extended public key, so that every
+    // cosigner derives the same descriptor regardless of argument order.
+    // `sortedmulti_a`, which would sort the derived keys instead, is not
+    // supported by miniscript.
+    keys.sort_by_key(|xkey| xkey.xkey.encode());
+
+    for window in keys.windows(2) {
+      ensure!(
+        window[0].xkey != window[1].xkey,
+        "duplicate cosigner {}",
+        window[0].xkey,
+      );
+    }
+
+    [false, true]
+      .into_iter()
+      .map(|change| {
+        let keys = keys
+          .iter()
+          .map(|xkey| {
+            DescriptorPublicKey::XPub(DescriptorXKey {
+              origin: xkey.origin.clone(),
+              xkey: xkey.xkey,
+              derivation_path: xkey.derivation_path.child(ChildNumber::Normal {
+                index: change.into(),
+              }),
+              wildcard: Wildcard::Unhardened,
+            })
+            .to_string()
+          })
+          .collect::<Vec<String>>()
+          .join(",");
+
+        let descriptor = if p2wsh {
+          format!("wsh(sortedmulti({threshold},{keys}))")
+        } else {
+          format!("tr({UNSPENDABLE_INTERNAL_KEY},multi_a({threshold},{keys}))")
+        };
+
+        let descriptor = descriptor.parse::<miniscript::Descriptor<DescriptorPublicKey>>()?;
+
+        Ok(descriptor.to_string())
+      })
+      .collect()
+  }
+
+  /// Returns receive and change descriptors for a watch-only wallet. `key` may
+  /// be a bare extended public key, in which case BIP86 `/0/*` and `/1/*`
+  /// paths are derived from it, or a taproot descriptor whose internal key
+  /// ends in `/0/*` or `/<0;1>/*`.
+  fn watch_only_descriptors(key: &str, network: Network) -> Result<Vec<String>> {
+    let (origin, xpub, derivation_path) = match key.parse::<Xpub>() {
+      Ok(xpub) => (None, xpub, DerivationPath::master()),
+      Err(_) => {
+        let descriptor = key
+          .parse::<miniscript::Descriptor<DescriptorPublicKey>>()
+          .with_context(|| format!("`{key}` is neither an extended public key nor a descriptor"))?;
+
+        let descriptor = if descriptor.is_multipath() {
+          descriptor.into_single_descriptors()?.remove(0)
+        } else {
+          descriptor
+        };
+
+        let miniscript::Descriptor::Tr(tr) = &descriptor else {
+          bail!("watch-only descriptor must be a taproot `tr()` descriptor");
+        };
+
+        let DescriptorPublicKey::XPub(xkey) = tr.internal_key() else {
+          bail!("watch-only descriptor must use an extended public key");
+        };
+
+        let Some((ChildNumber::Normal { index: 0 }, derivation_path)) =
+          xkey.derivation_path.as_ref().split_last()
+        else {
+          bail!("watch-only descriptor must end in `/0/*`");
+        };
+
+        ensure!(
+          tr.tap_tree().is_none() && xkey.wildcard == Wildcard::Unhardened,
+          "watch-only descriptor must be a ranged, key-path-only `tr()` descriptor",
+        );
+
+        (
+          xkey.origin.clone(),
+          xkey.xkey,
+          DerivationPath::from(derivation_path),
+        )
+      }
+    };
+
+    ensure!(
+      xpub.network == NetworkKind::from(network),
+      "extended public key is not valid for {network}",
+    );
+
+    [false, true]
+      .into_iter()
+      .map(|change| {
+        let public_key = DescriptorPublicKey::XPub(DescriptorXKey {
+          origin: origin.clone(),
+          xkey: xpub,
+          derivation_path: derivation_path.child(ChildNumber::Normal {
+            index: change.into(),
+          }),
+          wildcard: Wildcard::Unhardened,
+        });
+
+        Ok(miniscript::Descriptor::new_tr(public_key, None)?.to_string())
+      })
+      .collect()
+  }
+
   pub(crate) fn check_version(client: Client) -> Result<Client> {
     const MIN_VERSION: usize = 280000;
 
@@ -746,6 +1195,7 @@ impl Wallet {
         let mut tx = database.begin_write()?;
         tx.set_quick_repair(true);
 
+        tx.open_table(FROZEN)?;
         tx.open_table(RUNE_TO_ETCHING)?;
 
         tx.open_table(STATISTICS)?
@@ -821,24 +1271,171 @@ impl Wallet {
     )
   }
 
+  pub(crate) fn freeze(&self, item: Frozen) -> Result {
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    wtx
+      .open_table(FROZEN)?
+      .insert(item.to_string().as_str(), ())?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Unfreezes `item`, returning false if it was not frozen. Outputs that are
+  /// no longer frozen are unlocked, unless they hold inscriptions or runes.
+  pub(crate) fn unfreeze(&self, item: Frozen) -> Result<bool> {
+    let before = self.frozen_utxos()?;
+
+    let mut wtx = self.database.begin_write()?;
+    wtx.set_quick_repair(true);
+
+    let frozen = wtx
+      .open_table(FROZEN)?
+      .remove(item.to_string().as_str())?
+      .is_some();
+
+    wtx.commit()?;
+
+    let after = self.frozen_utxos()?;
+
+    let unlock = before
+      .difference(&after)
+      .filter(|outpoint| self.locked_utxos().contains_key(outpoint))
+      .filter(|outpoint| {
+        self.output_info.get(outpoint).is_some_and(|output| {
+          output.inscriptions.iter().flatten().next().is_none()
+            && output.runes.iter().flatten().next().is_none()
+        })
+      })
+      .cloned()
+      .collect::<Vec<OutPoint>>();
+
+    if !unlock.is_empty() && !self.bitcoin_client().unlock_unspent(&unlock)? {
+      bail!("failed to unlock UTXOs");
+    }
+
+    Ok(frozen)
+  }
+
+  /// Returns frozen items along with the wallet outputs that they freeze.
+  pub(crate) fn frozen(&self) -> Result<BTreeMap<Frozen, BTreeSet<OutPoint>>> {
+    let rtx = self.database.begin_read()?;
+
+    // wallet databases created before outputs could be frozen lack the table
+    let table = match rtx.open_table(FROZEN) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(BTreeMap::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut frozen = BTreeMap::new();
+
+    for result in table.iter()? {
+      let item = result?.0.value().parse::<Frozen>()?;
+
+      let outputs = self
+        .output_info
+        .iter()
+        .filter(|(outpoint, output)| item.freezes(**outpoint, output))
+        .map(|(outpoint, _)| *outpoint)
+        .collect();
+
+      frozen.insert(item, outputs);
+    }
+
+    Ok(frozen)
+  }
+
+  /// Returns wallet outputs that may not be spent because they, or an
+  /// inscription or rune they hold, are frozen.
+  pub(crate) fn frozen_utxos(&self) -> Result<BTreeSet<OutPoint>> {
+    Ok(self.frozen()?.into_values().flatten().collect())
+  }
+
+  /// Records the inscriptions and runes currently held by each input of
+  /// `psbt` so that `ord wallet finalize` can check them. Inputs spending
+  /// `unbroadcast` outputs, which the server can't know about, are skipped.
+  pub(crate) fn annotate_psbt(&self, psbt: &mut Psbt, unbroadcast: &[OutPoint]) -> Result {
+    let outputs = psbt
+      .unsigned_tx
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .filter(|outpoint| !unbroadcast.contains(outpoint))
+      .collect::<Vec<OutPoint>>();
+
+    let output_info = self.get_output_info(&outputs)?;
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(&psbt.unsigned_tx.input) {
+      if unbroadcast.contains(&txin.previous_output) {
+        PsbtAssets {
+          unbroadcast: true,
+          ..default()
+        }
+        .store(input);
+      } else if let Some(info) = output_info.get(&txin.previous_output)
+        && info.indexed
+      {
+        PsbtAssets::from_output(info).store(input);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns `transaction` as an annotated PSBT with wallet UTXO information
+  /// filled in. Inputs that are already signed keep their witnesses.
+  pub(crate) fn unsigned_psbt(&self, transaction: &Transaction) -> Result<String> {
+    let mut unsigned_transaction = transaction.clone();
+
+    for txin in &mut unsigned_transaction.input {
+      txin.witness = Witness::new();
+    }
+
+    let psbt = self
+      .bitcoin_client()
+      .wallet_process_psbt(
+        &base64_encode(&Psbt::from_unsigned_tx(unsigned_transaction)?.serialize()),
+        Some(false),
+        None,
+        None,
+      )?
+      .psbt;
+
+    let mut psbt = Psbt::deserialize(&base64_decode(&psbt)?)?;
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(&transaction.input) {
+      if !txin.witness.is_empty() {
+        input.final_script_witness = Some(txin.witness.clone());
+      }
+    }
+
+    self.annotate_psbt(&mut psbt, &[])?;
+
+    Ok(base64_encode(&psbt.serialize()))
+  }
+
+  pub(crate) fn write_psbt(psbt: &str, path: &Path) -> Result {
+    fs::write(path, psbt).with_context(|| format!("failed to write PSBT to `{}`", path.display()))
+  }
+
   pub(super) fn sign_and_broadcast_transaction(
     &self,
     unsigned_transaction: Transaction,
     dry_run: bool,
+    psbt_out: Option<&Path>,
     burn_amount: Option<Amount>,
   ) -> Result<(Txid, String, u64)> {
     let unspent_outputs = self.utxos();
 
-    let (txid, psbt) = if dry_run {
-      let psbt = self
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64_encode(&Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
+    let (txid, psbt) = if dry_run || psbt_out.is_some() || self.watch_only {
+      let psbt = self.unsigned_psbt(&unsigned_transaction)?;
+
+      if let Some(path) = psbt_out {
+        Self::write_psbt(&psbt, path)?;
+      }
 
       (unsigned_transaction.compute_txid(), psbt)
     } else {
@@ -959,7 +1556,7 @@ impl Wallet {
         satpoint,
         self.inscriptions().clone(),
         self.utxos().clone(),
-        self.locked_utxos().clone().into_keys().collect(),
+        self.locked_and_protected_utxos(),
         runic_outputs,
         destination.script_pubkey(),
         change,
@@ -967,6 +1564,8 @@ impl Wallet {
         postage,
         self.chain().network(),
       )
+      .satisfaction_weight(self.satisfaction_weight())
+      .frozen_utxos(self.frozen_utxos()?)
       .build_transaction()?,
     )
   }
@@ -998,11 +1597,13 @@ impl Wallet {
       .map(|satpoint| satpoint.outpoint)
       .collect::<HashSet<OutPoint>>();
 
+    let frozen_utxos = self.frozen_utxos()?;
+
     let balances = self
       .get_runic_outputs()?
       .unwrap_or_default()
       .into_iter()
-      .filter(|output| !inscribed_outputs.contains(output))
+      .filter(|output| !inscribed_outputs.contains(output) && !frozen_utxos.contains(output))
       .map(|output| {
         self.get_runes_balances_in_output(&output).map(|balance| {
           (
@@ -5,15 +5,18 @@ pub struct Plan {
   pub(crate) destinations: Vec<Address>,
   pub(crate) dry_run: bool,
   pub(crate) etching: Option<Etching>,
+  pub(crate) frozen_utxos: BTreeSet<OutPoint>,
   pub(crate) inscriptions: Vec<Inscription>,
   pub(crate) mode: Mode,
   pub(crate) no_backup: bool,
   pub(crate) no_limit: bool,
   pub(crate) parent_info: Vec<ParentInfo>,
   pub(crate) postages: Vec<Amount>,
+  pub(crate) psbt_out: Option<PathBuf>,
   pub(crate) reinscribe: bool,
   pub(crate) reveal_fee_rate: FeeRate,
   pub(crate) reveal_satpoints: Vec<(SatPoint, TxOut)>,
+  pub(crate) satisfaction_weight: Weight,
   pub(crate) satpoint: Option<SatPoint>,
 }
 
@@ -24,15 +27,18 @@ impl Default for Plan {
       destinations: Vec::new(),
       dry_run: false,
       etching: None,
+      frozen_utxos: BTreeSet::new(),
       inscriptions: Vec::new(),
       mode: Mode::SharedOutput,
       no_backup: false,
       no_limit: false,
       parent_info: Vec::new(),
       postages: vec![Amount::from_sat(10_000)],
+      psbt_out: None,
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       reveal_satpoints: Vec::new(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       satpoint: None,
     }
   }
@@ -63,20 +69,32 @@ impl Plan {
       wallet.get_change_address()?,
     )?;
 
-    if self.dry_run {
-      let commit_psbt = wallet
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64_encode(
-            &Psbt::from_unsigned_tx(Self::remove_witnesses(commit_tx.clone()))?.serialize(),
-          ),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
+    if self.dry_run || self.psbt_out.is_some() || wallet.watch_only() {
+      let commit_psbt = wallet.unsigned_psbt(&Self::remove_witnesses(commit_tx.clone()))?;
+
+      let reveal_psbt = if self.dry_run {
+        Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?
+      } else {
+        let mut reveal_psbt = self.signable_reveal_psbt(&commit_tx, commit_vout, &reveal_tx)?;
 
-      let reveal_psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
+        wallet.annotate_psbt(
+          &mut reveal_psbt,
+          &[OutPoint {
+            txid: commit_tx.compute_txid(),
+            vout: commit_vout.try_into().unwrap(),
+          }],
+        )?;
+
+        reveal_psbt
+      };
+
+      if let Some(path) = &self.psbt_out {
+        Wallet::write_psbt(&commit_psbt, path)?;
+
+        if !self.no_backup && !wallet.watch_only() {
+          Self::backup_recovery_key(wallet, recovery_key_pair)?;
+        }
+      }
 
       return Ok(Some(Box::new(self.output(
         commit_tx.compute_txid(),
@@ -183,6 +201,45 @@ impl Plan {
     }
   }
 
+  /// The reveal transaction's commit input is already signed with the
+  /// ephemeral key, so the PSBT handed to an external signer carries that
+  /// witness along with every prevout, which taproot sighashes commit to.
+  fn signable_reveal_psbt(
+    &self,
+    commit_tx: &Transaction,
+    commit_vout: usize,
+    reveal_tx: &Transaction,
+  ) -> Result<Psbt> {
+    let mut psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
+
+    let mut prevouts = self
+      .parent_info
+      .iter()
+      .map(|parent_info| parent_info.tx_out.clone())
+      .collect::<Vec<TxOut>>();
+
+    if self.mode == Mode::SatPoints {
+      prevouts.extend(
+        self
+          .reveal_satpoints
+          .iter()
+          .map(|(_satpoint, txout)| txout.clone()),
+      );
+    }
+
+    prevouts.push(commit_tx.output[commit_vout].clone());
+
+    for ((input, txin), prevout) in psbt.inputs.iter_mut().zip(&reveal_tx.input).zip(prevouts) {
+      input.witness_utxo = Some(prevout);
+
+      if !txin.witness.is_empty() {
+        input.final_script_witness = Some(txin.witness.clone());
+      }
+    }
+
+    Ok(psbt)
+  }
+
   fn remove_witnesses(mut transaction: Transaction) -> Transaction {
     for txin in transaction.input.iter_mut() {
       txin.witness = Witness::new();
@@ -273,6 +330,23 @@ impl Plan {
       );
     }
 
+    for outpoint in self
+      .parent_info
+      .iter()
+      .map(|info| info.location.outpoint)
+      .chain(
+        self
+          .reveal_satpoints
+          .iter()
+          .map(|(satpoint, _)| satpoint.outpoint),
+      )
+    {
+      ensure!(
+        !self.frozen_utxos.contains(&outpoint),
+        "output {outpoint} is frozen"
+      );
+    }
+
     match self.mode {
       Mode::SameSat => {
         assert_eq!(
@@ -332,7 +406,7 @@ impl Plan {
           outpoint: *outpoint,
           offset: 0,
         })
-        .ok_or_else(|| anyhow!("wallet contains no cardinal utxos"))?
+        .ok_or(SnafuError::NoCardinalUtxos)?
     };
 
     let mut reinscription = false;
@@ -536,6 +610,8 @@ impl Plan {
       Target::Value(target_value),
       chain.network(),
     )
+    .satisfaction_weight(self.satisfaction_weight)
+    .frozen_utxos(self.frozen_utxos.clone())
     .build_transaction()?;
 
     let (vout, _commit_output) = unsigned_commit_tx
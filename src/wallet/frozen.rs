@@ -0,0 +1,113 @@
+use super::*;
+
+/// An output, inscription, or rune that the wallet refuses to spend until it
+/// is unfrozen. Inscriptions and runes stay frozen as they move between wallet
+/// outputs, and runes are frozen in every output that holds them.
+#[derive(
+  Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, DeserializeFromStr, SerializeDisplay,
+)]
+pub enum Frozen {
+  InscriptionId(InscriptionId),
+  OutPoint(OutPoint),
+  Rune(Rune),
+}
+
+impl Frozen {
+  pub(crate) fn freezes(self, outpoint: OutPoint, output: &api::Output) -> bool {
+    match self {
+      Self::InscriptionId(inscription_id) => output
+        .inscriptions
+        .iter()
+        .flatten()
+        .any(|id| *id == inscription_id),
+      Self::OutPoint(frozen) => frozen == outpoint,
+      Self::Rune(rune) => output
+        .runes
+        .iter()
+        .flatten()
+        .any(|(spaced_rune, _)| spaced_rune.rune == rune),
+    }
+  }
+}
+
+impl Display for Frozen {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::InscriptionId(inscription_id) => inscription_id.fmt(f),
+      Self::OutPoint(outpoint) => outpoint.fmt(f),
+      Self::Rune(rune) => rune.fmt(f),
+    }
+  }
+}
+
+impl FromStr for Frozen {
+  type Err = SnafuError;
+
+  fn from_str(input: &str) -> Result<Self, Self::Err> {
+    if re::OUTPOINT.is_match(input) {
+      Ok(Self::OutPoint(
+        input
+          .parse()
+          .snafu_context(error::OutPointParse { input })?,
+      ))
+    } else if re::INSCRIPTION_ID.is_match(input) {
+      Ok(Self::InscriptionId(
+        input
+          .parse()
+          .snafu_context(error::InscriptionIdParse { input })?,
+      ))
+    } else if re::SPACED_RUNE.is_match(input) {
+      Ok(Self::Rune(
+        input
+          .parse::<SpacedRune>()
+          .snafu_context(error::RuneParse { input })?
+          .rune,
+      ))
+    } else {
+      Err(SnafuError::FrozenParse {
+        input: input.to_string(),
+      })
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn from_str() {
+    #[track_caller]
+    fn case(s: &str, frozen: Frozen) {
+      assert_eq!(s.parse::<Frozen>().unwrap(), frozen);
+    }
+
+    case(
+      "0000000000000000000000000000000000000000000000000000000000000000:0",
+      Frozen::OutPoint(outpoint(0)),
+    );
+
+    case(
+      "1111111111111111111111111111111111111111111111111111111111111111i1",
+      Frozen::InscriptionId(inscription_id(1)),
+    );
+
+    case("AAAA", Frozen::Rune("AAAA".parse().unwrap()));
+    case("A•A•A•A", Frozen::Rune("AAAA".parse().unwrap()));
+
+    assert_matches!("foo".parse::<Frozen>(), Err(SnafuError::FrozenParse { .. }));
+  }
+
+  #[test]
+  fn display() {
+    assert_eq!(
+      Frozen::Rune("A•B".parse::<SpacedRune>().unwrap().rune).to_string(),
+      "AB"
+    );
+
+    assert_eq!(
+      Frozen::OutPoint(outpoint(1)).to_string(),
+      outpoint(1).to_string()
+    );
+  }
+}
@@ -0,0 +1,289 @@
+use {super::*, ordinals::COIN_VALUE};
+
+/// Sats that the wallet refuses to spend as cardinals, either to pay fees, to
+/// pad transactions, or to fund sends, identified by rarity and charm. Rare
+/// sats are always the first sat of a block, so ranges are searched by block,
+/// coin, and palindrome boundaries, and never sat by sat.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Protection {
+  charms: Vec<Charm>,
+  rarity: Option<Rarity>,
+}
+
+impl Protection {
+  pub(crate) fn new(rarity: Option<Rarity>, charms: Vec<Charm>) -> Result<Self> {
+    if let Some(rarity) = rarity {
+      ensure!(
+        rarity > Rarity::Common,
+        "cannot protect {rarity} sats, since every sat is at least {rarity}"
+      );
+    }
+
+    for charm in &charms {
+      ensure!(
+        Self::rarity(*charm).is_some()
+          || matches!(charm, Charm::Coin | Charm::Nineball | Charm::Palindrome),
+        "cannot protect {charm} sats, since {charm} is not a property of sats"
+      );
+    }
+
+    Ok(Self { charms, rarity })
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.charms.is_empty() && self.rarity.is_none()
+  }
+
+  /// Returns the protected sats in sat range `start..end`, as sorted,
+  /// non-overlapping ranges.
+  pub(crate) fn protected_ranges(&self, (start, end): (u64, u64)) -> Vec<(u64, u64)> {
+    let mut sats = BTreeSet::new();
+
+    if self.rarity.is_some()
+      || self
+        .charms
+        .iter()
+        .any(|charm| Self::rarity(*charm).is_some())
+    {
+      let mut height = Sat(start).height();
+
+      if height.starting_sat().n() < start {
+        height = height + 1;
+      }
+
+      while height.starting_sat().n() < end {
+        if self.protects_rarity(height.starting_sat().rarity()) {
+          sats.insert(height.starting_sat().n());
+        }
+
+        height = height + 1;
+      }
+    }
+
+    if self.charms.contains(&Charm::Coin) {
+      let mut sat = start.div_ceil(COIN_VALUE) * COIN_VALUE;
+
+      while sat < end {
+        sats.insert(sat);
+        sat += COIN_VALUE;
+      }
+    }
+
+    if self.charms.contains(&Charm::Palindrome) {
+      let mut sat = Self::next_palindrome(start);
+
+      while sat < end {
+        sats.insert(sat);
+        sat = Self::next_palindrome(sat + 1);
+      }
+    }
+
+    let nineball = if self.charms.contains(&Charm::Nineball) {
+      Some((
+        start.max(Height(9).starting_sat().n()),
+        end.min(Height(10).starting_sat().n()),
+      ))
+      .filter(|(start, end)| start < end)
+    } else {
+      None
+    };
+
+    let mut ranges = sats
+      .into_iter()
+      .filter(|sat| {
+        nineball
+          .map(|(start, end)| *sat < start || *sat >= end)
+          .unwrap_or(true)
+      })
+      .map(|sat| (sat, sat + 1))
+      .chain(nineball)
+      .collect::<Vec<(u64, u64)>>();
+
+    ranges.sort();
+
+    ranges
+  }
+
+  /// Returns true if any sat in `sat_ranges` is protected.
+  pub(crate) fn protects(&self, sat_ranges: &[(u64, u64)]) -> bool {
+    sat_ranges
+      .iter()
+      .any(|range| !self.protected_ranges(*range).is_empty())
+  }
+
+  fn protects_rarity(&self, rarity: Rarity) -> bool {
+    self.rarity.is_some_and(|protected| rarity >= protected)
+      || self
+        .charms
+        .iter()
+        .any(|charm| Self::rarity(*charm) == Some(rarity))
+  }
+
+  fn rarity(charm: Charm) -> Option<Rarity> {
+    match charm {
+      Charm::Uncommon => Some(Rarity::Uncommon),
+      Charm::Rare => Some(Rarity::Rare),
+      Charm::Epic => Some(Rarity::Epic),
+      Charm::Legendary => Some(Rarity::Legendary),
+      Charm::Mythic => Some(Rarity::Mythic),
+      _ => None,
+    }
+  }
+
+  /// Returns the smallest palindrome greater than or equal to `n`.
+  fn next_palindrome(n: u64) -> u64 {
+    let digits = n.to_string();
+    let len = digits.len();
+
+    let mirror = |half: u64| {
+      let half = half.to_string();
+      let tail = half[..len / 2].chars().rev().collect::<String>();
+      format!("{half}{tail}").parse::<u64>().unwrap()
+    };
+
+    let half = digits[..len.div_ceil(2)].parse::<u64>().unwrap();
+
+    let palindrome = mirror(half);
+
+    if palindrome >= n {
+      return palindrome;
+    }
+
+    if (half + 1).to_string().len() > len.div_ceil(2) {
+      10u64.pow(len.try_into().unwrap()) + 1
+    } else {
+      mirror(half + 1)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn common_sats_and_inscription_charms_cannot_be_protected() {
+    assert_eq!(
+      Protection::new(Some(Rarity::Common), Vec::new())
+        .unwrap_err()
+        .to_string(),
+      "cannot protect common sats, since every sat is at least common",
+    );
+
+    assert_eq!(
+      Protection::new(None, vec![Charm::Cursed])
+        .unwrap_err()
+        .to_string(),
+      "cannot protect cursed sats, since cursed is not a property of sats",
+    );
+  }
+
+  #[test]
+  fn next_palindrome() {
+    #[track_caller]
+    fn case(n: u64, expected: u64) {
+      assert_eq!(Protection::next_palindrome(n), expected);
+      assert!(Sat(expected).palindrome());
+    }
+
+    case(0, 0);
+    case(10, 11);
+    case(12, 22);
+    case(99, 99);
+    case(100, 101);
+    case(999, 999);
+    case(1000, 1001);
+    case(1234, 1331);
+    case(1299, 1331);
+    case(12921, 12921);
+    case(12922, 13031);
+    case(99999, 99999);
+    case(100000, 100001);
+
+    for n in 0..2000 {
+      let expected = (n..).find(|n| Sat(*n).palindrome()).unwrap();
+      assert_eq!(Protection::next_palindrome(n), expected, "{n}");
+    }
+  }
+
+  #[test]
+  fn protected_ranges_by_rarity() {
+    let protection = Protection::new(Some(Rarity::Uncommon), Vec::new()).unwrap();
+
+    assert_eq!(
+      protection.protected_ranges((0, 100 * COIN_VALUE + 1)),
+      [
+        (0, 1),
+        (50 * COIN_VALUE, 50 * COIN_VALUE + 1),
+        (100 * COIN_VALUE, 100 * COIN_VALUE + 1),
+      ],
+    );
+
+    assert!(protection.protected_ranges((1, 50 * COIN_VALUE)).is_empty());
+
+    let protection = Protection::new(Some(Rarity::Mythic), Vec::new()).unwrap();
+
+    assert_eq!(protection.protected_ranges((0, 100 * COIN_VALUE)), [(0, 1)]);
+
+    assert!(
+      protection
+        .protected_ranges((50 * COIN_VALUE, 100 * COIN_VALUE))
+        .is_empty()
+    );
+  }
+
+  #[test]
+  fn protected_ranges_by_rarity_charm() {
+    let protection = Protection::new(None, vec![Charm::Uncommon]).unwrap();
+
+    assert_eq!(
+      protection.protected_ranges((0, 100 * COIN_VALUE)),
+      [(50 * COIN_VALUE, 50 * COIN_VALUE + 1)],
+    );
+  }
+
+  #[test]
+  fn protected_ranges_by_coin() {
+    let protection = Protection::new(None, vec![Charm::Coin]).unwrap();
+
+    assert_eq!(
+      protection.protected_ranges((1, 2 * COIN_VALUE + 1)),
+      [
+        (COIN_VALUE, COIN_VALUE + 1),
+        (2 * COIN_VALUE, 2 * COIN_VALUE + 1)
+      ],
+    );
+  }
+
+  #[test]
+  fn protected_ranges_by_palindrome() {
+    let protection = Protection::new(None, vec![Charm::Palindrome]).unwrap();
+
+    assert_eq!(
+      protection.protected_ranges((100, 150)),
+      [(101, 102), (111, 112), (121, 122), (131, 132), (141, 142)],
+    );
+  }
+
+  #[test]
+  fn protected_ranges_by_nineball() {
+    let protection = Protection::new(None, vec![Charm::Nineball, Charm::Coin]).unwrap();
+
+    assert_eq!(
+      protection.protected_ranges((449 * COIN_VALUE, 451 * COIN_VALUE + 1)),
+      [
+        (449 * COIN_VALUE, 449 * COIN_VALUE + 1),
+        (450 * COIN_VALUE, 451 * COIN_VALUE + 1),
+      ],
+    );
+  }
+
+  #[test]
+  fn protects() {
+    let protection = Protection::new(Some(Rarity::Uncommon), Vec::new()).unwrap();
+
+    assert!(protection.protects(&[(10, 20), (50 * COIN_VALUE, 51 * COIN_VALUE)]));
+    assert!(!protection.protects(&[(10, 20), (50 * COIN_VALUE + 1, 51 * COIN_VALUE)]));
+    assert!(!Protection::default().protects(&[(0, 1)]));
+  }
+}
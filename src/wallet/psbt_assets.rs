@@ -0,0 +1,164 @@
+use {
+  super::*,
+  bitcoin::psbt::{self, raw::ProprietaryKey},
+};
+
+const PREFIX: &[u8] = b"ord";
+
+const INSCRIPTION_SUBTYPE: u8 = 0;
+const RUNE_SUBTYPE: u8 = 1;
+const UNBROADCAST_SUBTYPE: u8 = 2;
+
+/// Inscriptions and runes that an input held when `ord` built a PSBT, stored
+/// in the input's proprietary fields so that `ord wallet finalize` can tell
+/// assets that were meant to be spent from ones that were not. Inputs that
+/// spend an output of a transaction that had not been broadcast, like the
+/// commit output spent by a reveal transaction, are marked as `unbroadcast`.
+/// The mark is only used to explain why such an input is rejected when it is
+/// finalized without the transaction that creates it, and never exempts an
+/// input from being checked.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct PsbtAssets {
+  pub(crate) inscriptions: BTreeSet<InscriptionId>,
+  pub(crate) runes: BTreeMap<Rune, u128>,
+  pub(crate) unbroadcast: bool,
+}
+
+impl PsbtAssets {
+  pub(crate) fn from_output(output: &api::Output) -> Self {
+    Self {
+      inscriptions: output.inscriptions.iter().flatten().copied().collect(),
+      runes: output
+        .runes
+        .iter()
+        .flatten()
+        .map(|(spaced_rune, pile)| (spaced_rune.rune, pile.amount))
+        .collect(),
+      unbroadcast: false,
+    }
+  }
+
+  pub(crate) fn load(input: &psbt::Input) -> Result<Self> {
+    let mut assets = Self::default();
+
+    for (key, value) in &input.proprietary {
+      if key.prefix != PREFIX {
+        continue;
+      }
+
+      match key.subtype {
+        INSCRIPTION_SUBTYPE => {
+          assets.inscriptions.insert(
+            InscriptionId::from_value(&key.key)
+              .ok_or_else(|| anyhow!("invalid inscription ID in PSBT"))?,
+          );
+        }
+        RUNE_SUBTYPE => {
+          assets.runes.insert(
+            Rune(u128::from_le_bytes(
+              key
+                .key
+                .as_slice()
+                .try_into()
+                .context("invalid rune in PSBT")?,
+            )),
+            u128::from_le_bytes(
+              value
+                .as_slice()
+                .try_into()
+                .context("invalid rune amount in PSBT")?,
+            ),
+          );
+        }
+        UNBROADCAST_SUBTYPE => assets.unbroadcast = true,
+        subtype => bail!("unknown `ord` PSBT field subtype {subtype}"),
+      }
+    }
+
+    Ok(assets)
+  }
+
+  pub(crate) fn store(&self, input: &mut psbt::Input) {
+    input.proprietary.retain(|key, _| key.prefix != PREFIX);
+
+    for inscription_id in &self.inscriptions {
+      input.proprietary.insert(
+        Self::key(INSCRIPTION_SUBTYPE, inscription_id.value()),
+        Vec::new(),
+      );
+    }
+
+    for (rune, amount) in &self.runes {
+      input.proprietary.insert(
+        Self::key(RUNE_SUBTYPE, rune.0.to_le_bytes().into()),
+        amount.to_le_bytes().into(),
+      );
+    }
+
+    if self.unbroadcast {
+      input
+        .proprietary
+        .insert(Self::key(UNBROADCAST_SUBTYPE, Vec::new()), Vec::new());
+    }
+  }
+
+  fn key(subtype: u8, key: Vec<u8>) -> ProprietaryKey {
+    ProprietaryKey {
+      prefix: PREFIX.into(),
+      subtype,
+      key,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn store_and_load() {
+    let assets = PsbtAssets {
+      inscriptions: [inscription_id(1), inscription_id(2)].into(),
+      runes: [(Rune(0), 1000), (Rune(u128::MAX), u128::MAX)].into(),
+      unbroadcast: true,
+    };
+
+    let mut input = psbt::Input::default();
+
+    assets.store(&mut input);
+
+    assert_eq!(PsbtAssets::load(&input).unwrap(), assets);
+  }
+
+  #[test]
+  fn store_replaces_existing_assets() {
+    let mut input = psbt::Input::default();
+
+    PsbtAssets {
+      inscriptions: [inscription_id(1)].into(),
+      runes: [(Rune(0), 1)].into(),
+      unbroadcast: true,
+    }
+    .store(&mut input);
+
+    PsbtAssets::default().store(&mut input);
+
+    assert!(input.proprietary.is_empty());
+  }
+
+  #[test]
+  fn other_proprietary_fields_are_ignored() {
+    let mut input = psbt::Input::default();
+
+    input.proprietary.insert(
+      ProprietaryKey {
+        prefix: b"foo".into(),
+        subtype: 0,
+        key: Vec::new(),
+      },
+      Vec::new(),
+    );
+
+    assert_eq!(PsbtAssets::load(&input).unwrap(), PsbtAssets::default());
+  }
+}
@@ -43,6 +43,7 @@ pub enum Error {
     output_value: Amount,
     dust_value: Amount,
   },
+  Frozen(OutPoint),
   InvalidAddress(bitcoin::address::FromScriptError),
   NotEnoughCardinalUtxos,
   NotInWallet(SatPoint),
@@ -72,6 +73,7 @@ impl Display for Error {
         f,
         "output value is below dust value: {output_value} < {dust_value}"
       ),
+      Error::Frozen(outpoint) => write!(f, "output {outpoint} is frozen"),
       Error::InvalidAddress(source) => write!(f, "invalid address: {source}",),
       Error::NotInWallet(outgoing_satpoint) => {
         write!(f, "outgoing satpoint {outgoing_satpoint} not in wallet")
@@ -116,6 +118,7 @@ pub struct TransactionBuilder {
   amounts: BTreeMap<OutPoint, TxOut>,
   change_addresses: BTreeSet<Address>,
   fee_rate: FeeRate,
+  frozen_utxos: BTreeSet<OutPoint>,
   inputs: Vec<OutPoint>,
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
   locked_utxos: BTreeSet<OutPoint>,
@@ -124,6 +127,7 @@ pub struct TransactionBuilder {
   outputs: Vec<TxOut>,
   recipient: ScriptBuf,
   runic_utxos: BTreeSet<OutPoint>,
+  satisfaction_weight: Weight,
   target: Target,
   unused_change_addresses: Vec<Address>,
   utxos: BTreeSet<OutPoint>,
@@ -135,6 +139,8 @@ impl TransactionBuilder {
   const ADDITIONAL_INPUT_VBYTES: usize = 57;
   const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
   const SCHNORR_SIGNATURE_SIZE: usize = 64;
+  pub(crate) const KEY_PATH_SATISFACTION_WEIGHT: Weight =
+    Weight::from_wu(1 + Self::SCHNORR_SIGNATURE_SIZE as u64);
   pub(crate) const MAX_POSTAGE: Amount = Amount::from_sat(2 * 10_000);
 
   pub fn new(
@@ -154,6 +160,7 @@ impl TransactionBuilder {
       amounts,
       change_addresses: change.iter().cloned().collect(),
       fee_rate,
+      frozen_utxos: BTreeSet::new(),
       inputs: Vec::new(),
       inscriptions,
       locked_utxos,
@@ -161,12 +168,29 @@ impl TransactionBuilder {
       outputs: Vec::new(),
       recipient,
       runic_utxos,
+      satisfaction_weight: Self::KEY_PATH_SATISFACTION_WEIGHT,
       target,
       unused_change_addresses: change.to_vec(),
       network,
     }
   }
 
+  /// Use `satisfaction_weight` as the weight of the witness of each input,
+  /// instead of that of a single Schnorr signature. This is needed when
+  /// inputs are spent from script paths, for example those of multisig
+  /// wallets.
+  pub fn satisfaction_weight(mut self, satisfaction_weight: Weight) -> Self {
+    self.satisfaction_weight = satisfaction_weight;
+    self
+  }
+
+  /// Never spend `frozen_utxos`, neither as the outgoing output nor to pad
+  /// the transaction or pay fees.
+  pub fn frozen_utxos(mut self, frozen_utxos: BTreeSet<OutPoint>) -> Self {
+    self.frozen_utxos = frozen_utxos;
+    self
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     if self.change_addresses.len() < 2 {
       return Err(Error::DuplicateAddress(
@@ -232,6 +256,10 @@ impl TransactionBuilder {
       .value
       .to_sat();
 
+    if self.frozen_utxos.contains(&self.outgoing.outpoint) {
+      return Err(Error::Frozen(self.outgoing.outpoint));
+    }
+
     if self.outgoing.offset >= amount {
       return Err(Error::OutOfRange(self.outgoing, amount - 1));
     }
@@ -334,7 +362,7 @@ impl TransactionBuilder {
 
     if let Some(mut deficit) = total.checked_sub(self.outputs.last().unwrap().value) {
       while deficit > Amount::ZERO {
-        let additional_fee = self.fee_rate.fee(Self::ADDITIONAL_INPUT_VBYTES);
+        let additional_fee = self.fee_rate.fee(self.additional_input_vbytes());
 
         let needed = deficit
           .checked_add(additional_fee)
@@ -449,15 +477,20 @@ impl TransactionBuilder {
   }
 
   /// Estimate the size in virtual bytes of the transaction under construction.
-  /// We initialize wallets with taproot descriptors only, so we know that all
-  /// inputs are taproot key path spends, which allows us to know that witnesses
-  /// will all consist of single Schnorr signatures.
+  /// Wallets created by `ord wallet create` use taproot descriptors, so unless
+  /// a different satisfaction weight is given, we know that all inputs are
+  /// taproot key path spends, which allows us to know that witnesses will all
+  /// consist of single Schnorr signatures.
   fn estimate_vbytes(&self) -> usize {
-    Self::estimate_vbytes_with(self.inputs.len(), &self.outputs)
+    Self::estimate_vbytes_with(self.inputs.len(), &self.outputs, self.satisfaction_weight)
   }
 
-  fn estimate_vbytes_with(inputs: usize, outputs: &[TxOut]) -> usize {
-    Transaction {
+  pub(crate) fn estimate_vbytes_with(
+    inputs: usize,
+    outputs: &[TxOut],
+    satisfaction_weight: Weight,
+  ) -> usize {
+    let transaction = Transaction {
       version: Version(2),
       lock_time: LockTime::ZERO,
       input: (0..inputs)
@@ -465,12 +498,37 @@ impl TransactionBuilder {
           previous_output: OutPoint::null(),
           script_sig: ScriptBuf::new(),
           sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
-          witness: Witness::from_slice(&[&[0; Self::SCHNORR_SIGNATURE_SIZE]]),
+          witness: Witness::new(),
         })
         .collect(),
       output: outputs.to_vec(),
+    };
+
+    if inputs == 0 {
+      return transaction.vsize();
     }
-    .vsize()
+
+    // segwit marker and flag, and for each input, the witness item count
+    // followed by the satisfaction
+    let witness_weight =
+      Weight::from_wu(2) + (Weight::from_wu(1) + satisfaction_weight) * inputs as u64;
+
+    (transaction.weight() + witness_weight)
+      .to_vbytes_ceil()
+      .try_into()
+      .unwrap()
+  }
+
+  fn additional_input_vbytes(&self) -> usize {
+    Self::ADDITIONAL_INPUT_VBYTES
+      + usize::try_from(
+        self
+          .satisfaction_weight
+          .checked_sub(Self::KEY_PATH_SATISFACTION_WEIGHT)
+          .unwrap_or(Weight::ZERO)
+          .to_vbytes_ceil(),
+      )
+      .unwrap()
   }
 
   fn estimate_fee(&self) -> Amount {
@@ -515,6 +573,13 @@ impl TransactionBuilder {
       "invariant: inputs spend outgoing sat"
     );
 
+    for tx_in in &transaction.input {
+      assert!(
+        !self.frozen_utxos.contains(&tx_in.previous_output),
+        "invariant: frozen outputs are not spent",
+      );
+    }
+
     let mut sat_offset = 0;
     let mut found = false;
     for tx_in in &transaction.input {
@@ -623,11 +688,11 @@ impl TransactionBuilder {
       actual_fee -= output.value;
     }
 
-    let mut modified_tx = transaction.clone();
-    for input in &mut modified_tx.input {
-      input.witness = Witness::from_slice(&[&[0; 64]]);
-    }
-    let expected_fee = self.fee_rate.fee(modified_tx.vsize());
+    let expected_fee = self.fee_rate.fee(Self::estimate_vbytes_with(
+      transaction.input.len(),
+      &transaction.output,
+      self.satisfaction_weight,
+    ));
 
     assert_eq!(
       actual_fee, expected_fee,
@@ -683,6 +748,7 @@ impl TransactionBuilder {
       if self.runic_utxos.contains(utxo)
         || inscribed_utxos.contains(utxo)
         || self.locked_utxos.contains(utxo)
+        || self.frozen_utxos.contains(utxo)
       {
         continue;
       }
@@ -784,12 +850,14 @@ mod tests {
     let tx_builder = TransactionBuilder {
       amounts,
       fee_rate: FeeRate::try_from(1.0).unwrap(),
+      frozen_utxos: BTreeSet::new(),
       utxos: BTreeSet::new(),
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
       recipient: recipient(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
       inputs: vec![outpoint(1), outpoint(2), outpoint(3)],
@@ -1074,6 +1142,111 @@ mod tests {
     .unwrap();
   }
 
+  #[test]
+  #[should_panic(expected = "invariant: frozen outputs are not spent")]
+  fn invariant_frozen_outputs_are_not_spent() {
+    let mut builder = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      vec![(outpoint(1), tx_out(10_000, address(0)))]
+        .into_iter()
+        .collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Postage,
+      Network::Testnet,
+    )
+    .select_outgoing()
+    .unwrap();
+
+    builder.frozen_utxos.insert(outpoint(1));
+
+    builder.build().unwrap();
+  }
+
+  #[test]
+  fn frozen_outgoing_output_is_an_error() {
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::new(),
+        vec![(outpoint(1), tx_out(10_000, address(0)))]
+          .into_iter()
+          .collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+        Network::Testnet,
+      )
+      .frozen_utxos([outpoint(1)].into())
+      .build_transaction(),
+      Err(Error::Frozen(outpoint(1))),
+    )
+  }
+
+  #[test]
+  fn frozen_outputs_are_not_used_to_pay_fees() {
+    let utxos = vec![
+      (outpoint(1), tx_out(1_000, address(0))),
+      (outpoint(2), tx_out(20_000, address(0))),
+      (outpoint(3), tx_out(20_000, address(0))),
+    ];
+
+    let transaction = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      utxos.into_iter().collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Value(Amount::from_sat(10_000)),
+      Network::Testnet,
+    )
+    .frozen_utxos([outpoint(2)].into())
+    .build_transaction()
+    .unwrap();
+
+    assert_eq!(
+      transaction
+        .input
+        .iter()
+        .map(|tx_in| tx_in.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(1), outpoint(3)],
+    );
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::new(),
+        vec![
+          (outpoint(1), tx_out(1_000, address(0))),
+          (outpoint(2), tx_out(20_000, address(0))),
+        ]
+        .into_iter()
+        .collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Value(Amount::from_sat(10_000)),
+        Network::Testnet,
+      )
+      .frozen_utxos([outpoint(2)].into())
+      .build_transaction(),
+      Err(Error::NotEnoughCardinalUtxos),
+    )
+  }
+
   #[test]
   #[should_panic(expected = "invariant: outgoing sat is sent to recipient")]
   fn invariant_sat_is_sent_to_recipient() {
@@ -1359,12 +1532,14 @@ mod tests {
     TransactionBuilder {
       amounts,
       fee_rate: FeeRate::try_from(1.0).unwrap(),
+      frozen_utxos: BTreeSet::new(),
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
       inputs: vec![outpoint(1), outpoint(2), outpoint(3)],
@@ -1400,12 +1575,14 @@ mod tests {
     TransactionBuilder {
       amounts,
       fee_rate: FeeRate::try_from(1.0).unwrap(),
+      frozen_utxos: BTreeSet::new(),
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
       inputs: vec![outpoint(1), outpoint(2), outpoint(3)],
@@ -1675,14 +1852,121 @@ mod tests {
 
   #[test]
   fn additional_input_size_is_correct() {
-    let before = TransactionBuilder::estimate_vbytes_with(0, &[]);
-    let after = TransactionBuilder::estimate_vbytes_with(1, &[]);
+    let before = TransactionBuilder::estimate_vbytes_with(
+      0,
+      &[],
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+    );
+    let after = TransactionBuilder::estimate_vbytes_with(
+      1,
+      &[],
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+    );
     assert_eq!(after - before, TransactionBuilder::ADDITIONAL_INPUT_VBYTES);
   }
 
+  #[test]
+  fn key_path_estimate_matches_schnorr_witness() {
+    for inputs in 0..4 {
+      assert_eq!(
+        TransactionBuilder::estimate_vbytes_with(
+          inputs,
+          &[tx_out(1_000, recipient_address())],
+          TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+        ),
+        Transaction {
+          version: Version(2),
+          lock_time: LockTime::ZERO,
+          input: (0..inputs)
+            .map(|_| TxIn {
+              witness: Witness::from_slice(&[&[0; TransactionBuilder::SCHNORR_SIGNATURE_SIZE]]),
+              ..tx_in(OutPoint::null())
+            })
+            .collect(),
+          output: vec![tx_out(1_000, recipient_address())],
+        }
+        .vsize(),
+      );
+    }
+  }
+
+  #[test]
+  fn satisfaction_weight_is_used_to_estimate_fee() {
+    let fee = |satisfaction_weight| {
+      let transaction = TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::new(),
+        [
+          (outpoint(1), tx_out(10_000, address(0))),
+          (outpoint(2), tx_out(20_000, address(0))),
+        ]
+        .into_iter()
+        .collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Value(Amount::from_sat(10_000)),
+        Network::Testnet,
+      )
+      .satisfaction_weight(satisfaction_weight)
+      .build_transaction()
+      .unwrap();
+
+      assert_eq!(transaction.input.len(), 2);
+
+      Amount::from_sat(30_000)
+        - transaction
+          .output
+          .iter()
+          .map(|tx_out| tx_out.value)
+          .sum::<Amount>()
+    };
+
+    assert_eq!(
+      fee(TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT),
+      Amount::from_sat(188),
+    );
+
+    assert_eq!(fee(Weight::from_wu(253)), Amount::from_sat(282));
+  }
+
+  #[test]
+  fn satisfaction_weight_increases_additional_input_size() {
+    let mut tx_builder = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      BTreeMap::new(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Postage,
+      Network::Testnet,
+    );
+
+    assert_eq!(
+      tx_builder.additional_input_vbytes(),
+      TransactionBuilder::ADDITIONAL_INPUT_VBYTES
+    );
+
+    tx_builder = tx_builder.satisfaction_weight(Weight::from_wu(253));
+
+    assert_eq!(
+      tx_builder.additional_input_vbytes(),
+      TransactionBuilder::ADDITIONAL_INPUT_VBYTES + 47
+    );
+  }
+
   #[test]
   fn additional_output_size_is_correct() {
-    let before = TransactionBuilder::estimate_vbytes_with(0, &[]);
+    let before = TransactionBuilder::estimate_vbytes_with(
+      0,
+      &[],
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+    );
     let after = TransactionBuilder::estimate_vbytes_with(
       0,
       &[TxOut {
@@ -1693,6 +1977,7 @@ mod tests {
           .script_pubkey(),
         value: Amount::from_sat(0),
       }],
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
     );
     assert_eq!(after - before, TransactionBuilder::ADDITIONAL_OUTPUT_VBYTES);
   }
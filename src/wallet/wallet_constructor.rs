@@ -1,10 +1,11 @@
-use super::*;
+use {super::*, protection::Protection};
 
 #[derive(Clone)]
 pub(crate) struct WalletConstructor {
   ord_client: reqwest::blocking::Client,
   name: String,
   no_sync: bool,
+  protection: Protection,
   rpc_url: Url,
   settings: Settings,
 }
@@ -13,6 +14,7 @@ impl WalletConstructor {
   pub(crate) fn construct(
     name: String,
     no_sync: bool,
+    protection: Protection,
     settings: Settings,
     rpc_url: Url,
   ) -> Result<Wallet> {
@@ -37,6 +39,7 @@ impl WalletConstructor {
         .build()?,
       name,
       no_sync,
+      protection,
       rpc_url,
       settings,
     }
@@ -46,7 +49,7 @@ impl WalletConstructor {
   pub(crate) fn build(self) -> Result<Wallet> {
     let database = Wallet::open_database(&self.name, &self.settings)?;
 
-    let bitcoin_client = {
+    let (bitcoin_client, watch_only, satisfaction_weight) = {
       let client =
         Wallet::check_version(self.settings.bitcoin_rpc_client(Some(self.name.clone()))?)?;
 
@@ -84,16 +87,22 @@ impl WalletConstructor {
 
       let wallet_info = client.call::<WalletInfo>("getwalletinfo", &[])?;
 
-      if wallet_info.private_keys_enabled {
-        Wallet::check_descriptors(
-          &self.name,
-          client
-            .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::Value::Null])?
-            .descriptors,
-        )?;
-      }
+      let descriptors = client
+        .call::<ListDescriptorsResult>("listdescriptors", &[serde_json::Value::Null])?
+        .descriptors;
+
+      let satisfaction_weight = if wallet_info.private_keys_enabled {
+        Wallet::check_descriptors(&self.name, descriptors)?;
+        None
+      } else {
+        Wallet::descriptor_satisfaction_weight(&descriptors)
+      };
 
-      client
+      (
+        client,
+        !wallet_info.private_keys_enabled,
+        satisfaction_weight,
+      )
     };
 
     let bitcoin_block_count = bitcoin_client.get_block_count().unwrap() + 1;
@@ -123,17 +132,38 @@ impl WalletConstructor {
     let output_info = self.get_output_info(utxos.clone().into_keys().collect())?;
 
     let inscriptions = output_info
-      .iter()
-      .flat_map(|(_output, info)| info.inscriptions.clone().unwrap_or_default())
+      .values()
+      .flat_map(|info| info.inscriptions.clone().unwrap_or_default())
       .collect::<Vec<InscriptionId>>();
 
     let (inscriptions, inscription_info) = self.get_inscriptions(&inscriptions)?;
 
     let status = self.get_server_status()?;
 
+    let protected_utxos = if self.protection.is_empty() {
+      BTreeSet::new()
+    } else {
+      ensure!(
+        status.sat_index,
+        "ord index must be built with `--index-sats` to protect sats"
+      );
+
+      output_info
+        .iter()
+        .filter(|(_, info)| {
+          info
+            .sat_ranges
+            .as_ref()
+            .is_some_and(|sat_ranges| self.protection.protects(sat_ranges))
+        })
+        .map(|(outpoint, _)| *outpoint)
+        .collect()
+    };
+
     Ok(Wallet {
       bitcoin_client,
       database,
+      has_rune_history_index: status.rune_history_index,
       has_rune_index: status.rune_index,
       has_sat_index: status.sat_index,
       inscription_info,
@@ -141,9 +171,12 @@ impl WalletConstructor {
       locked_utxos,
       ord_client: self.ord_client,
       output_info,
+      protected_utxos,
       rpc_url: self.rpc_url,
+      satisfaction_weight,
       settings: self.settings,
       utxos,
+      watch_only,
     })
   }
 
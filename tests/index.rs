@@ -90,3 +90,124 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn events_requires_event_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("index events")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: `ord index events` requires index created with `--index-events` flag\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn events_can_be_resumed_from_cursor() {
+  let core = mockcore::spawn();
+  core.mine_blocks(2);
+
+  let output = CommandBuilder::new("--index-events index events")
+    .core(&core)
+    .run_and_deserialize_output::<api::Events>();
+
+  assert!(!output.more);
+  assert_eq!(
+    output
+      .events
+      .iter()
+      .map(|entry| entry.cursor)
+      .collect::<Vec<u64>>(),
+    [0, 1, 2],
+  );
+
+  for (height, entry) in output.events.iter().enumerate() {
+    assert_eq!(
+      entry.event,
+      ord::index::event::Event::BlockCommitted {
+        block_hash: core.state().hashes[height],
+        block_height: height.try_into().unwrap(),
+      },
+    );
+  }
+
+  let output = CommandBuilder::new("--index-events index events --after 0 --limit 1")
+    .core(&core)
+    .run_and_deserialize_output::<api::Events>();
+
+  assert!(output.more);
+  assert_eq!(output.events.len(), 1);
+  assert_eq!(output.events[0].cursor, 1);
+}
+
+#[test]
+fn rune_activity_requires_runes() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-rune-activity index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--index-rune-activity` requires `--index-runes`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn rune_history_requires_runes() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-rune-history index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--index-rune-history` requires `--index-runes`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn inscription_history_requires_inscriptions() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-inscription-history --no-index-inscriptions index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: `--index-inscription-history` cannot be used with `--no-index-inscriptions`\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn search_requires_inscriptions() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-search --no-index-inscriptions index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--index-search` cannot be used with `--no-index-inscriptions`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn metaprotocols_require_inscriptions() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-metaprotocols --no-index-inscriptions index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: `--index-metaprotocols` cannot be used with `--no-index-inscriptions`\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn filters_require_inscriptions() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-filters --no-index-inscriptions index update")
+    .core(&core)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--index-filters` cannot be used with `--no-index-inscriptions`\n")
+    .run_and_extract_stdout();
+}
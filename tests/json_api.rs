@@ -186,6 +186,7 @@ fn get_inscription() {
       sat: Some(Sat(50 * COIN_VALUE)),
       satpoint: SatPoint::from_str(&format!("{}:{}:{}", reveal, 0, 0)).unwrap(),
       timestamp: 2,
+      transfers: None,
       metaprotocol: None
     }
   )
@@ -250,6 +251,7 @@ fn get_inscription_with_metaprotocol_and_properties() {
       sat: Some(Sat(50 * COIN_VALUE)),
       satpoint: SatPoint::from_str(&format!("{}:{}:{}", output.reveal, 0, 0)).unwrap(),
       timestamp: 2,
+      transfers: None,
       metaprotocol: Some("foo".to_string())
     }
   );
@@ -639,6 +641,7 @@ fn get_status() {
       json_api: true,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
+      rune_history_index: false,
       rune_index: true,
       runes: 0,
       sat_index: true,
@@ -691,6 +694,7 @@ fn get_runes() {
         timestamp: 10,
         turbo: false,
       },
+      holders: None,
       id: RuneId { block: 10, tx: 1 },
       mintable: false,
       parent: Some(InscriptionId {
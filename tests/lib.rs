@@ -95,6 +95,25 @@ fn create_wallet(core: &mockcore::Handle, ord: &TestServer) {
     .run_and_extract_stdout();
 }
 
+fn xpub(network: Network) -> String {
+  bitcoin::bip32::Xpub::from_priv(
+    &bitcoin::secp256k1::Secp256k1::new(),
+    &bitcoin::bip32::Xpriv::new_master(network, &[0; 32]).unwrap(),
+  )
+  .to_string()
+}
+
+fn create_watch_only_wallet(core: &mockcore::Handle, ord: &TestServer) {
+  CommandBuilder::new(format!(
+    "wallet create --watch-only {}",
+    xpub(Network::Bitcoin)
+  ))
+  .core(core)
+  .ord(ord)
+  .stdout_regex(".*")
+  .run_and_extract_stdout();
+}
+
 fn sats(
   core: &mockcore::Handle,
   ord: &TestServer,
@@ -366,7 +385,9 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   <dt>premine percentage</dt>
   <dd>.*</dd>
   <dt>burned</dt>
-  <dd>0 {symbol}</dd>
+  <dd>0 {symbol}</dd>(
+  <dt>holders</dt>
+  <dd>\d+</dd>)?
   <dt>divisibility</dt>
   <dd>{divisibility}</dd>
   <dt>symbol</dt>
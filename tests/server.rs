@@ -264,6 +264,7 @@ fn inscription_page() {
       id: inscription,
       number: 0,
       next: None,
+      offers: Vec::new(),
       output: Some(TxOut {
         value: Amount::from_sat(10000),
         script_pubkey: output.script_pubkey,
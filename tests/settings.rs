@@ -23,8 +23,15 @@ fn default() {
   "index": ".*index\.redb",
   "index_addresses": false,
   "index_cache_size": \d+,
+  "index_events": false,
+  "index_filters": false,
+  "index_inscription_history": false,
+  "index_metaprotocols": false,
+  "index_rune_activity": false,
+  "index_rune_history": false,
   "index_runes": false,
   "index_sats": false,
+  "index_search": false,
   "index_transactions": false,
   "integration_test": false,
   "max_savepoints": 2,
@@ -32,7 +39,9 @@ fn default() {
   "savepoint_interval": 10,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "webhook_secret": null,
+  "webhook_urls": \[\]
 \}
 "#,
     )
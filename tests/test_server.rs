@@ -56,7 +56,7 @@ impl TestServer {
       let ord_server_handle = ord_server_handle.clone();
       thread::spawn(|| {
         server
-          .run(settings, index, ord_server_handle, Some(tx))
+          .run(settings, index, ord_server_handle, Some(tx), None)
           .unwrap()
       });
     }
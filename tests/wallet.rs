@@ -8,6 +8,10 @@ mod burn;
 mod cardinals;
 mod create;
 mod dump;
+mod extract;
+mod finalize;
+mod freeze;
+mod history;
 mod inscribe;
 mod inscriptions;
 mod label;
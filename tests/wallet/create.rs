@@ -1,4 +1,7 @@
-use {super::*, ord::subcommand::wallet::create::Output};
+use {
+  super::*,
+  ord::subcommand::wallet::create::{Output, WatchOnlyOutput},
+};
 
 #[test]
 fn create() {
@@ -91,3 +94,270 @@ fn create_with_different_name() {
 
   assert!(core.wallets().contains("inscription-wallet"));
 }
+
+#[test]
+fn create_watch_only_wallet_from_xpub() {
+  let core = mockcore::spawn();
+
+  let xpub = xpub(Network::Bitcoin);
+
+  let output = CommandBuilder::new(format!("wallet create --watch-only {xpub}"))
+    .core(&core)
+    .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  assert!(core.watch_only_wallets().contains("ord"));
+
+  assert_eq!(core.descriptors(), output.descriptors);
+  assert_regex_match!(
+    &output.descriptors[0],
+    format!(r"tr\({xpub}/0/\*\)#[[:alnum:]]{{8}}")
+  );
+  assert_regex_match!(
+    &output.descriptors[1],
+    format!(r"tr\({xpub}/1/\*\)#[[:alnum:]]{{8}}")
+  );
+}
+
+#[test]
+fn create_watch_only_wallet_from_descriptor() {
+  let core = mockcore::spawn();
+
+  let xpub = xpub(Network::Bitcoin);
+
+  for descriptor in [
+    format!("tr([deadbeef/86'/0'/0']{xpub}/0/*)"),
+    format!("tr([deadbeef/86'/0'/0']{xpub}/<0;1>/*)"),
+  ] {
+    let output = CommandBuilder::new(format!("wallet create --watch-only {descriptor}"))
+      .core(&core)
+      .run_and_deserialize_output::<WatchOnlyOutput>();
+
+    assert_regex_match!(
+      &output.descriptors[0],
+      format!(r"tr\(\[deadbeef/86'/0'/0'\]{xpub}/0/\*\)#[[:alnum:]]{{8}}")
+    );
+    assert_regex_match!(
+      &output.descriptors[1],
+      format!(r"tr\(\[deadbeef/86'/0'/0'\]{xpub}/1/\*\)#[[:alnum:]]{{8}}")
+    );
+  }
+}
+
+#[test]
+fn watch_only_descriptor_must_be_taproot() {
+  CommandBuilder::new(format!(
+    "wallet create --watch-only wpkh({}/0/*)",
+    xpub(Network::Bitcoin)
+  ))
+  .core(&mockcore::spawn())
+  .expected_stderr("error: watch-only descriptor must be a taproot `tr()` descriptor\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_xpub_must_match_chain() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(format!(
+    "wallet create --watch-only {}",
+    xpub(Network::Testnet)
+  ))
+  .core(&core)
+  .expected_stderr("error: extended public key is not valid for bitcoin\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(!core.wallets().contains("ord"));
+}
+
+#[test]
+fn watch_only_wallet_can_receive_and_check_balance() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_watch_only_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>();
+
+  assert_eq!(
+    CommandBuilder::new("wallet balance")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Balance>()
+      .cardinal,
+    50 * COIN_VALUE,
+  );
+}
+
+fn cosigners(n: u8) -> Vec<String> {
+  (0..n)
+    .map(|i| {
+      bitcoin::bip32::Xpub::from_priv(
+        &bitcoin::secp256k1::Secp256k1::new(),
+        &bitcoin::bip32::Xpriv::new_master(Network::Bitcoin, &[i; 32]).unwrap(),
+      )
+      .to_string()
+    })
+    .collect()
+}
+
+#[test]
+fn create_taproot_multisig_wallet() {
+  let core = mockcore::spawn();
+
+  let cosigners = cosigners(3);
+
+  let output = CommandBuilder::new(format!(
+    "wallet create --multisig 2 --cosigner [deadbeef/48'/0'/0'/2']{}/0/0 --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[1], cosigners[2],
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  assert!(core.watch_only_wallets().contains("ord"));
+
+  assert_eq!(core.descriptors(), output.descriptors);
+
+  for (descriptor, change) in output.descriptors.iter().zip([0, 1]) {
+    assert_regex_match!(
+      descriptor,
+      r"tr\(50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0,multi_a\(2,.*\)\)#[[:alnum:]]{8}"
+    );
+    assert!(descriptor.contains(&format!(
+      "[deadbeef/48'/0'/0'/2']{}/0/0/{change}/*",
+      cosigners[0]
+    )));
+    assert!(descriptor.contains(&format!("{}/{change}/*", cosigners[1])));
+    assert!(descriptor.contains(&format!("{}/{change}/*", cosigners[2])));
+  }
+}
+
+#[test]
+fn create_p2wsh_multisig_wallet() {
+  let core = mockcore::spawn();
+
+  let cosigners = cosigners(2);
+
+  let output = CommandBuilder::new(format!(
+    "wallet create --multisig 2 --p2wsh --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[1],
+  ))
+  .core(&core)
+  .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  assert!(core.watch_only_wallets().contains("ord"));
+
+  for (descriptor, change) in output.descriptors.iter().zip([0, 1]) {
+    assert_regex_match!(descriptor, r"wsh\(sortedmulti\(2,.*\)\)#[[:alnum:]]{8}");
+    assert!(descriptor.contains(&format!("{}/{change}/*", cosigners[0])));
+    assert!(descriptor.contains(&format!("{}/{change}/*", cosigners[1])));
+  }
+}
+
+#[test]
+fn multisig_descriptor_does_not_depend_on_cosigner_order() {
+  let cosigners = cosigners(2);
+
+  let forward = CommandBuilder::new(format!(
+    "wallet --name a create --multisig 1 --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[1],
+  ))
+  .core(&mockcore::spawn())
+  .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  let backward = CommandBuilder::new(format!(
+    "wallet --name b create --multisig 1 --cosigner {} --cosigner {}",
+    cosigners[1], cosigners[0],
+  ))
+  .core(&mockcore::spawn())
+  .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  assert_eq!(forward.descriptors, backward.descriptors);
+}
+
+#[test]
+fn multisig_threshold_must_not_exceed_cosigners() {
+  let core = mockcore::spawn();
+
+  let cosigners = cosigners(2);
+
+  CommandBuilder::new(format!(
+    "wallet create --multisig 3 --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[1],
+  ))
+  .core(&core)
+  .expected_stderr("error: multisig threshold must be between 1 and the number of cosigners, 2\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(!core.wallets().contains("ord"));
+}
+
+#[test]
+fn multisig_cosigners_must_be_distinct() {
+  let cosigners = cosigners(1);
+
+  CommandBuilder::new(format!(
+    "wallet create --multisig 1 --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[0],
+  ))
+  .core(&mockcore::spawn())
+  .expected_stderr(format!("error: duplicate cosigner {}\n", cosigners[0]))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn multisig_cosigner_must_not_be_ranged() {
+  let cosigners = cosigners(1);
+
+  CommandBuilder::new(format!(
+    "wallet create --multisig 1 --cosigner {}/0/*",
+    cosigners[0],
+  ))
+  .core(&mockcore::spawn())
+  .expected_stderr(format!(
+    "error: cosigner `{}/0/*` must not end in a wildcard\n",
+    cosigners[0]
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn multisig_wallet_returns_unsigned_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let cosigners = cosigners(2);
+
+  CommandBuilder::new(format!(
+    "wallet create --multisig 2 --cosigner {} --cosigner {}",
+    cosigners[0], cosigners[1],
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<WatchOnlyOutput>();
+
+  core.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<ord::subcommand::wallet::send::Output>();
+
+  assert!(core.mempool().is_empty());
+
+  let psbt = Psbt::deserialize(&base64_decode(&output.psbt).unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.compute_txid(), output.txid);
+}
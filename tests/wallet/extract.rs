@@ -0,0 +1,234 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{
+    extract::{Extracted, Output},
+    sats::OutputRare,
+  },
+};
+
+fn fund(core: &mockcore::Handle, ord: &TestServer) {
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+    .core(core)
+    .ord(ord)
+    .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+}
+
+#[test]
+fn requires_sat_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet extract --fee-rate 1 --rarity uncommon")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord wallet extract` requires index created with `--index-sats`\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_rarity() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  fund(&core, &ord);
+
+  let output = CommandBuilder::new("--index-sats wallet extract --fee-rate 1 --rarity uncommon")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  let outpoint = OutPoint {
+    txid: output.txid,
+    vout: 0,
+  };
+
+  assert_eq!(
+    output.extracted,
+    [Extracted {
+      sat: Sat(100 * COIN_VALUE),
+      output: outpoint,
+    }],
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.input.len(), 1);
+  assert_eq!(tx.output.len(), 2);
+  assert_eq!(tx.output[0].value.to_sat(), 10_000);
+  assert_eq!(
+    tx.output[1].value.to_sat(),
+    50 * COIN_VALUE + 142 - 10_000 - output.fee,
+  );
+
+  core.mine_blocks(1);
+
+  let sats = CommandBuilder::new("--index-sats wallet sats")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputRare>>();
+
+  assert!(
+    sats
+      .iter()
+      .any(|sat| sat.sat == Sat(100 * COIN_VALUE) && sat.output == outpoint && sat.offset == 0)
+  );
+}
+
+#[test]
+fn extract_sat() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  fund(&core, &ord);
+
+  let sat = 100 * COIN_VALUE + 1_000;
+
+  let output = CommandBuilder::new(format!(
+    "--index-sats wallet extract --fee-rate 1 --sat {sat} --postage 5000sat"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.extracted,
+    [Extracted {
+      sat: Sat(sat),
+      output: OutPoint {
+        txid: output.txid,
+        vout: 1,
+      },
+    }],
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.output[0].value.to_sat(), 1_000);
+  assert_eq!(tx.output[1].value.to_sat(), 5_000);
+}
+
+#[test]
+fn extract_sat_not_in_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  fund(&core, &ord);
+
+  CommandBuilder::new("--index-sats wallet extract --fee-rate 1 --sat 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: could not find sat `1` in wallet outputs\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_requires_selection() {
+  CommandBuilder::new("wallet extract --fee-rate 1")
+    .expected_exit_code(2)
+    .stderr_regex(".*the following required arguments were not provided.*")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn nothing_to_extract() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  fund(&core, &ord);
+
+  CommandBuilder::new("--index-sats wallet extract --fee-rate 1 --rarity mythic")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: wallet contains no sats to extract\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_sat_from_inscribed_output_fails() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let inscription = response.json::<api::Inscription>().unwrap();
+
+  let sat = inscription.sat.unwrap();
+
+  CommandBuilder::new(format!(
+    "--index-sats wallet extract --fee-rate 1 --sat {sat}"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: cannot extract sat {sat} from inscribed output {}\n",
+    inscription.satpoint.outpoint,
+  ))
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn extract_pays_fee_from_change() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-sats wallet extract --fee-rate 1 --rarity uncommon")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.extracted,
+    [Extracted {
+      sat: Sat(50 * COIN_VALUE),
+      output: OutPoint {
+        txid: output.txid,
+        vout: 0,
+      },
+    }],
+  );
+
+  let tx = core.mempool()[0].clone();
+
+  assert_eq!(tx.input.len(), 1);
+  assert_eq!(tx.output.len(), 2);
+  assert_eq!(tx.output[0].value.to_sat(), 10_000);
+  assert_eq!(
+    tx.output[1].value.to_sat(),
+    50 * COIN_VALUE - 10_000 - output.fee,
+  );
+}
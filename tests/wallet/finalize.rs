@@ -0,0 +1,299 @@
+use {super::*, ord::subcommand::wallet::finalize::Output};
+
+fn send_psbt_out(core: &mockcore::Handle, ord: &TestServer) -> (InscriptionId, Arc<TempDir>, Send) {
+  create_wallet(core, ord);
+
+  core.mine_blocks(1);
+
+  let (inscription, _) = inscribe(core, ord);
+
+  core.mine_blocks(1);
+
+  let (tempdir, stdout) = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 --psbt-out psbt.txt bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv {inscription}",
+  ))
+  .core(core)
+  .ord(ord)
+  .stdout_regex(".*")
+  .run();
+
+  assert!(core.mempool().is_empty());
+
+  (inscription, tempdir, serde_json::from_str(&stdout).unwrap())
+}
+
+#[test]
+fn psbt_out_can_be_finalized() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (_, tempdir, send) = send_psbt_out(&core, &ord);
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("psbt.txt")).unwrap(),
+    send.psbt,
+  );
+
+  let output = CommandBuilder::new("wallet finalize psbt.txt")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [send.txid]);
+  assert_eq!(core.mempool()[0].compute_txid(), send.txid);
+}
+
+#[test]
+fn finalize_dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (_, _, send) = send_psbt_out(&core, &ord);
+
+  let output = CommandBuilder::new(format!("wallet finalize --dry-run {}", send.psbt))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [send.txid]);
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn finalize_rejects_unexpected_inscription() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (inscription, _, send) = send_psbt_out(&core, &ord);
+
+  let mut psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  for input in &mut psbt.inputs {
+    input.proprietary.clear();
+  }
+
+  CommandBuilder::new(format!(
+    "wallet finalize {}",
+    base64_encode(&psbt.serialize())
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: input {}:0 contains unexpected inscription {inscription}\n",
+    inscription.txid,
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn finalize_rejects_inscription_lost_to_fees() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (inscription, _, send) = send_psbt_out(&core, &ord);
+
+  let mut psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  psbt.unsigned_tx.output.clear();
+  psbt.outputs.clear();
+
+  CommandBuilder::new(format!(
+    "wallet finalize {}",
+    base64_encode(&psbt.serialize())
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: inscription {inscription} would be lost to fees\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn finalize_combines_psbts() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (_, tempdir, send) = send_psbt_out(&core, &ord);
+
+  let output = CommandBuilder::new(format!("wallet finalize psbt.txt {}", send.psbt))
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [send.txid]);
+  assert_eq!(core.mempool()[0].compute_txid(), send.txid);
+}
+
+#[test]
+fn finalize_rejects_psbts_for_different_transactions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (_, _, send) = send_psbt_out(&core, &ord);
+
+  let mut psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  psbt.unsigned_tx.lock_time = bitcoin::absolute::LockTime::from_consensus(1);
+
+  CommandBuilder::new(format!(
+    "wallet finalize {} {}",
+    send.psbt,
+    base64_encode(&psbt.serialize())
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: PSBT for transaction {} does not spend an output of an earlier PSBT\n",
+    psbt.unsigned_tx.compute_txid(),
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn reveal_psbt_can_be_finalized_with_commit_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (tempdir, stdout) = CommandBuilder::new(
+    "wallet inscribe --psbt-out commit.psbt --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .core(&core)
+  .ord(&ord)
+  .stdout_regex(".*")
+  .run();
+
+  let inscribe = serde_json::from_str::<Batch>(&stdout).unwrap();
+
+  let reveal_psbt = inscribe.reveal_psbt.unwrap();
+
+  let output = CommandBuilder::new(format!(
+    "wallet finalize --dry-run commit.psbt {reveal_psbt}"
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [inscribe.commit, inscribe.reveal]);
+  assert!(core.mempool().is_empty());
+
+  let output = CommandBuilder::new(format!("wallet finalize commit.psbt {reveal_psbt}"))
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [inscribe.commit, inscribe.reveal]);
+  assert_eq!(core.mempool().len(), 2);
+}
+
+#[test]
+fn finalize_rejects_tampered_unbroadcast_flag() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (inscription, _, send) = send_psbt_out(&core, &ord);
+
+  let mut psbt = Psbt::deserialize(&base64_decode(&send.psbt).unwrap()).unwrap();
+
+  for input in &mut psbt.inputs {
+    input.proprietary.clear();
+
+    input.proprietary.insert(
+      bitcoin::psbt::raw::ProprietaryKey {
+        prefix: b"ord".to_vec(),
+        subtype: 2,
+        key: Vec::new(),
+      },
+      Vec::new(),
+    );
+  }
+
+  CommandBuilder::new(format!(
+    "wallet finalize {}",
+    base64_encode(&psbt.serialize())
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: input {}:0 contains unexpected inscription {inscription}\n",
+    inscription.txid,
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
+
+#[test]
+fn reveal_psbt_requires_commit_psbt_until_commit_is_indexed() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (tempdir, stdout) = CommandBuilder::new(
+    "wallet inscribe --psbt-out commit.psbt --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .core(&core)
+  .ord(&ord)
+  .stdout_regex(".*")
+  .run();
+
+  let inscribe = serde_json::from_str::<Batch>(&stdout).unwrap();
+
+  let reveal_psbt = inscribe.reveal_psbt.unwrap();
+
+  CommandBuilder::new("wallet finalize commit.psbt")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  CommandBuilder::new(format!("wallet finalize {reveal_psbt}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(format!(
+      "error: input {}:0 spends an unbroadcast output, finalize it together with the PSBT that \
+      creates it\n",
+      inscribe.commit,
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!("wallet finalize {reveal_psbt}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.txids, [inscribe.reveal]);
+}
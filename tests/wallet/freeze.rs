@@ -0,0 +1,202 @@
+use {
+  super::*,
+  ord::{subcommand::wallet::freeze::Output, wallet::frozen::Frozen},
+};
+
+#[test]
+fn frozen_inscription_cannot_be_sent_until_unfrozen() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  core.mine_blocks(1);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!("wallet freeze {inscription}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  let outpoint = OutPoint {
+    txid: reveal,
+    vout: 0,
+  };
+
+  assert_eq!(
+    output.frozen,
+    [(Frozen::InscriptionId(inscription), [outpoint].into())].into(),
+  );
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!("error: output {outpoint} is frozen\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!("wallet unfreeze {inscription}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert!(output.frozen.is_empty());
+
+  CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+}
+
+#[test]
+fn frozen_outputs_are_not_used_to_fund_transactions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let coinbase = core.mine_blocks(1)[0].txdata[0].compute_txid();
+
+  let outpoint = OutPoint {
+    txid: coinbase,
+    vout: 0,
+  };
+
+  CommandBuilder::new(format!("wallet freeze {outpoint}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: not enough cardinal utxos\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!("wallet unfreeze {outpoint}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+}
+
+#[test]
+fn frozen_rune_cannot_be_sent() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet freeze {}",
+    Rune(RUNE)
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.frozen[&Frozen::Rune(Rune(RUNE))].len(), 1);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE)
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: insufficient `AAAAAAAAAAAAA` balance, only 0\u{A0}¢ in wallet\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn freeze_without_arguments_lists_frozen_items() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let outpoint = OutPoint {
+    txid: core.mine_blocks(1)[0].txdata[0].compute_txid(),
+    vout: 0,
+  };
+
+  let inscription = InscriptionId {
+    txid: outpoint.txid,
+    index: 5,
+  };
+
+  CommandBuilder::new(format!("wallet freeze {outpoint} {inscription}"))
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    CommandBuilder::new("wallet freeze")
+      .temp_dir(tempdir.clone())
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Output>()
+      .frozen,
+    [
+      (Frozen::InscriptionId(inscription), BTreeSet::new()),
+      (Frozen::OutPoint(outpoint), [outpoint].into()),
+    ]
+    .into(),
+  );
+}
+
+#[test]
+fn unfreezing_item_that_is_not_frozen_is_an_error() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new("wallet unfreeze AAAA")
+    .temp_dir(tempdir.clone())
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr("error: AAAA is not frozen\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,374 @@
+use {super::*, ord::decimal::Decimal, ord::subcommand::wallet::history::Output};
+
+#[track_caller]
+fn history(core: &mockcore::Handle, ord: &TestServer, args: &str) -> Vec<Output> {
+  CommandBuilder::new(format!("{args} wallet history"))
+    .core(core)
+    .ord(ord)
+    .run_and_deserialize_output::<Vec<Output>>()
+}
+
+#[track_caller]
+fn entry(history: &[Output], txid: Txid) -> &Output {
+  history
+    .iter()
+    .find(|output| output.transaction == txid)
+    .unwrap()
+}
+
+#[test]
+fn history_annotates_inscriptions() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let postage = 10_000;
+
+  let (inscription, reveal) = inscribe_with_options(&core, &ord, Some(postage), 1);
+
+  let history = history(&core, &ord, "");
+
+  let received = entry(&history, reveal);
+  assert_eq!(received.inscriptions_received, vec![inscription]);
+  assert!(received.inscriptions_sent.is_empty());
+  assert_eq!(received.net, i64::try_from(postage).unwrap());
+
+  let coinbase = entry(&history, core.tx(1, 0).compute_txid());
+
+  assert_eq!(coinbase.net, i64::try_from(50 * COIN_VALUE).unwrap());
+  assert!(coinbase.inscriptions_received.is_empty());
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = self::history(&core, &ord, "");
+
+  let sent = entry(&history, send.txid);
+  assert!(sent.inscriptions_received.is_empty());
+  assert_eq!(sent.inscriptions_sent, vec![inscription]);
+  assert_eq!(sent.net, -i64::try_from(postage).unwrap());
+}
+
+#[test]
+fn history_annotates_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let rune = SpacedRune::new(Rune(RUNE), 0);
+
+  let amount = Decimal {
+    value: 1000,
+    scale: 0,
+  };
+
+  let history = history(&core, &ord, "--regtest --index-runes");
+
+  assert_eq!(
+    entry(&history, etched.output.reveal).runes_received,
+    [(rune, amount)].into(),
+  );
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = self::history(&core, &ord, "--regtest --index-runes");
+
+  let sent = entry(&history, send.txid);
+  assert!(sent.runes_received.is_empty());
+  assert_eq!(sent.runes_sent, [(rune, amount)].into());
+  assert!(sent.runes_burned.is_empty());
+  assert_eq!(sent.mint, None);
+}
+
+#[test]
+fn history_inscription_annotations_survive_spends() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-inscription-history"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let send = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {inscription}",
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = history(&core, &ord, "");
+
+  let received = entry(&history, reveal);
+  assert_eq!(received.inscriptions_received, vec![inscription]);
+  assert!(received.inscriptions_sent.is_empty());
+
+  let sent = entry(&history, send.txid);
+  assert!(sent.inscriptions_received.is_empty());
+  assert_eq!(sent.inscriptions_sent, vec![inscription]);
+}
+
+#[test]
+fn history_rune_annotations_survive_spends() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-runes", "--index-rune-history", "--regtest"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let rune = SpacedRune::new(Rune(RUNE), 0);
+
+  let amount = Decimal {
+    value: 1000,
+    scale: 0,
+  };
+
+  let send = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet send --fee-rate 1 bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw 1000:{}",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let history = history(&core, &ord, "--regtest --index-runes");
+
+  assert_eq!(
+    entry(&history, etched.output.reveal).runes_received,
+    [(rune, amount)].into(),
+  );
+
+  assert_eq!(
+    entry(&history, send.txid).runes_sent,
+    [(rune, amount)].into()
+  );
+}
+
+#[test]
+fn history_can_be_exported_as_csv() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let (tempdir, stdout) = CommandBuilder::new("wallet history --csv history.csv")
+    .core(&core)
+    .ord(&ord)
+    .stdout_regex(".*")
+    .run();
+
+  let history = serde_json::from_str::<Vec<Output>>(&stdout).unwrap();
+
+  let csv = fs::read_to_string(tempdir.path().join("history.csv")).unwrap();
+
+  let mut lines = csv.lines();
+
+  assert_eq!(
+    lines.next().unwrap(),
+    "transaction,confirmations,timestamp,net,inscriptions_received,inscriptions_sent,mint,\
+     runes_received,runes_sent,runes_burned",
+  );
+
+  assert_eq!(lines.clone().count(), history.len());
+
+  let reveal = history
+    .iter()
+    .find(|output| output.transaction == reveal)
+    .unwrap();
+
+  assert!(lines.any(|line| line
+    == format!(
+      "{},{},{},{},{inscription},,,,,",
+      reveal.transaction, reveal.confirmations, reveal.timestamp, reveal.net,
+    )));
+}
+
+#[test]
+fn history_only_reports_mints_which_succeed() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-runes", "--index-rune-history", "--regtest"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let (block, tx) = core.tx_index(etched.output.reveal);
+
+  let mint = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(block, tx, 0, default()), (block, tx, 1, default())],
+    op_return: Some(
+      Runestone {
+        mint: Some(etched.id),
+        ..default()
+      }
+      .encipher(),
+    ),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let history = history(&core, &ord, "--regtest --index-runes");
+
+  let failed = entry(&history, mint);
+  assert_eq!(failed.mint, None);
+  assert!(failed.runes_burned.is_empty());
+}
+
+#[test]
+fn history_reports_mints() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-runes", "--index-rune-history", "--regtest"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        symbol: '¢',
+        supply: "100".parse().unwrap(),
+        terms: Some(batch::Terms {
+          cap: 1,
+          offset: None,
+          amount: "100".parse().unwrap(),
+          height: None,
+        }),
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ord::subcommand::wallet::mint::Output>();
+
+  core.mine_blocks(1);
+
+  let history = history(&core, &ord, "--regtest --index-runes");
+
+  let minted = entry(&history, output.mint);
+  assert_eq!(minted.mint, Some(SpacedRune::new(Rune(RUNE), 0)));
+  assert_eq!(
+    minted.runes_received,
+    [(
+      SpacedRune::new(Rune(RUNE), 0),
+      Decimal {
+        value: 100,
+        scale: 0
+      }
+    )]
+    .into(),
+  );
+  assert!(minted.runes_burned.is_empty());
+}
+
+#[test]
+fn history_reports_runes_burned_by_cenotaphs() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-runes", "--index-rune-history", "--regtest"],
+    &[],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let (block, tx) = core.tx_index(etched.output.reveal);
+
+  let cenotaph = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(block, tx, 0, default()), (block, tx, 1, default())],
+    op_return: Some(
+      Runestone {
+        edicts: vec![Edict {
+          id: etched.id,
+          amount: 0,
+          output: 5,
+        }],
+        ..default()
+      }
+      .encipher(),
+    ),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let history = history(&core, &ord, "--regtest --index-runes");
+
+  let burned = entry(&history, cenotaph);
+  assert!(burned.runes_sent.is_empty());
+  assert_eq!(
+    burned.runes_burned,
+    [(
+      SpacedRune::new(Rune(RUNE), 0),
+      Decimal {
+        value: 1000,
+        scale: 0
+      }
+    )]
+    .into(),
+  );
+}
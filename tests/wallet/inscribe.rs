@@ -458,6 +458,90 @@ fn inscribe_with_dry_run_flag() {
   assert_eq!(core.mempool().len(), 2);
 }
 
+#[test]
+fn inscribe_from_watch_only_wallet_returns_psbts() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_watch_only_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --file degenerate.png --fee-rate 1")
+    .write("degenerate.png", [1; 520])
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  assert!(core.mempool().is_empty());
+  assert!(!inscribe.reveal_broadcast);
+
+  let commit_psbt =
+    Psbt::deserialize(&base64_decode(&inscribe.commit_psbt.unwrap()).unwrap()).unwrap();
+
+  assert_eq!(commit_psbt.unsigned_tx.compute_txid(), inscribe.commit);
+  assert!(
+    commit_psbt
+      .inputs
+      .iter()
+      .all(|input| input.final_script_witness.is_none())
+  );
+
+  let reveal_psbt =
+    Psbt::deserialize(&base64_decode(&inscribe.reveal_psbt.unwrap()).unwrap()).unwrap();
+
+  assert_eq!(reveal_psbt.unsigned_tx.compute_txid(), inscribe.reveal);
+  assert_eq!(
+    reveal_psbt.inputs[0].witness_utxo,
+    Some(
+      commit_psbt.unsigned_tx.output
+        [usize::try_from(reveal_psbt.unsigned_tx.input[0].previous_output.vout).unwrap()]
+      .clone()
+    ),
+  );
+  assert_eq!(
+    reveal_psbt.inputs[0]
+      .final_script_witness
+      .as_ref()
+      .unwrap()
+      .len(),
+    3,
+  );
+}
+
+#[test]
+fn inscribe_with_psbt_out() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let (tempdir, stdout) = CommandBuilder::new(
+    "wallet inscribe --psbt-out commit.psbt --file degenerate.png --fee-rate 1",
+  )
+  .write("degenerate.png", [1; 520])
+  .core(&core)
+  .ord(&ord)
+  .stdout_regex(".*")
+  .run();
+
+  let inscribe = serde_json::from_str::<Batch>(&stdout).unwrap();
+
+  assert!(core.mempool().is_empty());
+
+  assert_eq!(
+    fs::read_to_string(tempdir.path().join("commit.psbt")).unwrap(),
+    inscribe.commit_psbt.unwrap(),
+  );
+
+  let reveal_psbt =
+    Psbt::deserialize(&base64_decode(&inscribe.reveal_psbt.unwrap()).unwrap()).unwrap();
+
+  assert!(reveal_psbt.inputs[0].final_script_witness.is_some());
+}
+
 #[test]
 fn inscribe_with_dry_run_flag_fees_increase() {
   let core = mockcore::spawn();
@@ -1,4 +1,8 @@
 use super::*;
 
 mod accept;
+mod buy;
+mod cancel;
 mod create;
+mod list;
+mod list_for_sale;
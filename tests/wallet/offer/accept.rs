@@ -633,3 +633,145 @@ fn seller_input_must_not_be_signed() {
   ))
   .run_and_extract_stdout();
 }
+
+#[test]
+fn accepted_rune_offer_works() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let seller_address = Address::from_script(
+    &core.tx_by_id(location.txid).output[usize::try_from(location.vout).unwrap()].script_pubkey,
+    Network::Regtest,
+  )
+  .unwrap();
+
+  core.state().remove_wallet_address(seller_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "--regtest wallet offer create --rune 400:{} --utxo {location} --amount 1btc --fee-rate 0",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  let buyer_addresses = core.state().clear_wallet_addresses();
+
+  core.state().add_wallet_address(seller_address.clone());
+
+  CommandBuilder::new(format!(
+    "--regtest wallet offer accept --rune 500:{} --amount 1btc --psbt {}",
+    Rune(RUNE),
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: unexpected outgoing 400 AAAAAAAAAAAAA\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--regtest wallet offer accept --rune 400:{} --amount 1btc --psbt {}",
+    Rune(RUNE),
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Accept>();
+
+  core.mine_blocks(1);
+
+  let balance = CommandBuilder::new("--regtest wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  assert_eq!(
+    balance.runes.unwrap(),
+    [(SpacedRune::new(Rune(RUNE), 0), "600".parse().unwrap())].into(),
+  );
+
+  core.state().remove_wallet_address(seller_address);
+
+  for address in buyer_addresses {
+    core.state().add_wallet_address(address);
+  }
+
+  let balance = CommandBuilder::new("--regtest wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  assert_eq!(
+    balance.runes.unwrap(),
+    [(SpacedRune::new(Rune(RUNE), 0), "400".parse().unwrap())].into(),
+  );
+}
+
+#[test]
+fn rune_offer_must_not_burn_seller_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let tx = Transaction {
+    version: Version(2),
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: location,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![
+      TxOut {
+        value: Amount::ZERO,
+        script_pubkey: Runestone {
+          edicts: vec![Edict {
+            id: etched.id,
+            amount: 400,
+            output: 1,
+          }],
+          pointer: Some(0),
+          ..default()
+        }
+        .encipher(),
+      },
+      TxOut {
+        value: Amount::from_sat(10_000),
+        script_pubkey: core.state().new_address(false).into(),
+      },
+    ],
+  };
+
+  CommandBuilder::new([
+    "--regtest",
+    "wallet",
+    "offer",
+    "accept",
+    "--rune",
+    &format!("400:{}", Rune(RUNE)),
+    "--amount",
+    "1btc",
+    "--psbt",
+    &base64_encode(&Psbt::from_unsigned_tx(tx).unwrap().serialize()),
+  ])
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: unexpected outgoing 1000 AAAAAAAAAAAAA\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+type Buy = ord::subcommand::wallet::offer::buy::Output;
+type ListForSale = ord::subcommand::wallet::offer::list_for_sale::Output;
+
+fn list_for_sale(
+  core: &mockcore::Handle,
+  ord: &TestServer,
+  postage: u64,
+) -> (InscriptionId, ListForSale) {
+  create_wallet(core, ord);
+
+  let (inscription, txid) = inscribe_with_options(core, ord, Some(postage), 0);
+
+  let listing = CommandBuilder::new(format!(
+    "wallet offer list-for-sale --inscription {inscription} --price 1btc"
+  ))
+  .core(core)
+  .ord(ord)
+  .run_and_deserialize_output::<ListForSale>();
+
+  let inscription_address = Address::from_script(
+    &core.tx_by_id(txid).output[0].script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  core.state().remove_wallet_address(inscription_address);
+
+  core
+    .state()
+    .remove_wallet_address(listing.seller_address.clone().assume_checked());
+
+  (inscription, listing)
+}
+
+#[test]
+fn bought_listing_works() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let postage = 9000;
+
+  let (inscription, listing) = list_for_sale(&core, &ord, postage);
+
+  let buy = CommandBuilder::new(format!(
+    "wallet offer buy --inscription {inscription} --price 1btc --fee-rate 1 --psbt {}",
+    listing.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Buy>();
+
+  core.mine_blocks(1);
+
+  let tx = core.tx_by_id(buy.txid);
+
+  assert_eq!(
+    tx.output[1],
+    TxOut {
+      value: Amount::from_sat(COIN_VALUE + postage),
+      script_pubkey: listing.seller_address.assume_checked().script_pubkey(),
+    },
+  );
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(inscriptions[0].location.outpoint.txid, buy.txid);
+  assert_eq!(inscriptions[0].location.outpoint.vout, 0);
+}
+
+#[test]
+fn listing_price_must_match() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (inscription, listing) = list_for_sale(&core, &ord, 9000);
+
+  CommandBuilder::new(format!(
+    "wallet offer buy --inscription {inscription} --price 2btc --fee-rate 1 --psbt {}",
+    listing.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: unexpected listing price of 1 BTC\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn listing_inscription_must_match() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  let (inscription, listing) = list_for_sale(&core, &ord, 9000);
+
+  let other = InscriptionId {
+    txid: inscription.txid,
+    index: 1,
+  };
+
+  CommandBuilder::new(format!(
+    "wallet offer buy --inscription {other} --price 1btc --fee-rate 1 --psbt {}",
+    listing.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: unexpected listing inscription {inscription}\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn sellers_cannot_buy_their_own_listings() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let listing = CommandBuilder::new(format!(
+    "wallet offer list-for-sale --inscription {inscription} --price 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ListForSale>();
+
+  CommandBuilder::new(format!(
+    "wallet offer buy --inscription {inscription} --price 1btc --fee-rate 1 --psbt {}",
+    listing.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: listing input `{txid}:0` is owned by wallet\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
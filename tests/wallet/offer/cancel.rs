@@ -0,0 +1,110 @@
+use super::*;
+
+type Cancel = ord::subcommand::wallet::offer::cancel::Output;
+type Create = ord::subcommand::wallet::offer::create::Output;
+
+#[test]
+fn cancelled_offers_are_pruned() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &["--accept-offers"]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let inscription_address = Address::from_script(
+    &core.tx_by_id(txid).output[0].script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  core
+    .state()
+    .remove_wallet_address(inscription_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --amount 1btc --fee-rate 1 --submit {}",
+    ord.url().join("offer").unwrap(),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  assert_eq!(
+    ord.json_request("/offers").json::<api::Offers>().unwrap(),
+    api::Offers {
+      offers: vec![create.psbt.clone()]
+    },
+  );
+
+  let cancel = CommandBuilder::new(format!(
+    "wallet offer cancel --fee-rate 1 --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Cancel>();
+
+  core.mine_blocks(1);
+
+  assert_eq!(core.tx_by_id(cancel.txid).output.len(), 1);
+
+  assert_eq!(
+    ord.json_request("/offers").json::<api::Offers>().unwrap(),
+    api::Offers { offers: Vec::new() },
+  );
+
+  assert_eq!(
+    ord
+      .json_request(format!("/offers/{inscription}"))
+      .json::<api::Offers>()
+      .unwrap(),
+    api::Offers { offers: Vec::new() },
+  );
+}
+
+#[test]
+fn sellers_cannot_cancel_offers() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let inscription_address = Address::from_script(
+    &core.tx_by_id(txid).output[0].script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  core
+    .state()
+    .remove_wallet_address(inscription_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --amount 1btc --fee-rate 1"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  core.state().clear_wallet_addresses();
+
+  core.state().add_wallet_address(inscription_address);
+
+  CommandBuilder::new(format!(
+    "wallet offer cancel --fee-rate 1 --psbt {}",
+    create.psbt
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: seller input `{}:0` is owned by wallet: only buyers can cancel offers\n",
+    txid
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -45,7 +45,7 @@ fn created_offer_is_correct() {
     address,
   );
 
-  assert_eq!(create.inscription, inscription);
+  assert_eq!(create.inscription, Some(inscription));
 
   let psbt = Psbt::deserialize(&base64_decode(&create.psbt).unwrap()).unwrap();
 
@@ -229,3 +229,104 @@ fn offers_can_be_submitted() {
     },
   );
 }
+
+#[test]
+fn created_rune_offer_is_correct() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let seller_output =
+    core.tx_by_id(location.txid).output[usize::try_from(location.vout).unwrap()].clone();
+
+  let seller_address =
+    Address::from_script(&seller_output.script_pubkey, Network::Regtest).unwrap();
+
+  core.state().remove_wallet_address(seller_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "--regtest wallet offer create --rune 400:{} --utxo {location} --amount 1btc --fee-rate 1",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  assert_eq!(
+    create
+      .seller_address
+      .require_network(Network::Regtest)
+      .unwrap(),
+    seller_address,
+  );
+
+  assert_eq!(create.inscription, None);
+
+  let psbt = Psbt::deserialize(&base64_decode(&create.psbt).unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.input[0].previous_output, location);
+
+  assert_eq!(
+    Runestone::decipher(&psbt.unsigned_tx),
+    Some(Artifact::Runestone(Runestone {
+      edicts: vec![Edict {
+        id: etched.id,
+        amount: 400,
+        output: 1,
+      }],
+      pointer: Some(2),
+      ..default()
+    })),
+  );
+
+  assert_eq!(
+    psbt.unsigned_tx.output[2],
+    TxOut {
+      value: seller_output.value + Amount::from_sat(COIN_VALUE),
+      script_pubkey: seller_output.script_pubkey,
+    },
+  );
+
+  assert!(core.state().is_wallet_address(
+    &Address::from_script(&psbt.unsigned_tx.output[1].script_pubkey, Network::Regtest).unwrap()
+  ));
+}
+
+#[test]
+fn rune_offer_utxo_must_contain_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let location = etched.output.rune.unwrap().location.unwrap();
+
+  let seller_address = Address::from_script(
+    &core.tx_by_id(location.txid).output[usize::try_from(location.vout).unwrap()].script_pubkey,
+    Network::Regtest,
+  )
+  .unwrap();
+
+  core.state().remove_wallet_address(seller_address);
+
+  CommandBuilder::new(format!(
+    "--regtest wallet offer create --rune 1001:{} --utxo {location} --amount 1btc --fee-rate 1",
+    Rune(RUNE),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: utxo {location} contains 1000 AAAAAAAAAAAAA, but offer is for 1001\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
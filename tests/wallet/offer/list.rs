@@ -0,0 +1,126 @@
+use super::*;
+
+type Create = ord::subcommand::wallet::offer::create::Output;
+type List = Vec<ord::subcommand::wallet::offer::list::Output>;
+
+#[test]
+fn offers_for_inscriptions_in_wallet_are_listed() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &["--accept-offers"]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let inscription_address = Address::from_script(
+    &core.tx_by_id(txid).output[0].script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  core
+    .state()
+    .remove_wallet_address(inscription_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --amount 1btc --fee-rate 0 --submit {}",
+    ord.url().join("offer").unwrap(),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  assert_eq!(
+    CommandBuilder::new("wallet offer list")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<List>(),
+    List::new(),
+  );
+
+  core.state().clear_wallet_addresses();
+
+  core.state().add_wallet_address(inscription_address);
+
+  assert_eq!(
+    CommandBuilder::new("wallet offer list")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<List>(),
+    vec![ord::subcommand::wallet::offer::list::Output {
+      amount: COIN_VALUE,
+      inscription,
+      psbt: create.psbt,
+    }],
+  );
+}
+
+#[test]
+fn offers_for_outputs_with_runes_are_reported() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(
+    &core,
+    &["--index-runes", "--regtest"],
+    &["--accept-offers"],
+  );
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let (block, tx) = core.tx_index(etched.output.reveal);
+
+  core.mine_blocks(1);
+
+  let seller_address = CommandBuilder::new("--regtest wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .require_network(Network::Regtest)
+    .unwrap();
+
+  let merge = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(block, tx, 0, default()), (block, tx, 1, default())],
+    recipient: Some(seller_address.clone()),
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  core.state().remove_wallet_address(seller_address.clone());
+
+  let create = CommandBuilder::new(format!(
+    "--regtest wallet offer create --inscription {} --amount 1btc --fee-rate 0 --submit {}",
+    etched.output.inscriptions[0].id,
+    ord.url().join("offer").unwrap(),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Create>();
+
+  core.state().clear_wallet_addresses();
+
+  core.state().add_wallet_address(seller_address);
+
+  let txid = Psbt::deserialize(&base64_decode(&create.psbt).unwrap())
+    .unwrap()
+    .unsigned_tx
+    .compute_txid();
+
+  assert_eq!(
+    CommandBuilder::new("--regtest wallet offer list")
+      .core(&core)
+      .ord(&ord)
+      .expected_stderr(format!(
+        "warning: skipping offer {txid}: seller input {merge}:0 contains runes\n"
+      ))
+      .run_and_deserialize_output::<List>(),
+    List::new(),
+  );
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+type ListForSale = ord::subcommand::wallet::offer::list_for_sale::Output;
+
+#[test]
+fn listing_is_signed_with_sighash_single_anyonecanpay() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let postage = 9000;
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(postage), 0);
+
+  let listing = CommandBuilder::new(format!(
+    "wallet offer list-for-sale --inscription {inscription} --price 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ListForSale>();
+
+  assert_eq!(listing.inscription, inscription);
+  assert_eq!(listing.price, COIN_VALUE);
+
+  let psbt = Psbt::deserialize(&base64_decode(&listing.psbt).unwrap()).unwrap();
+
+  assert_eq!(
+    psbt.unsigned_tx.input[0].previous_output,
+    OutPoint { txid, vout: 0 }
+  );
+
+  assert_eq!(
+    psbt.unsigned_tx.output,
+    vec![TxOut {
+      value: Amount::from_sat(COIN_VALUE + postage),
+      script_pubkey: listing.seller_address.assume_checked().script_pubkey(),
+    }],
+  );
+
+  let signature = psbt.inputs[0]
+    .final_script_witness
+    .as_ref()
+    .unwrap()
+    .nth(0)
+    .unwrap();
+
+  assert_eq!(signature.last(), Some(&0x83));
+}
+
+#[test]
+fn inscription_must_be_in_wallet() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, txid) = inscribe_with_options(&core, &ord, Some(9000), 0);
+
+  let inscription_address = Address::from_script(
+    &core.tx_by_id(txid).output[0].script_pubkey,
+    Network::Bitcoin,
+  )
+  .unwrap();
+
+  core.state().remove_wallet_address(inscription_address);
+
+  CommandBuilder::new(format!(
+    "wallet offer list-for-sale --inscription {inscription} --price 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!("error: inscription {inscription} not in wallet\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -311,3 +311,242 @@ fn offer_create_does_not_select_non_cardinal_utxos() {
   .expected_stderr("error: not enough cardinal utxos\n")
   .run_and_extract_stdout();
 }
+
+#[test]
+fn send_amount_does_not_select_protected_utxos() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet --protect-rarity uncommon send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 600sat",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: not enough cardinal utxos (1 output(s) containing protected sats were excluded, run `ord wallet extract` or pass `--protect-split` to split protected sats into their own outputs)\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn send_amount_selects_utxos_without_protected_sats() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet --protect-rarity mythic --protect-charm nineball send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 600sat",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+}
+
+#[test]
+fn inscribe_does_not_select_protected_utxos() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet --protect-charm coin inscribe --fee-rate 1 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: wallet contains no cardinal utxos (1 output(s) containing protected sats were excluded, run `ord wallet extract` or pass `--protect-split` to split protected sats into their own outputs)\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn protecting_sats_requires_sat_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet --protect-rarity rare send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 600sat",
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: ord index must be built with `--index-sats` to protect sats\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn only_sat_charms_can_be_protected() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet --protect-charm cursed balance")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: cannot protect cursed sats, since cursed is not a property of sats\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn protect_split_splits_protected_sats_before_running_command() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let address = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .assume_checked();
+
+  CommandBuilder::new(format!(
+    "--index-sats wallet --protect-rarity uncommon send --fee-rate 1 {address} 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet --protect-rarity uncommon --protect-split 1 inscribe --fee-rate 1 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .core(&core)
+  .ord(&ord)
+  .stderr_regex("Split protected sats into their own outputs in transaction [[:xdigit:]]{64}\n.*")
+  .run_and_deserialize_output::<Batch>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 3);
+
+  let split = mempool
+    .iter()
+    .find(|tx| {
+      tx.output
+        .iter()
+        .filter(|output| output.value.to_sat() == 10_000)
+        .count()
+        == 2
+    })
+    .unwrap();
+
+  assert_eq!(split.input.len(), 2);
+
+  let mut inputs = BTreeSet::new();
+
+  for tx in &mempool {
+    for input in &tx.input {
+      assert!(inputs.insert(input.previous_output));
+    }
+  }
+}
+
+#[test]
+fn protect_split_frees_change_when_every_output_is_protected() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--index-sats wallet --protect-rarity uncommon inscribe --fee-rate 1 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .stderr_regex(".*pass `--protect-split`.*")
+  .run_and_extract_stdout();
+
+  let batch = CommandBuilder::new(
+    "--index-sats wallet --protect-rarity uncommon --protect-split 1 inscribe --fee-rate 1 --file foo.txt",
+  )
+  .write("foo.txt", "FOO")
+  .core(&core)
+  .ord(&ord)
+  .stderr_regex("Split protected sats into their own outputs in transaction [[:xdigit:]]{64}\n")
+  .run_and_deserialize_output::<Batch>();
+
+  let mempool = core.mempool();
+
+  assert_eq!(mempool.len(), 3);
+
+  let split = &mempool[0];
+
+  assert_eq!(split.input.len(), 1);
+  assert_eq!(split.output.len(), 2);
+  assert_eq!(split.output[0].value.to_sat(), 10_000);
+
+  let commit = mempool
+    .iter()
+    .find(|tx| tx.compute_txid() == batch.commit)
+    .unwrap();
+
+  assert_eq!(
+    commit
+      .input
+      .iter()
+      .map(|input| input.previous_output)
+      .collect::<Vec<OutPoint>>(),
+    [OutPoint {
+      txid: split.compute_txid(),
+      vout: 1,
+    }],
+  );
+}
+
+#[test]
+fn protect_split_requires_protection() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("--index-sats wallet --protect-split 1 balance")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: `--protect-split` requires `--protect-rarity` or `--protect-charm`\n")
+    .run_and_extract_stdout();
+}
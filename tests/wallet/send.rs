@@ -667,6 +667,36 @@ fn send_dry_run() {
   assert_eq!(output.asset, Outgoing::InscriptionId(inscription));
 }
 
+#[test]
+fn send_from_watch_only_wallet_returns_unsigned_psbt() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_watch_only_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+
+  let psbt = Psbt::deserialize(&base64_decode(&output.psbt).unwrap()).unwrap();
+
+  assert_eq!(psbt.unsigned_tx.compute_txid(), output.txid);
+  assert_eq!(psbt.fee().unwrap().to_sat(), output.fee);
+  assert!(
+    psbt
+      .inputs
+      .iter()
+      .all(|input| input.final_script_witness.is_none())
+  );
+}
+
 #[test]
 fn sending_rune_that_has_not_been_etched_is_an_error() {
   let core = mockcore::builder().network(Network::Regtest).build();